@@ -100,10 +100,13 @@ impl<'a> Asg<'a> {
     }
 }
 
-pub fn new_alloc_context<'a>() -> Arena<ArenaNode<'a>> {
+/// The arena that all nodes of an ASG are allocated in.
+pub type AsgArena<'a> = Arena<ArenaNode<'a>>;
+
+pub fn new_alloc_context<'a>() -> AsgArena<'a> {
     Arena::new()
 }
 
-pub fn new_context<'a>(arena: &'a Arena<ArenaNode<'a>>) -> AsgContext<'a> {
+pub fn new_context<'a>(arena: &'a AsgArena<'a>) -> AsgContext<'a> {
     AsgContextInner::new(arena)
 }
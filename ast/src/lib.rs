@@ -111,6 +111,15 @@ impl Ast {
         Ok(serde_json::to_value(&self.ast).map_err(|e| AstError::failed_to_convert_ast_to_json_value(&e))?)
     }
 
+    /// Converts the ast into a JSON value and removes keys from object mappings.
+    pub fn to_json_value_without_keys(&self, excluded_keys: &[&str]) -> Result<serde_json::Value> {
+        let mut value = self.to_json_value()?;
+        for key in excluded_keys {
+            value = remove_key_from_json(value, key);
+        }
+        Ok(value)
+    }
+
     /// Serializes the ast into a JSON file.
     pub fn to_json_file(&self, mut path: std::path::PathBuf, file_name: &str) -> Result<()> {
         path.push(file_name);
@@ -131,11 +140,7 @@ impl Ast {
         let file = std::fs::File::create(&path).map_err(|e| AstError::failed_to_create_ast_json_file(&path, &e))?;
         let writer = std::io::BufWriter::new(file);
 
-        let mut value = self.to_json_value().unwrap();
-        for key in excluded_keys {
-            value = remove_key_from_json(value, key);
-        }
-        value = normalize_json_value(value);
+        let value = normalize_json_value(self.to_json_value_without_keys(excluded_keys)?);
 
         Ok(serde_json::to_writer_pretty(writer, &value)
             .map_err(|e| AstError::failed_to_write_ast_to_json_file(&path, &e))?)
@@ -17,13 +17,17 @@
 //! Compiles a Leo program from a file path.
 use crate::{
     constraints::{generate_constraints, generate_test_constraints},
-    AstSnapshotOptions, CompilerOptions, GroupType, Output, OutputFile, TypeInferencePhase,
+    AstSnapshotOptions, CompilerOptions, GroupType, Output, TypeInferencePhase,
 };
 pub use leo_asg::{new_context, AsgContext as Context, AsgContext};
-use leo_asg::{Asg, AsgPass, Program as AsgProgram};
-use leo_ast::{AstPass, Input, MainInput, Program as AstProgram};
-use leo_errors::{CompilerError, Result};
-use leo_imports::ImportParser;
+use leo_asg::{Asg, AsgArena, AsgPass, Program as AsgProgram};
+use leo_ast::{
+    Alias, AstPass, Circuit, DefinitionStatement, Function, FunctionInput, Identifier, ImportStatement, ImportSymbol,
+    Input, MainInput, Package, PackageAccess, PackageOrPackages, Packages, Program as AstProgram,
+    ReconstructingDirector, ReconstructingReducer,
+};
+use leo_errors::{CompilerError, LeoError, Result, SendableLeoError};
+use leo_imports::{FileSystem, ImportParser, SourceProvider};
 use leo_input::LeoInputParser;
 use leo_package::inputs::InputPairs;
 use leo_parser::parse_ast;
@@ -35,9 +39,10 @@ use snarkvm_r1cs::{ConstraintSynthesizer, ConstraintSystem, SynthesisError};
 
 use sha2::{Digest, Sha256};
 use std::{
-    fs,
+    io,
     marker::PhantomData,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
 use indexmap::IndexMap;
@@ -54,6 +59,34 @@ pub fn thread_leaked_context() -> AsgContext<'static> {
     THREAD_GLOBAL_CONTEXT.with(|f| *f)
 }
 
+/// Owns the arena that a compilation allocates its ASG in.
+///
+/// Unlike `thread_leaked_context()`, the memory is freed when the session is dropped,
+/// so long-running processes should compile each program in a new session.
+/// Compilers created from the session's context borrow the session.
+#[derive(Default)]
+pub struct CompilerSession<'a> {
+    arena: AsgArena<'a>,
+}
+
+impl<'a> CompilerSession<'a> {
+    ///
+    /// Returns a new session with an empty arena.
+    ///
+    pub fn new() -> Self {
+        Self {
+            arena: leo_asg::new_alloc_context(),
+        }
+    }
+
+    ///
+    /// Returns a new context allocating into this session's arena.
+    ///
+    pub fn context(&'a self) -> AsgContext<'a> {
+        leo_asg::new_context(&self.arena)
+    }
+}
+
 /// Stores information to compile a Leo program.
 #[derive(Clone)]
 pub struct Compiler<'a, F: PrimeField, G: GroupType<F>> {
@@ -67,6 +100,7 @@ pub struct Compiler<'a, F: PrimeField, G: GroupType<F>> {
     options: CompilerOptions,
     imports_map: IndexMap<String, String>,
     ast_snapshot_options: AstSnapshotOptions,
+    source_provider: Arc<dyn SourceProvider>,
     _engine: PhantomData<F>,
     _group: PhantomData<G>,
 }
@@ -95,6 +129,7 @@ impl<'a, F: PrimeField, G: GroupType<F>> Compiler<'a, F, G> {
             options: options.unwrap_or_default(),
             imports_map,
             ast_snapshot_options: ast_snapshot_options.unwrap_or_default(),
+            source_provider: Arc::new(FileSystem),
             _engine: PhantomData,
             _group: PhantomData,
         }
@@ -135,6 +170,15 @@ impl<'a, F: PrimeField, G: GroupType<F>> Compiler<'a, F, G> {
         self.options = options;
     }
 
+    ///
+    /// Sets where the main file and imported packages are read from.
+    ///
+    /// Defaults to the local filesystem.
+    ///
+    pub fn set_source_provider(&mut self, source_provider: Arc<dyn SourceProvider>) {
+        self.source_provider = source_provider;
+    }
+
     ///
     /// Returns a new `Compiler` from the given main file path.
     ///
@@ -230,7 +274,9 @@ impl<'a, F: PrimeField, G: GroupType<F>> Compiler<'a, F, G> {
     ///
     pub fn parse_program(&mut self) -> Result<()> {
         // Load the program file.
-        let content = fs::read_to_string(&self.main_file_path)
+        let content = self
+            .source_provider
+            .read_file(&self.main_file_path)
             .map_err(|e| CompilerError::file_read_error(self.main_file_path.clone(), e))?;
 
         self.parse_program_from_string(&content)
@@ -256,7 +302,11 @@ impl<'a, F: PrimeField, G: GroupType<F>> Compiler<'a, F, G> {
         // Preform import resolution.
         ast = leo_ast_passes::Importer::do_pass(
             ast.into_repr(),
-            &mut ImportParser::new(self.main_file_path.clone(), self.imports_map.clone()),
+            &mut ImportParser::with_source_provider(
+                self.main_file_path.clone(),
+                self.imports_map.clone(),
+                self.source_provider.clone(),
+            ),
         )?;
 
         if self.ast_snapshot_options.imports_resolved {
@@ -349,16 +399,23 @@ impl<'a, F: PrimeField, G: GroupType<F>> Compiler<'a, F, G> {
     }
 
     ///
-    /// Returns a SHA256 checksum of the program file.
+    /// Returns a SHA256 checksum of the import-resolved program and the compiler options.
+    ///
+    /// Spans are excluded, so the checksum only changes when the program itself changes,
+    /// including any file or package it imports.
     ///
     pub fn checksum(&self) -> Result<String> {
-        // Read in the main file as string
-        let unparsed_file = fs::read_to_string(&self.main_file_path)
-            .map_err(|e| CompilerError::file_read_error(self.main_file_path.clone(), e))?;
+        // Identifiers serialize their span inside a string, so they are cleared before serializing
+        let program = ReconstructingDirector::new(IdentifierSpanEraser).reduce_program(&self.program)?;
+        let program = leo_ast::Ast::new(program).to_json_value_without_keys(&["span"])?;
+        let options = serde_json::json!({
+            "constant_folding_enabled": self.options.constant_folding_enabled,
+            "dead_code_elimination_enabled": self.options.dead_code_elimination_enabled,
+        });
 
-        // Hash the file contents
         let mut hasher = Sha256::new();
-        hasher.update(unparsed_file.as_bytes());
+        hasher.update(program.to_string().as_bytes());
+        hasher.update(options.to_string().as_bytes());
         let hash = hasher.finalize();
 
         Ok(format!("{:x}", hash))
@@ -390,23 +447,127 @@ impl<'a, F: PrimeField, G: GroupType<F>> ConstraintSynthesizer<F> for Compiler<'
     /// Synthesizes the circuit with program input.
     ///
     fn generate_constraints<CS: ConstraintSystem<F>>(&self, cs: &mut CS) -> Result<(), SynthesisError> {
-        let output_directory = self.output_directory.clone();
-        let package_name = self.program_name.clone();
+        self.compile_constraints(cs)
+            .map(|_| ())
+            .map_err(|error| SynthesisError::IoError(io::Error::other(SendableLeoError::new(error))))
+    }
+}
 
-        let result = match self.compile_constraints(cs) {
-            Err(err) => {
-                eprintln!("{}", err);
-                std::process::exit(err.exit_code())
-            }
-            Ok(result) => result,
-        };
+///
+/// Returns the Leo error that made `generate_constraints()` fail, if the given error came from it.
+///
+pub fn synthesis_leo_error(error: SynthesisError) -> Option<LeoError> {
+    match error {
+        SynthesisError::IoError(error) => SendableLeoError::from_io_error(error),
+        _ => None,
+    }
+}
 
-        // Write results to file
-        let output_file = OutputFile::new(&package_name);
-        output_file
-            .write(&output_directory, result.to_string().as_bytes())
-            .unwrap();
+///
+/// Clears the span of every identifier in the program, including the identifiers that key its
+/// definitions and name its imported symbols.
+///
+struct IdentifierSpanEraser;
+
+impl IdentifierSpanEraser {
+    fn erase_package_access(&mut self, access: &PackageAccess) -> PackageAccess {
+        match access {
+            PackageAccess::Star { span } => PackageAccess::Star { span: span.clone() },
+            PackageAccess::SubPackage(package) => PackageAccess::SubPackage(Box::new(Package {
+                name: Identifier::new(package.name.name.clone()),
+                access: self.erase_package_access(&package.access),
+                span: package.span.clone(),
+            })),
+            PackageAccess::Symbol(symbol) => PackageAccess::Symbol(ImportSymbol {
+                symbol: Identifier::new(symbol.symbol.name.clone()),
+                alias: symbol.alias.as_ref().map(|alias| Identifier::new(alias.name.clone())),
+                span: symbol.span.clone(),
+            }),
+            PackageAccess::Multiple(packages) => PackageAccess::Multiple(Packages {
+                name: Identifier::new(packages.name.name.clone()),
+                accesses: packages
+                    .accesses
+                    .iter()
+                    .map(|access| self.erase_package_access(access))
+                    .collect(),
+                span: packages.span.clone(),
+            }),
+        }
+    }
+}
 
-        Ok(())
+impl ReconstructingReducer for IdentifierSpanEraser {
+    fn in_circuit(&self) -> bool {
+        false
+    }
+
+    fn swap_in_circuit(&mut self) {}
+
+    fn reduce_identifier(&mut self, identifier: &Identifier) -> Result<Identifier> {
+        Ok(Identifier::new(identifier.name.clone()))
+    }
+
+    fn reduce_package_or_packages(
+        &mut self,
+        _package_or_packages: &PackageOrPackages,
+        new: PackageOrPackages,
+    ) -> Result<PackageOrPackages> {
+        Ok(match new {
+            PackageOrPackages::Package(package) => PackageOrPackages::Package(Package {
+                access: self.erase_package_access(&package.access),
+                ..package
+            }),
+            PackageOrPackages::Packages(packages) => PackageOrPackages::Packages(Packages {
+                accesses: packages
+                    .accesses
+                    .iter()
+                    .map(|access| self.erase_package_access(access))
+                    .collect(),
+                ..packages
+            }),
+        })
+    }
+
+    fn reduce_program(
+        &mut self,
+        program: &AstProgram,
+        expected_input: Vec<FunctionInput>,
+        import_statements: Vec<ImportStatement>,
+        imports: IndexMap<Vec<String>, AstProgram>,
+        aliases: IndexMap<Identifier, Alias>,
+        circuits: IndexMap<Identifier, Circuit>,
+        functions: IndexMap<Identifier, Function>,
+        global_consts: IndexMap<Vec<Identifier>, DefinitionStatement>,
+    ) -> Result<AstProgram> {
+        let erase = |identifier: Identifier| Identifier::new(identifier.name);
+
+        Ok(AstProgram {
+            name: program.name.clone(),
+            expected_input,
+            import_statements,
+            imports,
+            aliases: aliases
+                .into_iter()
+                .map(|(name, alias)| {
+                    let alias = Alias {
+                        name: erase(alias.name),
+                        ..alias
+                    };
+                    (erase(name), alias)
+                })
+                .collect(),
+            circuits: circuits
+                .into_iter()
+                .map(|(name, circuit)| (erase(name), circuit))
+                .collect(),
+            functions: functions
+                .into_iter()
+                .map(|(name, function)| (erase(name), function))
+                .collect(),
+            global_consts: global_consts
+                .into_iter()
+                .map(|(names, definition)| (names.into_iter().map(erase).collect(), definition))
+                .collect(),
+        })
     }
 }
@@ -17,10 +17,11 @@
 use std::{
     fs,
     path::{Path, PathBuf},
+    sync::Arc,
 };
 
-use leo_asg::*;
 use leo_errors::Result;
+use leo_imports::MemoryFileSystem;
 
 use leo_synthesizer::{CircuitSynthesizer, SerializedCircuit, SummarizedCircuit};
 use leo_test_framework::{
@@ -30,19 +31,21 @@ use leo_test_framework::{
 use serde_yaml::Value;
 use snarkvm_curves::{bls12_377::Bls12_377, edwards_bls12::Fq};
 
-use crate::{compiler::Compiler, targets::edwards_bls12::EdwardsGroupType, AstSnapshotOptions, Output};
+use crate::{
+    compiler::{synthesis_leo_error, Compiler, CompilerSession},
+    targets::edwards_bls12::EdwardsGroupType,
+    AstSnapshotOptions, Output,
+};
 use indexmap::IndexMap;
 
-pub type EdwardsTestCompiler = Compiler<'static, Fq, EdwardsGroupType>;
+pub type EdwardsTestCompiler<'a> = Compiler<'a, Fq, EdwardsGroupType>;
 // pub type EdwardsConstrainedValue = ConstrainedValue<'static, Fq, EdwardsGroupType>;
 
-//convenience function for tests, leaks memory
-pub(crate) fn make_test_context() -> AsgContext<'static> {
-    let allocator = Box::leak(Box::new(new_alloc_context()));
-    new_context(allocator)
-}
-
-fn new_compiler(path: PathBuf, theorem_options: Option<AstSnapshotOptions>) -> EdwardsTestCompiler {
+fn new_compiler<'a>(
+    session: &'a CompilerSession<'a>,
+    path: PathBuf,
+    theorem_options: Option<AstSnapshotOptions>,
+) -> EdwardsTestCompiler<'a> {
     let program_name = "test".to_string();
     let output_dir = PathBuf::from("/tmp/output/");
     fs::create_dir_all(output_dir.clone()).unwrap();
@@ -51,7 +54,7 @@ fn new_compiler(path: PathBuf, theorem_options: Option<AstSnapshotOptions>) -> E
         program_name,
         path,
         output_dir,
-        make_test_context(),
+        session.context(),
         None,
         IndexMap::new(),
         theorem_options,
@@ -68,12 +71,13 @@ fn hash_file(path: &str) -> String {
     format!("{:x}", hash)
 }
 
-pub(crate) fn parse_program(
+pub(crate) fn parse_program<'a>(
+    session: &'a CompilerSession<'a>,
     program_string: &str,
     theorem_options: Option<AstSnapshotOptions>,
     cwd: Option<PathBuf>,
-) -> Result<EdwardsTestCompiler> {
-    let mut compiler = new_compiler(cwd.unwrap_or_else(|| "compiler-test".into()), theorem_options);
+) -> Result<EdwardsTestCompiler<'a>> {
+    let mut compiler = new_compiler(session, cwd.unwrap_or_else(|| "compiler-test".into()), theorem_options);
 
     compiler.parse_program_from_string(program_string)?;
 
@@ -115,7 +119,9 @@ impl Namespace for CompileNamespace {
         });
         // .unwrap_or(test.path.clone());
 
+        let session = CompilerSession::new();
         let parsed = parse_program(
+            &session,
             &test.content,
             Some(AstSnapshotOptions {
                 spans_enabled: false,
@@ -188,7 +194,7 @@ impl Namespace for CompileNamespace {
                 .map_err(|x| x.to_string())?;
             let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
             let output = parsed.compile_constraints(&mut cs).map_err(|x| x.to_string())?;
-            let circuit: SummarizedCircuit = SerializedCircuit::from(cs).into();
+            let circuit = SummarizedCircuit::from(&cs);
 
             if circuit.num_constraints == 0 {
                 return Err(
@@ -251,3 +257,376 @@ impl Runner for TestRunner {
 pub fn compiler_tests() {
     leo_test_framework::run_tests(&TestRunner, "compiler");
 }
+
+#[test]
+pub fn test_in_memory_package() {
+    let mut sources = MemoryFileSystem::new();
+    sources
+        .insert(
+            "/package/src/main.leo",
+            "import circuits.Point;
+import dependency.double;
+
+function main(a: u32) -> u32 {
+    let p = Point { x: a, y: 1u32 };
+    return double(p.x + p.y);
+}
+",
+        )
+        .insert(
+            "/package/src/circuits.leo",
+            "circuit Point {
+    x: u32,
+    y: u32,
+}
+",
+        )
+        .insert(
+            "/package/imports/dependency/src/main.leo",
+            "function double(x: u32) -> u32 {
+    return x * 2u32;
+}
+",
+        );
+
+    let session = CompilerSession::new();
+    let mut compiler = new_compiler(&session, "/package/src/main.leo".into(), None);
+    compiler.set_source_provider(Arc::new(sources.clone()));
+    compiler.parse_program().unwrap();
+    compiler.checksum().unwrap();
+
+    // Imports are only resolved through the provider.
+    sources.remove("/package/imports/dependency/src/main.leo");
+    let mut compiler = new_compiler(&session, "/package/src/main.leo".into(), None);
+    compiler.set_source_provider(Arc::new(sources));
+    assert!(compiler.parse_program().is_err());
+}
+
+#[test]
+pub fn test_in_memory_library_import() {
+    let mut sources = MemoryFileSystem::new();
+    sources
+        .insert(
+            "/package/src/main.leo",
+            "import gadgets.double;
+
+function main(a: u32) -> u32 {
+    return double(a);
+}
+",
+        )
+        .insert(
+            "/package/imports/gadgets/src/lib.leo",
+            "function double(x: u32) -> u32 {
+    return x * 2u32;
+}
+",
+        );
+
+    // Libraries without a main file are imported through their lib file.
+    let session = CompilerSession::new();
+    let mut compiler = new_compiler(&session, "/package/src/main.leo".into(), None);
+    compiler.set_source_provider(Arc::new(sources));
+    compiler.parse_program().unwrap();
+}
+
+#[test]
+pub fn test_checksum_includes_imports() {
+    let checksum = |main: &str, dependency: &str| {
+        let mut sources = MemoryFileSystem::new();
+        sources
+            .insert("/package/src/main.leo", main)
+            .insert("/package/imports/dependency/src/main.leo", dependency);
+
+        let session = CompilerSession::new();
+        let mut compiler = new_compiler(&session, "/package/src/main.leo".into(), None);
+        compiler.set_source_provider(Arc::new(sources));
+        compiler.parse_program().unwrap();
+        compiler.checksum().unwrap()
+    };
+
+    let main = "import dependency.double;
+
+function main(a: u32) -> u32 {
+    return double(a);
+}
+";
+    let dependency = "function double(x: u32) -> u32 {
+    return x * 2u32;
+}
+";
+
+    let original = checksum(main, dependency);
+
+    // Formatting changes do not change the program.
+    assert_eq!(original, checksum(&format!("\n{}", main), dependency));
+    assert_eq!(original, checksum(main, &format!("\n{}", dependency)));
+
+    // Renaming a variable does.
+    let renamed = main.replace("(a: u32)", "(b: u32)").replace("double(a)", "double(b)");
+    assert_ne!(original, checksum(&renamed, dependency));
+
+    // Changes to an imported package do.
+    assert_ne!(original, checksum(main, &dependency.replace("2u32", "3u32")));
+}
+
+#[test]
+pub fn test_synthesis_error_is_returned() {
+    use snarkvm_r1cs::{ConstraintSynthesizer, SynthesisError};
+
+    let program = "function main(a: u32) {
+    console.assert(a == 1u32);
+}
+";
+    let session = CompilerSession::new();
+    let mut compiler = parse_program(&session, program, None, None).unwrap();
+    compiler
+        .parse_input(
+            "[main]\na: u32 = 2;\n\n[registers]\n",
+            Path::new("input"),
+            "",
+            Path::new("state"),
+        )
+        .unwrap();
+
+    let mut cs: CircuitSynthesizer<Bls12_377> = Default::default();
+    let result = compiler.generate_constraints(&mut cs);
+    let error = match result {
+        Err(error @ SynthesisError::IoError(_)) => error,
+        _ => panic!("expected a synthesis error"),
+    };
+
+    let error = synthesis_leo_error(error).expect("the original error is kept");
+    assert!(error.to_string().contains("console.assert"), "{}", error);
+    assert!(error.span().is_some_and(|span| span.content.contains("console.assert")));
+}
+
+#[test]
+pub fn test_constraint_profile() {
+    use snarkvm_r1cs::ConstraintSystem;
+
+    let program = "function double(x: u32) -> u32 {
+    return x * 2u32;
+}
+
+function main(a: u32, b: u32) -> u32 {
+    let c = a + b;
+    return double(c);
+}
+";
+    let session = CompilerSession::new();
+    let mut compiler = parse_program(&session, program, None, None).unwrap();
+    compiler
+        .parse_input(
+            "[main]\na: u32 = 1;\nb: u32 = 2;\n\n[registers]\nr0: u32 = 0;\n",
+            Path::new("input"),
+            "",
+            Path::new("state"),
+        )
+        .unwrap();
+
+    let mut cs = CircuitSynthesizer::<Bls12_377> {
+        profile: Some(Default::default()),
+        ..Default::default()
+    };
+    compiler.compile_constraints(&mut cs).unwrap();
+    let profile = cs.profile.take().unwrap();
+
+    // Every constraint is attributed to exactly one function.
+    assert_eq!(profile.total().constraints, cs.num_constraints());
+    let functions = profile.by_function();
+    let total: usize = functions.iter().map(|(_, cost)| cost.constraints).sum();
+    assert_eq!(total, cs.num_constraints());
+    assert!(functions
+        .iter()
+        .any(|(name, cost)| name == "main" && cost.constraints > 0));
+    assert!(functions
+        .iter()
+        .any(|(name, cost)| name == "double" && cost.constraints > 0));
+
+    let statements = profile.by_statement();
+    assert!(statements.iter().any(|(key, _)| key == "main 6:5"));
+    assert!(statements.iter().any(|(key, _)| key == "double 2:5"));
+    assert!(profile.by_line().iter().any(|(key, _)| key == "double 2"));
+
+    let folded = profile.to_folded_stacks();
+    assert!(folded.lines().all(|line| line.starts_with("main;")));
+    assert!(folded
+        .lines()
+        .any(|line| line.contains(";statement 7:5;function call double 7:12;")));
+    let folded_total: usize = folded
+        .lines()
+        .map(|line| line.rsplit(' ').next().unwrap().parse::<usize>().unwrap())
+        .sum();
+    assert_eq!(folded_total, cs.num_constraints());
+}
+
+#[test]
+pub fn test_binary_circuit_round_trip() {
+    use leo_synthesizer::is_binary_circuit;
+    use snarkvm_r1cs::ConstraintSystem;
+
+    let program = "function main(a: u32, b: u32) -> u32 {
+    return a * b + 1u32;
+}
+";
+    let session = CompilerSession::new();
+    let mut compiler = parse_program(&session, program, None, None).unwrap();
+    compiler
+        .parse_input(
+            "[main]\na: u32 = 3;\nb: u32 = 5;\n\n[registers]\nr0: u32 = 0;\n",
+            Path::new("input"),
+            "",
+            Path::new("state"),
+        )
+        .unwrap();
+
+    let mut cs = CircuitSynthesizer::<Bls12_377>::default();
+    compiler.compile_constraints(&mut cs).unwrap();
+
+    let mut bytes = Vec::new();
+    cs.write_binary(&mut bytes).unwrap();
+    let summary = SummarizedCircuit::from(&cs);
+    let json = SerializedCircuit::from(cs).to_json_string().unwrap();
+    assert!(bytes.len() < json.len());
+
+    // The circuit is summarized the same whether or not it was serialized.
+    assert!(summary == SummarizedCircuit::from(SerializedCircuit::from_json_string(&json).unwrap()));
+
+    // The binary encoding is recognized, and the JSON encoding is not.
+    assert!(is_binary_circuit(&mut bytes.as_slice()).unwrap());
+    assert!(!is_binary_circuit(&mut json.as_bytes()).unwrap());
+
+    let decoded = CircuitSynthesizer::<Bls12_377>::read_binary(bytes.as_slice()).unwrap();
+    assert!(decoded.num_constraints() > 0);
+    assert_eq!(decoded.which_is_unsatisfied(), None);
+    assert_eq!(SerializedCircuit::from(decoded).to_json_string().unwrap(), json);
+
+    // A truncated stream is an error rather than a partial circuit.
+    assert!(CircuitSynthesizer::<Bls12_377>::read_binary(&bytes[..bytes.len() / 2]).is_err());
+}
+
+#[test]
+pub fn test_interchange_formats_round_trip() {
+    use snarkvm_r1cs::ConstraintSystem;
+    use std::convert::TryFrom;
+
+    let program = "function main(a: u32, b: u32) -> u32 {
+    return a * b + 1u32;
+}
+";
+    let session = CompilerSession::new();
+    let mut compiler = parse_program(&session, program, None, None).unwrap();
+    compiler
+        .parse_input(
+            "[main]\na: u32 = 3;\nb: u32 = 5;\n\n[registers]\nr0: u32 = 0;\n",
+            Path::new("input"),
+            "",
+            Path::new("state"),
+        )
+        .unwrap();
+
+    let mut cs = CircuitSynthesizer::<Bls12_377>::default();
+    compiler.compile_constraints(&mut cs).unwrap();
+    assert!(cs.num_constraints() > 0);
+
+    let (mut r1cs, mut wtns, mut zkif) = (Vec::new(), Vec::new(), Vec::new());
+    cs.write_r1cs(&mut r1cs).unwrap();
+    cs.write_wtns(&mut wtns).unwrap();
+    cs.write_zkif(&mut zkif).unwrap();
+    assert!(r1cs.starts_with(b"r1cs"));
+    assert!(wtns.starts_with(b"wtns"));
+    let json = SerializedCircuit::from(cs).to_json_string().unwrap();
+
+    // Both exports read back to the circuit the existing deserializer reads from JSON.
+    let expected =
+        CircuitSynthesizer::<Bls12_377>::try_from(SerializedCircuit::from_json_string(&json).unwrap()).unwrap();
+    let expected = SerializedCircuit::from(expected).to_json_string().unwrap();
+    let circom = CircuitSynthesizer::<Bls12_377>::read_circom(r1cs.as_slice(), wtns.as_slice()).unwrap();
+    assert_eq!(circom.which_is_unsatisfied(), None);
+    assert_eq!(SerializedCircuit::from(circom).to_json_string().unwrap(), expected);
+    let zkinterface = CircuitSynthesizer::<Bls12_377>::read_zkif(zkif.as_slice()).unwrap();
+    assert_eq!(zkinterface.which_is_unsatisfied(), None);
+    assert_eq!(SerializedCircuit::from(zkinterface).to_json_string().unwrap(), expected);
+
+    // The reference implementation of zkInterface accepts the export, and the circuit is satisfied in it.
+    let mut messages = zkinterface::Reader::new();
+    messages.read_from(zkif.as_slice()).unwrap();
+    let messages = zkinterface::Messages::from(&messages);
+    let mut validator = zkinterface::consumers::validator::Validator::new_as_prover();
+    let mut simulator = zkinterface::consumers::simulator::Simulator::default();
+    for header in &messages.circuit_headers {
+        validator.ingest_header(header);
+        simulator.ingest_header(header).unwrap();
+    }
+    for witness in &messages.witnesses {
+        validator.ingest_witness(witness);
+        simulator.ingest_witness(witness).unwrap();
+    }
+    for constraint_system in &messages.constraint_systems {
+        validator.ingest_constraint_system(constraint_system);
+        simulator.ingest_constraint_system(constraint_system).unwrap();
+    }
+    assert_eq!(validator.get_violations(), Vec::<String>::new());
+    assert_eq!(simulator.get_violations(), Vec::<String>::new());
+
+    // Truncated exports are rejected.
+    assert!(CircuitSynthesizer::<Bls12_377>::read_circom(r1cs.as_slice(), &wtns[..wtns.len() - 32]).is_err());
+    assert!(CircuitSynthesizer::<Bls12_377>::read_zkif(&zkif[..zkif.len() - 1]).is_err());
+}
+
+#[test]
+pub fn test_circuit_shape_and_witness() {
+    use leo_synthesizer::SerializedWitness;
+    use std::convert::TryFrom;
+
+    let program = "function main(a: u32, b: u32) -> u32 {
+    return a * b + 1u32;
+}
+";
+    let synthesize = |input: &str| {
+        let session = CompilerSession::new();
+        let mut compiler = parse_program(&session, program, None, None).unwrap();
+        compiler
+            .parse_input(input, Path::new("input"), "", Path::new("state"))
+            .unwrap();
+        let mut cs = CircuitSynthesizer::<Bls12_377>::default();
+        compiler.compile_constraints(&mut cs).unwrap();
+        cs
+    };
+    let cs = synthesize("[main]\na: u32 = 3;\nb: u32 = 5;\n\n[registers]\nr0: u32 = 0;\n");
+    let other = synthesize("[main]\na: u32 = 6;\nb: u32 = 7;\n\n[registers]\nr0: u32 = 0;\n");
+
+    let mut shape = Vec::new();
+    cs.write_binary_shape(&mut shape).unwrap();
+    let (circuit, witness) = SerializedCircuit::from(cs).split();
+    let (other_circuit, other_witness) = SerializedCircuit::from(other).split();
+    assert!(!circuit.has_assignments());
+
+    // Both inputs share the circuit shape, and only differ in their witness.
+    let json = circuit.to_json_string().unwrap();
+    assert_eq!(json, other_circuit.to_json_string().unwrap());
+    assert_ne!(
+        witness.to_json_string().unwrap(),
+        other_witness.to_json_string().unwrap()
+    );
+
+    // The shape is proven with any of its witnesses.
+    let other_witness = SerializedWitness::from_json_string(&other_witness.to_json_string().unwrap()).unwrap();
+    let mut from_json =
+        CircuitSynthesizer::<Bls12_377>::try_from(SerializedCircuit::from_json_string(&json).unwrap()).unwrap();
+    let mut from_binary = CircuitSynthesizer::<Bls12_377>::read_binary(shape.as_slice()).unwrap();
+    for circuit in [&mut from_json, &mut from_binary] {
+        circuit.set_witness(&witness).unwrap();
+        assert_eq!(circuit.which_is_unsatisfied(), None);
+        circuit.set_witness(&other_witness).unwrap();
+        assert_eq!(circuit.which_is_unsatisfied(), None);
+    }
+
+    // A witness that does not assign every variable is rejected.
+    let truncated = SerializedWitness {
+        public_variables: Vec::new(),
+        private_variables: Vec::new(),
+    };
+    assert!(from_json.set_witness(&truncated).is_err());
+}
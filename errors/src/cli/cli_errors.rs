@@ -400,6 +400,221 @@ create_errors!(
         msg: "unable to parse imported dependency's manifest",
         help: None,
     }
+
+    /// For when a path dependency does not point at a package directory.
+    @backtraced
+    dependency_path_not_found {
+        args: (name: impl Display, path: impl Debug),
+        msg: format!("dependency `{}` was not found at {:?}", name, path),
+        help: None,
+    }
+
+    /// For when cloning or checking out a git dependency failed.
+    @backtraced
+    failed_to_fetch_git_dependency {
+        args: (name: impl Display, error: impl Display),
+        msg: format!("failed to fetch git dependency `{}`: {}", name, error),
+        help: None,
+    }
+
+    /// For when the registry URL is neither a `file://` directory nor an Aleo PM host.
+    @backtraced
+    invalid_registry_url {
+        args: (url: impl Display),
+        msg: format!("invalid registry URL `{}`", url),
+        help: Some("use an Aleo PM host such as `https://api.aleo.pm/` or a directory such as `file:///srv/leo-registry`".to_string()),
+    }
+
+    /// For when the package version in the manifest is not a semantic version.
+    @backtraced
+    invalid_package_version {
+        args: (version: impl Display, error: impl Display),
+        msg: format!("package version `{}` is not a valid semantic version: {}", version, error),
+        help: Some("set `version` in Leo.toml to a version such as `0.1.0`".to_string()),
+    }
+
+    /// For when a workspace command is run outside of a workspace.
+    @backtraced
+    not_in_workspace {
+        args: (),
+        msg: "the current package is not part of a workspace",
+        help: Some("add a `[workspace]` section listing the member packages to the Leo.toml of the workspace root".to_string()),
+    }
+
+    /// For when no dependency has the requested name.
+    @backtraced
+    package_not_in_dependencies {
+        args: (package: impl Display),
+        msg: format!("package `{}` is not a dependency of the current package", package),
+        help: None,
+    }
+
+    /// For when a command needs a circuit but the package is a library.
+    @backtraced
+    library_package_has_no_circuit {
+        args: (package: impl Display),
+        msg: format!("package `{}` is a library and has no main function to run", package),
+        help: Some("add a `src/main.leo` file, or import the library from a program package".to_string()),
+    }
+
+    /// For when the manifest targets a curve and proving system that are not supported.
+    @backtraced
+    unsupported_target {
+        args: (curve: impl Display, proving_system: impl Display),
+        msg: format!("proving system `{}` over curve `{}` is not supported", proving_system, curve),
+        help: Some("set `[target]` to `curve = \"bls12_377\"` and `proving_system = \"groth16\"` or `\"marlin\"` in Leo.toml".to_string()),
+    }
+
+    /// For when the keys for a serialized circuit are not next to it.
+    @backtraced
+    circuit_keys_not_found {
+        args: (path: impl Debug, proving_system: impl Display),
+        msg: format!("no {} proving and verification keys were found next to the circuit file {:?}", proving_system, path),
+        help: Some("run `leo setup` and copy the `.lpk` and `.lvk` files, or the `.mpk` and `.mvk` files for Marlin, from `outputs/` next to the circuit file".to_string()),
+    }
+
+    /// For when the witness of a serialized circuit does not satisfy its constraints.
+    @backtraced
+    circuit_not_satisfied {
+        args: (constraint: impl Display),
+        msg: format!("the witness of the circuit does not satisfy constraint {}", constraint),
+        help: Some("run `leo build` again to write the circuit and its witness".to_string()),
+    }
+
+    /// For when a serialized circuit holds only its shape, and no witness is found for it.
+    @backtraced
+    circuit_witness_not_found {
+        args: (path: impl Display),
+        msg: format!("the circuit file holds no assignments, and no witness was found at {}", path),
+        help: Some("write a witness with `leo witness --input <name>`, and pass it with `--witness`".to_string()),
+    }
+
+    /// For when an input pair synthesizes a circuit of another shape than the built one.
+    @backtraced
+    witness_circuit_mismatch {
+        args: (input: impl Display),
+        msg: format!("the inputs `{}` synthesize a circuit of another shape than the built circuit", input),
+        help: Some("constants and array lengths in the inputs are part of the circuit, so these inputs need their own circuit and keys".to_string()),
+    }
+
+    /// For when the imported parameters cannot be read.
+    @backtraced
+    failed_to_read_parameters {
+        args: (path: impl Debug, error: impl Display),
+        msg: format!("cannot read the parameters at {:?} - {}", path, error),
+        help: Some("parameters are imported in the canonical serialization of snarkVM and arkworks, with compressed points".to_string()),
+    }
+
+    /// For when parameters are imported for a proving system without circuit-specific parameters.
+    @backtraced
+    parameters_import_unsupported {
+        args: (proving_system: impl Display),
+        msg: format!("parameters cannot be imported for proving system `{}`", proving_system),
+        help: Some("only Groth16 parameters are imported, set `proving_system = \"groth16\"` in Leo.toml".to_string()),
+    }
+
+    /// For when the imported parameters were produced for another circuit.
+    @backtraced
+    parameters_circuit_hash_mismatch {
+        args: (expected: impl Display, found: impl Display),
+        msg: format!("the parameters were produced for the circuit with hash `{}`, but the circuit has hash `{}`", expected, found),
+        help: Some("run the ceremony for the circuit built from the current sources, whose hash `leo build` reports".to_string()),
+    }
+
+    /// For when the imported parameters do not have the shape of the circuit.
+    @backtraced
+    parameters_circuit_shape_mismatch {
+        args: (query: impl Display, expected: impl Display, found: impl Display),
+        msg: format!("the parameters have {} elements in `{}`, but the circuit needs {}", found, query, expected),
+        help: Some("the parameters were produced for a circuit with other constraint and variable counts".to_string()),
+    }
+    /// For when some input pairs could not be proven in a batch.
+    @backtraced
+    inputs_not_proven {
+        args: (failed: impl Display, total: impl Display, inputs: impl Display),
+        msg: format!("{} of {} input pairs could not be proven: {}", failed, total, inputs),
+        help: Some("the proofs of the other input pairs were written, see the command output for the error of each failed pair".to_string()),
+    }
+
+    /// For when the thread pool for proving cannot be created.
+    @backtraced
+    failed_to_build_thread_pool {
+        args: (error: impl ErrorArg),
+        msg: format!("cannot create the thread pool for proving - {}", error),
+        help: None,
+    }
+
+    /// For when a package is looked up in a file registry under an invalid author, name or version.
+    @backtraced
+    invalid_registry_path_segment {
+        args: (kind: impl Display, value: impl Display),
+        msg: format!("invalid {} `{}` for a file registry", kind, value),
+        help: Some("authors and package names are ASCII alphanumeric words separated by dashes, and versions follow semver".to_string()),
+    }
+
+    /// For when a registry cannot yank package versions.
+    @backtraced
+    yank_unsupported {
+        args: (),
+        msg: "the Aleo Package Manager does not support yanking package versions",
+        help: Some("yanking is only available with a file registry, see `--registry`".to_string()),
+    }
+
+    /// For when a serialized circuit is proven outside a package without naming its proving system.
+    @backtraced
+    circuit_proving_system_unknown {
+        args: (path: impl Debug),
+        msg: format!("the proving system of the circuit file {:?} is unknown, as it is not in a package", path),
+        help: Some("pass `--proving-system groth16` or `--proving-system marlin`".to_string()),
+    }
+
+    /// For when a universal SRS is given for a proving system without one.
+    @backtraced
+    universal_srs_unsupported {
+        args: (proving_system: impl Display),
+        msg: format!("proving system `{}` does not use a universal SRS", proving_system),
+        help: Some("only Marlin uses a universal SRS, set `proving_system = \"marlin\"` in Leo.toml".to_string()),
+    }
+
+    /// For when the given universal SRS cannot be read.
+    @backtraced
+    failed_to_read_universal_srs {
+        args: (path: impl Debug, error: impl Display),
+        msg: format!("cannot read the universal SRS at {:?} - {}", path, error),
+        help: Some("the universal SRS is read as `leo setup` writes it to `outputs/<package>.srs`".to_string()),
+    }
+
+    /// For when the given universal SRS does not support the degree of the circuit.
+    @backtraced
+    universal_srs_too_small {
+        args: (path: impl Debug, max_degree: usize, needed: usize),
+        msg: format!("the universal SRS at {:?} supports degree {}, but the circuit needs degree {}", path, max_degree, needed),
+        help: None,
+    }
+
+    /// For when a proof made with the imported parameters does not verify against them.
+    @backtraced
+    parameters_circuit_mismatch {
+        args: (path: impl Debug),
+        msg: format!("the parameters at {:?} were not produced for the circuit, as a proof of the package inputs made with them does not verify", path),
+        help: Some("run the ceremony for the circuit built from the current sources, whose hash `leo build` reports".to_string()),
+    }
+
+    /// For when the URL or revision of a git dependency would be read by git as an option.
+    @backtraced
+    invalid_git_dependency_argument {
+        args: (name: impl Display, value: impl Display),
+        msg: format!("git dependency `{}` has the URL or revision `{}`, which git would read as an option", name, value),
+        help: None,
+    }
+
+    /// For when setting up would replace keys imported from a ceremony with generated keys.
+    @backtraced
+    imported_keys_outdated {
+        args: (package: impl Display, reason: impl Display),
+        msg: format!("the saved keys of `{}` were imported, and setting up would replace them with generated keys, as {}", package, reason),
+        help: Some("import parameters for the current circuit with `leo setup --import`, or pass `--replace-imported-keys` to generate keys".to_string()),
+    }
 );
 
 impl CliError {
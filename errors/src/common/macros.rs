@@ -76,6 +76,38 @@ macro_rules! create_errors {
         // Steps over the list of functions with an initial error code of 0.
         impl $error_type {
             create_errors!(@step 0i32, $(($(#[$docs])* $formatted_or_backtraced_list, $names($($arg_names: $arg_types,)*), $messages, $helps),)*);
+
+            /// Returns the span of the error if it is a formatted error.
+            pub fn span(&self) -> Option<&Span> {
+                match self {
+                    Self::FormattedError(formatted) => Some(&formatted.span),
+                    Self::BacktracedError(_) => None,
+                }
+            }
+
+            /// Splits the error into its backtraced error and the span of a formatted error.
+            pub fn into_parts(self) -> (BacktracedError, Option<Span>) {
+                match self {
+                    Self::FormattedError(formatted) => (formatted.backtrace, Some(formatted.span)),
+                    Self::BacktracedError(backtraced) => (backtraced, None),
+                }
+            }
+
+            /// Joins a backtraced error and an optional span into an error, as split by `into_parts`.
+            pub fn from_parts((backtrace, span): (BacktracedError, Option<Span>)) -> Self {
+                match span {
+                    Some(span) => Self::FormattedError(FormattedError { span, backtrace }),
+                    None => Self::BacktracedError(backtrace),
+                }
+            }
+
+            /// Returns the error message without any formatting.
+            pub fn message(&self) -> &str {
+                match self {
+                    Self::FormattedError(formatted) => &formatted.backtrace.message,
+                    Self::BacktracedError(backtraced) => &backtraced.message,
+                }
+            }
         }
     };
     // Matches the function if it is a formatted error.
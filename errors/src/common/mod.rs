@@ -27,6 +27,10 @@ pub use self::formatted::*;
 pub mod macros;
 pub use self::macros::*;
 
+/// This module contains a Leo error that can be sent between threads.
+pub mod sendable;
+pub use self::sendable::*;
+
 /// This module contains the common span object for Leo crates.
 pub mod span;
 pub use self::span::Span;
@@ -0,0 +1,178 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+    AsgError, AstError, BacktracedError, CliError, CompilerError, FormattedError, ImportError, LeoError, PackageError,
+    ParserError, SnarkVMError, Span, StateError,
+};
+use leo_input::InputParserError;
+
+use std::{error::Error, fmt, io, sync::Arc};
+
+///
+/// A Leo error that can be sent between threads, such as the source of an `io::Error`.
+///
+/// The spans of a Leo error hold non-atomic tendrils, so a sendable error keeps an owned copy
+/// of the error instead, with the span text as a `String`, and rebuilds the Leo error from it.
+///
+#[derive(Debug)]
+pub struct SendableLeoError(SendableKind);
+
+/// The owned parts of a Leo error, by the Leo error variant they are rebuilt into.
+#[derive(Debug)]
+enum SendableKind {
+    /// An ASG error.
+    Asg(SendableParts),
+    /// An AST error.
+    Ast(SendableParts),
+    /// A CLI error.
+    Cli(SendableParts),
+    /// A compiler error.
+    Compiler(SendableParts),
+    /// An import error.
+    Import(SendableParts),
+    /// An input error, which holds no span.
+    Input(InputParserError),
+    /// A package error.
+    Package(SendableParts),
+    /// A parser error.
+    Parser(SendableParts),
+    /// A SnarkVM error, which holds no span.
+    SnarkVM(SnarkVMError),
+    /// A state error.
+    State(SendableParts),
+}
+
+/// The message, codes and backtrace of an error, with the span of a formatted error.
+#[derive(Debug)]
+struct SendableParts {
+    /// The message, help, codes and backtrace of the error.
+    backtrace: BacktracedError,
+    /// The span of the error if it is a formatted error.
+    span: Option<SendableSpan>,
+}
+
+/// A span that owns its path and content.
+#[derive(Debug)]
+struct SendableSpan {
+    /// The line number where the error started.
+    line_start: usize,
+    /// The line number where the error stopped.
+    line_stop: usize,
+    /// The column number where the error started.
+    col_start: usize,
+    /// The column number where the error stopped.
+    col_stop: usize,
+    /// The path to the Leo file containing the error.
+    path: String,
+    /// The content of the file between the above boundaries.
+    content: String,
+}
+
+impl SendableParts {
+    /// Copies the given parts of an error, as split by `into_parts`.
+    fn new((backtrace, span): (BacktracedError, Option<Span>)) -> Self {
+        let span = span.map(|span| SendableSpan {
+            line_start: span.line_start,
+            line_stop: span.line_stop,
+            col_start: span.col_start,
+            col_stop: span.col_stop,
+            path: span.path.to_string(),
+            content: span.content.to_string(),
+        });
+        Self { backtrace, span }
+    }
+
+    /// Returns a copy of the parts of the error, to be joined by `from_parts`.
+    fn to_parts(&self) -> (BacktracedError, Option<Span>) {
+        let span = self.span.as_ref().map(|span| {
+            Span::new(
+                span.line_start,
+                span.line_stop,
+                span.col_start,
+                span.col_stop,
+                Arc::new(span.path.clone()),
+                span.content.as_str().into(),
+            )
+        });
+        (self.backtrace.clone(), span)
+    }
+}
+
+impl SendableLeoError {
+    /// Creates a sendable error from the given error, copying the content of its span.
+    pub fn new(error: LeoError) -> Self {
+        use SendableKind::*;
+
+        Self(match error {
+            LeoError::AsgError(error) => Asg(SendableParts::new(error.into_parts())),
+            LeoError::AstError(error) => Ast(SendableParts::new(error.into_parts())),
+            LeoError::CliError(error) => Cli(SendableParts::new(error.into_parts())),
+            LeoError::CompilerError(error) => Compiler(SendableParts::new(error.into_parts())),
+            LeoError::ImportError(error) => Import(SendableParts::new(error.into_parts())),
+            LeoError::InputError(error) => Input(error),
+            LeoError::PackageError(error) => Package(SendableParts::new(error.into_parts())),
+            LeoError::ParserError(error) => Parser(SendableParts::new(error.into_parts())),
+            LeoError::SnarkVMError(error) => SnarkVM(error),
+            LeoError::StateError(error) => State(SendableParts::new(error.into_parts())),
+        })
+    }
+
+    /// Returns the Leo error.
+    pub fn into_inner(self) -> LeoError {
+        use SendableKind::*;
+
+        match self.0 {
+            Asg(parts) => AsgError::from_parts(parts.to_parts()).into(),
+            Ast(parts) => AstError::from_parts(parts.to_parts()).into(),
+            Cli(parts) => CliError::from_parts(parts.to_parts()).into(),
+            Compiler(parts) => CompilerError::from_parts(parts.to_parts()).into(),
+            Import(parts) => ImportError::from_parts(parts.to_parts()).into(),
+            Input(error) => error.into(),
+            Package(parts) => PackageError::from_parts(parts.to_parts()).into(),
+            Parser(parts) => ParserError::from_parts(parts.to_parts()).into(),
+            SnarkVM(error) => error.into(),
+            State(parts) => StateError::from_parts(parts.to_parts()).into(),
+        }
+    }
+
+    /// Returns the Leo error that is the source of the given `io::Error`, if any.
+    pub fn from_io_error(error: io::Error) -> Option<LeoError> {
+        error
+            .into_inner()?
+            .downcast::<SendableLeoError>()
+            .ok()
+            .map(|error| error.into_inner())
+    }
+}
+
+impl fmt::Display for SendableLeoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        use SendableKind::*;
+
+        match &self.0 {
+            Asg(parts) | Ast(parts) | Cli(parts) | Compiler(parts) | Import(parts) | Package(parts) | Parser(parts)
+            | State(parts) => match parts.to_parts() {
+                (backtrace, Some(span)) => fmt::Display::fmt(&FormattedError { span, backtrace }, f),
+                (backtrace, None) => fmt::Display::fmt(&backtrace, f),
+            },
+            Input(error) => fmt::Display::fmt(error, f),
+            SnarkVM(error) => fmt::Display::fmt(error, f),
+        }
+    }
+}
+
+impl Error for SendableLeoError {}
@@ -144,6 +144,42 @@ impl LeoError {
             StateError(error) => error.exit_code(),
         }
     }
+
+    /// Returns the span of the error if it points into a source file.
+    pub fn span(&self) -> Option<&Span> {
+        use LeoError::*;
+
+        match self {
+            AsgError(error) => error.span(),
+            AstError(error) => error.span(),
+            CliError(error) => error.span(),
+            CompilerError(error) => error.span(),
+            ImportError(error) => error.span(),
+            InputError(_error) => None,
+            PackageError(error) => error.span(),
+            ParserError(error) => error.span(),
+            SnarkVMError(_error) => None,
+            StateError(error) => error.span(),
+        }
+    }
+
+    /// Returns the error message without the span or backtrace formatting.
+    pub fn message(&self) -> String {
+        use LeoError::*;
+
+        match self {
+            AsgError(error) => error.message().to_string(),
+            AstError(error) => error.message().to_string(),
+            CliError(error) => error.message().to_string(),
+            CompilerError(error) => error.message().to_string(),
+            ImportError(error) => error.message().to_string(),
+            InputError(error) => error.to_string(),
+            PackageError(error) => error.message().to_string(),
+            ParserError(error) => error.message().to_string(),
+            SnarkVMError(error) => error.to_string(),
+            StateError(error) => error.message().to_string(),
+        }
+    }
 }
 
 /// A global result type for all Leo crates, that defaults the errors to be a LeoError.
@@ -559,4 +559,246 @@ create_errors!(
         msg: format!("serialization failed: {}", error),
         help: None,
     }
+
+    /// For when reading the sources of a dependency to compute its checksum failed.
+    @backtraced
+    failed_to_hash_dependency {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("failed hashing the sources of the dependency at {:?}: {}", path, error),
+        help: None,
+    }
+
+    /// For when a dependency version is not a valid semver requirement.
+    @backtraced
+    invalid_version_requirement {
+        args: (package: impl Display, requirement: impl Display, error: impl ErrorArg),
+        msg: format!("invalid version requirement `{}` for package `{}`: {}", requirement, package, error),
+        help: Some("use a semver requirement such as `1.2.0`, `^1.2` or `~0.3`".to_string()),
+    }
+
+    /// For when no published version of a package satisfies all of its dependents.
+    @backtraced
+    conflicting_dependency_versions {
+        args: (package: impl Display, requirements: impl Display),
+        msg: format!("no version of package `{}` satisfies all requirements:\n{}", package, requirements),
+        help: None,
+    }
+
+    /// For when a package depends on itself through its dependencies.
+    @backtraced
+    recursive_dependency_found {
+        args: (message: impl Display),
+        msg: format!("recursive dependency found \n{}", message),
+        help: None,
+    }
+
+    /// For when the sources of a fetched dependency do not match the checksum in the lock file.
+    @backtraced
+    dependency_checksum_mismatch {
+        args: (package: impl Display, expected: impl Display, found: impl Display),
+        msg: format!(
+            "the sources of dependency `{}` do not match the lock file, expected checksum {} but found {}",
+            package, expected, found
+        ),
+        help: Some("run `leo fetch` to download the dependency again".to_string()),
+    }
+
+    /// For when the leoignore file has an IO error.
+    @backtraced
+    io_error_leoignore_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error leoignore file from the provided file path - {}", error),
+        help: None,
+    }
+
+    /// For when an include or exclude pattern is not a valid glob.
+    @backtraced
+    invalid_file_pattern {
+        args: (pattern: impl Display, error: impl Display),
+        msg: format!("invalid file pattern `{}`: {}", pattern, error),
+        help: None,
+    }
+
+    /// For when a workspace member does not match a package directory.
+    @backtraced
+    workspace_member_not_found {
+        args: (member: impl Display, root: impl Debug),
+        msg: format!("workspace member `{}` was not found in {:?}", member, root),
+        help: Some("list the directories of the member packages in `[workspace] members`".to_string()),
+    }
+
+    /// For when the lib file has an IO error.
+    @backtraced
+    io_error_lib_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error lib file from the provided file path - {}", error),
+        help: None,
+    }
+
+    /// For when removing the profile file failed.
+    @backtraced
+    failed_to_remove_profile_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot remove profile file from the provided file path - {:?}", path),
+        help: None,
+    }
+
+    /// For when the profile file has an IO error.
+    @backtraced
+    io_error_profile_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error profile file from the provided file path - {}", error),
+        help: None,
+    }
+
+    /// For when removing an exported circuit file failed.
+    @backtraced
+    failed_to_remove_export_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot remove exported circuit file from the provided file path - {:?}", path),
+        help: None,
+    }
+
+    /// For when an exported circuit file has an IO error.
+    @backtraced
+    io_error_export_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error exported circuit file from the provided file path - {}", error),
+        help: None,
+    }
+
+    /// For when reading the witness file failed.
+    @backtraced
+    failed_to_read_witness_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot read witness file from the provided file path - {:?}", path),
+        help: None,
+    }
+
+    /// For when removing the witness file failed.
+    @backtraced
+    failed_to_remove_witness_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot remove witness file from the provided file path - {:?}", path),
+        help: None,
+    }
+
+    /// For when the witness file has an IO error.
+    @backtraced
+    io_error_witness_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error witness file from the provided file path - {}", error),
+        help: None,
+    }
+
+    /// For when reading the universal SRS file failed.
+    @backtraced
+    failed_to_read_universal_srs_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot read universal SRS file from the provided file path - {:?}", path),
+        help: None,
+    }
+
+    /// For when removing the universal SRS file failed.
+    @backtraced
+    failed_to_remove_universal_srs_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot remove universal SRS file from the provided file path - {:?}", path),
+        help: None,
+    }
+
+    /// For when the universal SRS file has an IO error.
+    @backtraced
+    io_error_universal_srs_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error universal SRS file from the provided file path - {}", error),
+        help: None,
+    }
+
+    /// For when reading the metadata file failed.
+    @backtraced
+    failed_to_read_metadata_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot read metadata file from the provided file path - {:?}", path),
+        help: None,
+    }
+
+    /// For when parsing the metadata file failed.
+    @backtraced
+    failed_to_parse_metadata_file {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot parse metadata file {:?} - {}", path, error),
+        help: Some("remove the metadata file to treat the artifacts as produced from fresh randomness".to_string()),
+    }
+
+    /// For when removing the metadata file failed.
+    @backtraced
+    failed_to_remove_metadata_file {
+        args: (path: impl Debug),
+        msg: format!("Cannot remove metadata file from the provided file path - {:?}", path),
+        help: None,
+    }
+
+    /// For when the metadata file has an IO error.
+    @backtraced
+    io_error_metadata_file {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error metadata file from the provided file path - {}", error),
+        help: None,
+    }
+    /// For when a key or proof file does not start with an envelope.
+    @backtraced
+    missing_envelope {
+        args: (path: impl Debug),
+        msg: format!("{:?} does not name the circuit it was produced for", path),
+        help: Some("the file was written by an older version of Leo, run `leo clean` and `leo setup` to produce it again".to_string()),
+    }
+
+    /// For when parsing the envelope of a key or proof file failed.
+    @backtraced
+    failed_to_parse_envelope {
+        args: (path: impl Debug, error: impl ErrorArg),
+        msg: format!("Cannot parse the envelope of {:?} - {}", path, error),
+        help: None,
+    }
+
+    /// For when the envelope of a key or proof file has an unknown version.
+    @backtraced
+    unsupported_envelope_version {
+        args: (path: impl Debug, version: impl Display, supported: impl Display),
+        msg: format!("{:?} has envelope version {}, but this version of Leo reads version {}", path, version, supported),
+        help: Some("update Leo, or produce the file again with this version".to_string()),
+    }
+
+    /// For when a key or proof file was produced for another circuit.
+    @backtraced
+    envelope_mismatch {
+        args: (path: impl Debug, field: impl Display, expected: impl Display, found: impl Display),
+        msg: format!("{:?} was produced for {} `{}`, but `{}` was expected", path, field, found, expected),
+        help: Some("keys and proofs are only used with the circuit they were produced for, run `leo setup` to produce them again".to_string()),
+    }
+
+    /// For when the envelope of a key or proof file has an IO error.
+    @backtraced
+    io_error_envelope {
+        args: (error: impl ErrorArg),
+        msg: format!("IO error envelope - {}", error),
+        help: None,
+    }
+
+    /// For when two dependencies are imported under the same name.
+    @backtraced
+    import_name_collision {
+        args: (import_name: impl Display, first: impl Display, second: impl Display),
+        msg: format!("The import name `{}` refers to both `{}` and `{}`", import_name, first, second),
+        help: Some("rename one of the dependencies in the `[dependencies]` section of Leo.toml".to_string()),
+    }
+
+    /// For when the lock file records no checksum for a fetched dependency.
+    @backtraced
+    dependency_checksum_missing {
+        args: (package: impl Display),
+        msg: format!("the lock file records no checksum for dependency `{}`, so its sources cannot be verified", package),
+        help: Some("run `leo fetch` to download the dependency again and record its checksum".to_string()),
+    }
 );
@@ -19,3 +19,6 @@
 
 pub mod parser;
 pub use self::parser::*;
+
+pub mod source_provider;
+pub use self::source_provider::*;
@@ -14,32 +14,52 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::{FileSystem, SourceProvider};
 use leo_ast::Program;
 use leo_ast_passes::ImportResolver;
 use leo_errors::{ImportError, LeoError, Result, Span};
 
 use indexmap::{IndexMap, IndexSet};
-use std::path::PathBuf;
+use std::{path::PathBuf, sync::Arc};
 
 /// Stores imported packages.
 ///
 /// A program can import one or more packages. A package can be found locally in the source
 /// directory, foreign in the imports directory, or part of the core package list.
-#[derive(Clone, Default)]
+#[derive(Clone)]
 pub struct ImportParser {
     program_path: PathBuf,
     partial_imports: IndexSet<String>,
     imports: IndexMap<String, Program>,
     pub imports_map: IndexMap<String, String>,
+    pub(crate) source_provider: Arc<dyn SourceProvider>,
+}
+
+impl Default for ImportParser {
+    fn default() -> Self {
+        Self::new(PathBuf::default(), IndexMap::new())
+    }
 }
 
 impl ImportParser {
     pub fn new(program_path: PathBuf, imports_map: IndexMap<String, String>) -> Self {
+        Self::with_source_provider(program_path, imports_map, Arc::new(FileSystem))
+    }
+
+    ///
+    /// Returns an import parser that reads packages from the given source provider.
+    ///
+    pub fn with_source_provider(
+        program_path: PathBuf,
+        imports_map: IndexMap<String, String>,
+        source_provider: Arc<dyn SourceProvider>,
+    ) -> Self {
         ImportParser {
             program_path,
             partial_imports: Default::default(),
             imports: Default::default(),
             imports_map,
+            source_provider,
         }
     }
 }
@@ -18,24 +18,19 @@ use crate::ImportParser;
 use leo_ast::Program;
 use leo_errors::{ImportError, Result, Span};
 
-use std::{fs, fs::DirEntry, path::PathBuf};
+use std::path::{Path, PathBuf};
 
 static SOURCE_FILE_EXTENSION: &str = ".leo";
 static SOURCE_DIRECTORY_NAME: &str = "src/";
 static IMPORTS_DIRECTORY_NAME: &str = "imports/";
 
 impl ImportParser {
-    fn parse_package_access(
-        &mut self,
-        package: &DirEntry,
-        remaining_segments: &[&str],
-        span: &Span,
-    ) -> Result<Program> {
+    fn parse_package_access(&mut self, package: &Path, remaining_segments: &[&str], span: &Span) -> Result<Program> {
         if !remaining_segments.is_empty() {
-            return self.parse_package(package.path(), remaining_segments, span);
+            return self.parse_package(package.to_path_buf(), remaining_segments, span);
         }
 
-        let program = Self::parse_import_file(package, span)?;
+        let program = self.parse_import_file(package, span)?;
         let ast = leo_ast_passes::Importer::do_pass(program, self)?.into_repr();
 
         Ok(ast)
@@ -57,7 +52,7 @@ impl ImportParser {
         }
 
         // Trim path if importing from another file
-        if path.is_file() {
+        if self.source_provider.is_file(&path) {
             path.pop();
         }
 
@@ -71,32 +66,42 @@ impl ImportParser {
         imports_directory.push(IMPORTS_DIRECTORY_NAME);
 
         // Read from local `src` directory or the current path
-        if source_directory.exists() {
+        if self.source_provider.exists(&source_directory) {
             path = source_directory
         }
 
         // Get a vector of all packages in the source directory.
-        let entries = fs::read_dir(path)
-            .map_err(|error| ImportError::directory_error(error, &error_path, span))?
-            .collect::<Result<Vec<_>, std::io::Error>>()
+        let entries = self
+            .source_provider
+            .read_dir(&path)
             .map_err(|error| ImportError::directory_error(error, &error_path, span))?;
 
         // Check if the imported package name is in the source directory.
         let matched_source_entry = entries.into_iter().find(|entry| {
-            entry
-                .file_name()
-                .into_string()
-                .unwrap()
+            file_name(entry)
                 .trim_end_matches(SOURCE_FILE_EXTENSION)
                 .eq(package_name)
         });
 
-        if imports_directory.exists() {
+        // Packages from a local path are mapped to their absolute directory.
+        if let Some(directory) = self
+            .imports_map
+            .get(package_name)
+            .map(PathBuf::from)
+            .filter(|directory| directory.is_absolute())
+        {
+            return match matched_source_entry {
+                Some(_) => Err(ImportError::conflicting_imports(package_name, span).into()),
+                None => self.parse_package_access(&directory, &segments[1..], span),
+            };
+        }
+
+        if self.source_provider.exists(&imports_directory) {
             // Get a vector of all packages in the imports directory.
-            let entries = fs::read_dir(imports_directory)
-                .map_err(|error| ImportError::directory_error(error, &error_path, span))?
-                .collect::<Result<Vec<_>, std::io::Error>>()
-                .map_err(|error| ImportError::directory_error(error, error_path, span))?;
+            let entries = self
+                .source_provider
+                .read_dir(&imports_directory)
+                .map_err(|error| ImportError::directory_error(error, &error_path, span))?;
 
             // Keeping backward compatibilty for existing packages.
             // If index_map contains key, use it or try to access directly.
@@ -108,9 +113,7 @@ impl ImportParser {
                 .clone();
 
             // Check if the imported package name is in the imports directory.
-            let matched_import_entry = entries
-                .into_iter()
-                .find(|entry| entry.file_name().into_string().unwrap().eq(&package_name));
+            let matched_import_entry = entries.into_iter().find(|entry| file_name(entry).eq(&package_name));
 
             // Check if the package name was found in both the source and imports directory.
             match (matched_source_entry, matched_import_entry) {
@@ -128,3 +131,10 @@ impl ImportParser {
         }
     }
 }
+
+/// Returns the last component of the path as a string.
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
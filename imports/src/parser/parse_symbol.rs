@@ -18,9 +18,10 @@ use crate::ImportParser;
 use leo_ast::Program;
 use leo_errors::{ImportError, Result, Span};
 
-use std::fs::DirEntry;
+use std::path::Path;
 
 static MAIN_FILE: &str = "src/main.leo";
+static LIB_FILE: &str = "src/lib.leo";
 
 impl ImportParser {
     ///
@@ -28,30 +29,36 @@ impl ImportParser {
     ///
     /// Builds an abstract syntax tree from the given file and then builds the Leo syntax tree.
     ///
-    pub(crate) fn parse_import_file(package: &DirEntry, span: &Span) -> Result<Program> {
+    pub(crate) fn parse_import_file(&self, package: &Path, span: &Span) -> Result<Program> {
         // Get the package file type.
-        let file_type = package
-            .file_type()
-            .map_err(|error| ImportError::directory_error(error, package.path(), span))?;
+        let is_dir = self.source_provider.is_dir(package);
         let file_name = package
             .file_name()
-            .into_string()
-            .map_err(|_| ImportError::convert_os_string(span))?;
+            .and_then(|name| name.to_str())
+            .ok_or_else(|| ImportError::convert_os_string(span))?
+            .to_string();
 
-        let mut file_path = package.path();
-        if file_type.is_dir() {
+        let mut file_path = package.to_path_buf();
+        if is_dir {
             file_path.push(MAIN_FILE);
 
-            if !file_path.exists() {
-                return Err(ImportError::expected_main_file(file_path.as_path(), span).into());
+            // Library packages have no main file, so fall back to their lib file.
+            if !self.source_provider.is_file(&file_path) {
+                let lib_file_path = package.join(LIB_FILE);
+                if !self.source_provider.is_file(&lib_file_path) {
+                    return Err(ImportError::expected_main_file(file_path.as_path(), span).into());
+                }
+                file_path = lib_file_path;
             }
         }
 
         let file_path_str = file_path.to_str().unwrap_or_default();
 
         // Build the package abstract syntax tree.
-        let program_string =
-            &std::fs::read_to_string(&file_path).map_err(|x| ImportError::io_error(file_path_str, x, span))?;
+        let program_string = &self
+            .source_provider
+            .read_file(&file_path)
+            .map_err(|x| ImportError::io_error(file_path_str, x, span))?;
         let mut program = leo_parser::parse(file_path_str, program_string)?;
         program.name = file_name;
         Ok(program)
@@ -0,0 +1,138 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use indexmap::{IndexMap, IndexSet};
+use std::{
+    fs, io,
+    path::{Component, Path, PathBuf},
+};
+
+/// The source files and directories seen by the compiler and the import resolver.
+///
+/// The default provider is the local filesystem. Other providers allow compiling
+/// packages that only exist in memory, such as in a web service or the wasm build.
+pub trait SourceProvider: Send + Sync {
+    /// Returns the contents of the file at the given path.
+    fn read_file(&self, path: &Path) -> io::Result<String>;
+
+    /// Returns the paths of the entries in the directory at the given path.
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>>;
+
+    /// Returns `true` if the path points at a file.
+    fn is_file(&self, path: &Path) -> bool;
+
+    /// Returns `true` if the path points at a directory.
+    fn is_dir(&self, path: &Path) -> bool;
+
+    /// Returns `true` if the path points at a file or a directory.
+    fn exists(&self, path: &Path) -> bool {
+        self.is_file(path) || self.is_dir(path)
+    }
+}
+
+/// Reads sources from the local filesystem.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FileSystem;
+
+impl SourceProvider for FileSystem {
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        fs::read_dir(path)?.map(|entry| Ok(entry?.path())).collect()
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+}
+
+/// Holds sources in memory, keyed by path.
+///
+/// Directories are implied by the paths of the files they contain.
+#[derive(Clone, Debug, Default)]
+pub struct MemoryFileSystem {
+    files: IndexMap<PathBuf, String>,
+}
+
+impl MemoryFileSystem {
+    /// Returns an empty in-memory filesystem.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds or replaces the file at the given path.
+    pub fn insert<P: AsRef<Path>, S: ToString>(&mut self, path: P, content: S) -> &mut Self {
+        self.files.insert(normalize(path.as_ref()), content.to_string());
+        self
+    }
+
+    /// Removes the file at the given path and returns its contents.
+    pub fn remove<P: AsRef<Path>>(&mut self, path: P) -> Option<String> {
+        self.files.shift_remove(&normalize(path.as_ref()))
+    }
+}
+
+impl SourceProvider for MemoryFileSystem {
+    fn read_file(&self, path: &Path) -> io::Result<String> {
+        self.files.get(&normalize(path)).cloned().ok_or_else(|| not_found(path))
+    }
+
+    fn read_dir(&self, path: &Path) -> io::Result<Vec<PathBuf>> {
+        let directory = normalize(path);
+        let entries = self
+            .files
+            .keys()
+            .filter_map(|file| {
+                let child = file.strip_prefix(&directory).ok()?.components().next()?;
+                Some(directory.join(child))
+            })
+            .collect::<IndexSet<_>>();
+
+        if entries.is_empty() {
+            return Err(not_found(path));
+        }
+
+        Ok(entries.into_iter().collect())
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(&normalize(path))
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        let directory = normalize(path);
+        self.files
+            .keys()
+            .any(|file| file != &directory && file.starts_with(&directory))
+    }
+}
+
+/// Removes `.` components and trailing separators so that equal paths compare equal.
+fn normalize(path: &Path) -> PathBuf {
+    path.components()
+        .filter(|component| !matches!(component, Component::CurDir))
+        .collect()
+}
+
+fn not_found(path: &Path) -> io::Error {
+    io::Error::new(io::ErrorKind::NotFound, format!("{} not found", path.display()))
+}
@@ -16,20 +16,33 @@
 
 use crate::{commands::Command, context::Context};
 use leo_compiler::{
-    compiler::{thread_leaked_context, Compiler},
+    compiler::{thread_leaked_context, AsgContext, Compiler},
     group::targets::edwards_bls12::EdwardsGroupType,
-    AstSnapshotOptions, CompilerOptions,
+    AstSnapshotOptions, CompilerOptions, OutputFile,
 };
 use leo_errors::{CliError, Result};
 use leo_package::{
     inputs::*,
-    outputs::{ChecksumFile, CircuitFile, OutputsDirectory, OUTPUTS_DIRECTORY_NAME},
-    source::{MainFile, MAIN_FILENAME, SOURCE_DIRECTORY_NAME},
+    outputs::{
+        ChecksumFile, CircuitFile, CircuitFormat, ExportFile, ExportFormat, OutputsDirectory, ProfileFile, WitnessFile,
+        OUTPUTS_DIRECTORY_NAME,
+    },
+    source::{LibFile, MainFile, LIB_FILENAME, MAIN_FILENAME, SOURCE_DIRECTORY_NAME},
+};
+use leo_synthesizer::{
+    binary_circuit_has_assignments, is_binary_circuit, CircuitSynthesizer, SerializedCircuit, SerializedWitness,
+    SummarizedCircuit,
 };
-use leo_synthesizer::{CircuitSynthesizer, SerializedCircuit};
 
+use indexmap::IndexMap;
+use sha2::{Digest, Sha256};
 use snarkvm_curves::{bls12_377::Bls12_377, edwards_bls12::Fq};
 use snarkvm_r1cs::ConstraintSystem;
+use std::{
+    convert::TryFrom,
+    io::Read,
+    path::{Path, PathBuf},
+};
 use structopt::StructOpt;
 use tracing::span::Span;
 
@@ -55,6 +68,50 @@ pub struct BuildOptions {
     pub enable_canonicalized_ast_snapshot: bool,
     #[structopt(long, help = "Writes AST snapshot after the type inference phase.")]
     pub enable_type_inferenced_ast_snapshot: bool,
+    #[structopt(
+        long,
+        help = "Report the constraints and variables of each function, statement and line."
+    )]
+    pub profile: bool,
+    #[structopt(
+        long,
+        parse(try_from_str = parse_circuit_format),
+        help = "Write the circuit as `json` or `binary`, by default binary for circuits over 100000 constraints."
+    )]
+    pub circuit_format: Option<CircuitFormat>,
+    #[structopt(
+        long,
+        use_delimiter = true,
+        parse(try_from_str = parse_export_format),
+        help = "Export the circuit to a comma separated list of `r1cs`, `wtns` and `zkif` files."
+    )]
+    pub emit: Vec<ExportFormat>,
+}
+
+/// Circuits with more constraints are written in the binary format, unless a format is given.
+const LARGE_CIRCUIT_CONSTRAINTS: usize = 100_000;
+
+fn parse_circuit_format(format: &str) -> std::result::Result<CircuitFormat, String> {
+    match format {
+        "json" => Ok(CircuitFormat::Json),
+        "binary" => Ok(CircuitFormat::Binary),
+        _ => Err(format!(
+            "unknown circuit format `{}`, expected `json` or `binary`",
+            format
+        )),
+    }
+}
+
+fn parse_export_format(format: &str) -> std::result::Result<ExportFormat, String> {
+    match format {
+        "r1cs" => Ok(ExportFormat::R1cs),
+        "wtns" => Ok(ExportFormat::Wtns),
+        "zkif" => Ok(ExportFormat::Zkif),
+        _ => Err(format!(
+            "unknown export format `{}`, expected `r1cs`, `wtns` or `zkif`",
+            format
+        )),
+    }
 }
 
 impl From<BuildOptions> for CompilerOptions {
@@ -95,8 +152,50 @@ impl From<BuildOptions> for AstSnapshotOptions {
     }
 }
 
+///
+/// Returns the import map of the package at `package_path` from the lock file, if any.
+///
+/// The fetched dependencies are verified first, so the compiler never reads modified sources.
+///
+pub(crate) fn resolved_imports_map(context: &Context, package_path: &Path) -> Result<IndexMap<String, String>> {
+    if !context.lock_file_exists()? {
+        return Ok(Default::default());
+    }
+
+    let lock_file = context.lock_file()?;
+    match context.workspace_root()? {
+        Some(root) => {
+            lock_file.verify(&root)?;
+            lock_file.to_workspace_import_map(&root, package_path)
+        }
+        None => {
+            lock_file.verify(package_path)?;
+            lock_file.to_import_map()
+        }
+    }
+}
+
+///
+/// Reads a circuit written by `leo build`, in either format.
+///
+/// Returns the circuit and whether the file holds its assignments, rather than only its shape.
+///
+pub(crate) fn read_circuit(circuit_file: &CircuitFile, path: &Path) -> Result<(CircuitSynthesizer<Bls12_377>, bool)> {
+    let mut reader = circuit_file.open(path)?;
+    if is_binary_circuit(&mut reader)? {
+        let has_assignments = binary_circuit_has_assignments(&mut reader)?;
+        return Ok((CircuitSynthesizer::read_binary(reader)?, has_assignments));
+    }
+
+    let mut serialized = String::new();
+    reader.read_to_string(&mut serialized).map_err(CliError::cli_io_error)?;
+    let serialized = SerializedCircuit::from_json_string(&serialized)?;
+    let has_assignments = serialized.has_assignments();
+    Ok((CircuitSynthesizer::try_from(serialized)?, has_assignments))
+}
+
 /// Compile and build program command.
-#[derive(StructOpt, Debug)]
+#[derive(StructOpt, Clone, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
 pub struct Build {
     #[structopt(flatten)]
@@ -105,7 +204,7 @@ pub struct Build {
 
 impl Command for Build {
     type Input = ();
-    type Output = (Compiler<'static, Fq, EdwardsGroupType>, bool);
+    type Output = (Compiler<'static, Fq, EdwardsGroupType>, bool, Option<SummarizedCircuit>);
 
     fn log_span(&self) -> Span {
         tracing::span!(tracing::Level::INFO, "Build")
@@ -116,6 +215,22 @@ impl Command for Build {
     }
 
     fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        self.compile(&context, thread_leaked_context())
+    }
+}
+
+impl Build {
+    ///
+    /// Compiles the package, allocating its ASG in the given context.
+    ///
+    /// Returns the compiled program, whether its checksum differs from the previous build, and the
+    /// summary of its circuit, which library packages do not have.
+    ///
+    pub(crate) fn compile<'a>(
+        &self,
+        context: &Context,
+        asg_context: AsgContext<'a>,
+    ) -> Result<(Compiler<'a, Fq, EdwardsGroupType>, bool, Option<SummarizedCircuit>)> {
         let path = context.dir()?;
         let manifest = context.manifest().map_err(|_| CliError::manifest_file_not_found())?;
         let package_name = manifest.get_package_name();
@@ -138,8 +253,11 @@ impl Command for Build {
 
         tracing::info!("Starting...");
 
-        // Compile the main.leo file along with constraints
+        // Library packages are only type checked, since they have no main function to synthesize
         if !MainFile::exists_at(&package_path) {
+            if LibFile::exists_at(&package_path) {
+                return self.compile_library(context, asg_context, &package_path, output_directory);
+            }
             return Err(CliError::package_main_file_not_found().into());
         }
 
@@ -160,11 +278,7 @@ impl Command for Build {
         // Log compilation of files to console
         tracing::info!("Compiling main program... ({:?})", main_file_path);
 
-        let imports_map = if context.lock_file_exists()? {
-            context.lock_file()?.to_import_map()
-        } else {
-            Default::default()
-        };
+        let imports_map = resolved_imports_map(context, &package_path)?;
 
         // Load the program at `main_file_path`
         let program = Compiler::<Fq, EdwardsGroupType>::parse_program_with_input(
@@ -175,22 +289,23 @@ impl Command for Build {
             &input_path,
             &state_string,
             &state_path,
-            thread_leaked_context(),
+            asg_context,
             Some(self.compiler_options.clone().into()),
             imports_map,
-            Some(self.compiler_options.into()),
+            Some(self.compiler_options.clone().into()),
         )?;
 
-        // Compute the current program checksum
+        // Compute the checksum of the import-resolved program and the compiler options
         let program_checksum = program.checksum()?;
 
         // Generate the program on the constraint system and verify correctness
-        {
+        let circuit = {
             let mut cs = CircuitSynthesizer::<Bls12_377> {
                 constraints: Default::default(),
                 public_variables: Default::default(),
                 private_variables: Default::default(),
                 namespaces: Default::default(),
+                profile: self.compiler_options.profile.then(Default::default),
             };
             let temporary_program = program.clone();
             let output = temporary_program.compile_constraints(&mut cs)?;
@@ -198,14 +313,55 @@ impl Command for Build {
             tracing::debug!("Compiled output - {:#?}", output);
             tracing::info!("Number of constraints - {:#?}", cs.num_constraints());
 
-            // Serialize the circuit
-            let circuit_object = SerializedCircuit::from(cs);
-            let json = circuit_object.to_json_string().unwrap();
-            // println!("json: {}", json);
+            // Report the profile, and write its folded stacks for flamegraph tools.
+            if let Some(profile) = cs.profile.take() {
+                tracing::info!("Constraint profile\n{}", profile);
+                ProfileFile::new(&package_name).write_to(&path, profile.to_folded_stacks())?;
+            }
+
+            // Write the program output to the output `.out` file.
+            OutputFile::new(&package_name).write(&package_path, output.to_string().as_bytes())?;
+
+            let format = self.compiler_options.circuit_format.unwrap_or_else(|| {
+                if cs.num_constraints() > LARGE_CIRCUIT_CONSTRAINTS {
+                    CircuitFormat::Binary
+                } else {
+                    CircuitFormat::Json
+                }
+            });
+            let circuit_file = CircuitFile::with_format(&package_name, format);
+
+            // Export the circuit for external tooling.
+            for format in self.compiler_options.emit.iter() {
+                let writer = ExportFile::new(&package_name, *format).create(&path)?;
+                match format {
+                    ExportFormat::R1cs => cs.write_r1cs(writer)?,
+                    ExportFormat::Wtns => cs.write_wtns(writer)?,
+                    ExportFormat::Zkif => cs.write_zkif(writer)?,
+                }
+            }
+
+            // Summarize the circuit and write the witness of the package inputs to the `.witness` file.
+            let circuit = SummarizedCircuit::from(&cs);
+            WitnessFile::new(&package_name).write_to(&path, SerializedWitness::from(&cs).to_json_string()?)?;
+
+            // Write the shape of the circuit to its `.circuit` file, streamed for binary circuits.
+            match format {
+                CircuitFormat::Json => {
+                    let (circuit_object, _) = SerializedCircuit::from(cs).split();
+                    circuit_file.write_to(&path, circuit_object.to_json_string()?)?;
+                }
+                CircuitFormat::Binary => cs.write_binary_shape(circuit_file.create(&path)?)?,
+            }
+
+            // Remove a circuit left by a previous build in the other format, so it is never read stale.
+            let other_format = match format {
+                CircuitFormat::Json => CircuitFormat::Binary,
+                CircuitFormat::Binary => CircuitFormat::Json,
+            };
+            CircuitFile::with_format(&package_name, other_format).remove(&path)?;
 
-            // Write serialized circuit to circuit `.json` file.
-            let circuit_file = CircuitFile::new(&package_name);
-            circuit_file.write_to(&path, json)?;
+            circuit
 
             // Check that we can read the serialized circuit file
             // let serialized = circuit_file.read_from(&package_path)?;
@@ -214,13 +370,25 @@ impl Command for Build {
             // let deserialized = SerializedCircuit::from_json_string(&serialized).unwrap();
             // let _circuit_synthesizer = CircuitSynthesizer::<Bls12_377>::try_from(deserialized).unwrap();
             // println!("deserialized {:?}", circuit_synthesizer.num_constraints());
-        }
+        };
+
+        let circuit_checksum = circuit.checksum();
+        tracing::info!("Circuit hash - {}", circuit_checksum);
+
+        // The keys depend on every input to the circuit and on the proving system they are for,
+        // so the build checksum covers all of them
+        let target = manifest.get_target();
+        let mut hasher = Sha256::new();
+        hasher.update(program_checksum.as_bytes());
+        hasher.update(circuit_checksum.as_bytes());
+        hasher.update(format!("{}/{}", target.curve, target.proving_system).as_bytes());
+        let build_checksum = format!("{:x}", hasher.finalize());
 
         // If a checksum file exists, check if it differs from the new checksum
         let checksum_file = ChecksumFile::new(&package_name);
         let checksum_differs = if checksum_file.exists_at(&package_path) {
             let previous_checksum = checksum_file.read_from(&package_path)?;
-            program_checksum != previous_checksum
+            build_checksum != previous_checksum
         } else {
             // By default, the checksum differs if there is no checksum to compare against
             true
@@ -229,13 +397,54 @@ impl Command for Build {
         // If checksum differs, compile the program
         if checksum_differs {
             // Write the new checksum to the output directory
-            checksum_file.write_to(&path, program_checksum)?;
+            checksum_file.write_to(&path, build_checksum)?;
 
             tracing::debug!("Checksum saved ({:?})", path);
         }
 
         tracing::info!("Complete");
 
-        Ok((program, checksum_differs))
+        Ok((program, checksum_differs, Some(circuit)))
+    }
+
+    ///
+    /// Type checks the `lib.leo` file of a library package through the ASG.
+    ///
+    /// No constraints are synthesized, so no circuit, output or checksum files are written.
+    ///
+    fn compile_library<'a>(
+        &self,
+        context: &Context,
+        asg_context: AsgContext<'a>,
+        package_path: &Path,
+        output_directory: PathBuf,
+    ) -> Result<(Compiler<'a, Fq, EdwardsGroupType>, bool, Option<SummarizedCircuit>)> {
+        let package_name = context.manifest()?.get_package_name();
+
+        // Create the output directory for the AST snapshots
+        OutputsDirectory::create(package_path)?;
+
+        // Construct the path to the lib file in the source directory
+        let mut lib_file_path = package_path.to_path_buf();
+        lib_file_path.push(SOURCE_DIRECTORY_NAME);
+        lib_file_path.push(LIB_FILENAME);
+
+        tracing::info!("Checking library... ({:?})", lib_file_path);
+
+        let imports_map = resolved_imports_map(context, package_path)?;
+
+        let program = Compiler::<Fq, EdwardsGroupType>::parse_program_without_input(
+            package_name,
+            lib_file_path,
+            output_directory,
+            asg_context,
+            Some(self.compiler_options.clone().into()),
+            imports_map,
+            Some(self.compiler_options.clone().into()),
+        )?;
+
+        tracing::info!("Complete");
+
+        Ok((program, false, None))
     }
 }
@@ -15,12 +15,16 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{commands::Command, context::Context};
-use leo_compiler::OutputFile;
+use leo_compiler::{OutputFile, OUTPUT_FILE_EXTENSION};
 use leo_errors::Result;
 use leo_package::outputs::{
-    ChecksumFile, CircuitFile, ProofFile, ProvingKeyFile, Snapshot, SnapshotFile, VerificationKeyFile,
+    ChecksumFile, CircuitFile, CircuitFormat, ExportFile, ExportFormat, Metadata, MetadataFile, ProfileFile, ProofFile,
+    ProvingKeyFile, Snapshot, SnapshotFile, UniversalSrsFile, VerificationKeyFile, WitnessFile,
+    METADATA_FILE_EXTENSION, OUTPUTS_DIRECTORY_NAME, PROOF_FILE_EXTENSION, WITNESS_FILE_EXTENSION,
 };
+use leo_package::root::ProvingSystem;
 
+use std::{fs, path::Path};
 use structopt::StructOpt;
 use tracing::span::Span;
 
@@ -44,24 +48,60 @@ impl Command for Clean {
     fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
         let path = context.dir()?;
         let package_name = context.manifest()?.get_package_name();
+        let universal_srs = match UniversalSrsFile::new(&package_name).exists_at(&path) {
+            true => MetadataFile::new(&package_name).read_from(&path)?.universal_srs,
+            false => None,
+        };
 
         // Remove the checksum from the output directory
         ChecksumFile::new(&package_name).remove(&path)?;
 
-        // Remove the serialized circuit from the output directory
+        // Remove the serialized circuit, in either format, from the output directory
         CircuitFile::new(&package_name).remove(&path)?;
-
-        // Remove the program output file from the output directory
-        OutputFile::new(&package_name).remove(&path)?;
-
-        // Remove the proving key from the output directory
-        ProvingKeyFile::new(&package_name).remove(&path)?;
-
-        // Remove the verification key from the output directory
-        VerificationKeyFile::new(&package_name).remove(&path)?;
-
-        // Remove the proof from the output directory
-        ProofFile::new(&package_name).remove(&path)?;
+        CircuitFile::with_format(&package_name, CircuitFormat::Binary).remove(&path)?;
+
+        // Remove the witness of every input pair from the output directory
+        for witness in input_files(&path, WITNESS_FILE_EXTENSION) {
+            WitnessFile::new(&witness).remove(&path)?;
+        }
+
+        // Remove the exported circuit files from the output directory
+        for format in ExportFormat::ALL {
+            ExportFile::new(&package_name, format).remove(&path)?;
+        }
+
+        // Remove the constraint profile from the output directory
+        ProfileFile::new(&package_name).remove(&path)?;
+
+        // Remove the program output file of every input pair from the output directory
+        for output in input_files(&path, OUTPUT_FILE_EXTENSION) {
+            OutputFile::new(&output).remove(&path)?;
+        }
+
+        // Remove the keys of either proving system from the output directory
+        for proving_system in [ProvingSystem::Groth16, ProvingSystem::Marlin] {
+            ProvingKeyFile::with_proving_system(&package_name, proving_system).remove(&path)?;
+            VerificationKeyFile::with_proving_system(&package_name, proving_system).remove(&path)?;
+        }
+
+        // Remove the metadata of the keys and proofs from the output directory
+        for metadata in input_files(&path, METADATA_FILE_EXTENSION) {
+            MetadataFile::new(&metadata).remove(&path)?;
+        }
+
+        // Keep the universal SRS, which is reused across circuits, and how it was generated
+        if let Some(universal_srs) = universal_srs {
+            let metadata = Metadata {
+                universal_srs: Some(universal_srs),
+                ..Default::default()
+            };
+            MetadataFile::new(&package_name).write_to(&path, &metadata)?;
+        }
+
+        // Remove the proof of every input pair from the output directory
+        for proof in input_files(&path, PROOF_FILE_EXTENSION) {
+            ProofFile::new(&proof).remove(&path)?;
+        }
 
         // Remove AST snapshots from the output directory
         SnapshotFile::new(&package_name, Snapshot::Initial).remove(&path)?;
@@ -72,3 +112,16 @@ impl Command for Clean {
         Ok(())
     }
 }
+
+/// Returns the input pair names of the files with the given extension in the outputs directory.
+fn input_files(path: &Path, extension: &str) -> Vec<String> {
+    let entries = match fs::read_dir(path.join(OUTPUTS_DIRECTORY_NAME)) {
+        Ok(entries) => entries,
+        Err(_) => return Vec::new(),
+    };
+
+    entries
+        .filter_map(|entry| entry.ok()?.file_name().into_string().ok())
+        .filter_map(|name| name.strip_suffix(extension).map(str::to_string))
+        .collect()
+}
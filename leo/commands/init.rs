@@ -24,7 +24,10 @@ use tracing::span::Span;
 /// Init Leo project command within current directory
 #[derive(StructOpt, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
-pub struct Init {}
+pub struct Init {
+    #[structopt(long, help = "Create a library package without a main function or inputs")]
+    lib: bool,
+}
 
 impl Command for Init {
     type Input = ();
@@ -59,7 +62,11 @@ impl Command for Init {
 
         let username = read_username().ok();
 
-        LeoPackage::initialize(&package_name, &path, username)?;
+        if self.lib {
+            LeoPackage::initialize_library(&package_name, &path, username)?;
+        } else {
+            LeoPackage::initialize(&package_name, &path, username)?;
+        }
 
         Ok(())
     }
@@ -40,13 +40,13 @@ pub mod new;
 pub use new::New;
 
 pub mod prove;
-pub use prove::Prove;
+pub use prove::{ProgramProof, Prove, ProveInput};
 
 pub mod run;
 pub use run::Run;
 
 pub mod setup;
-pub use setup::Setup;
+pub use setup::{ProgramKeys, Setup};
 
 pub mod test;
 pub use test::Test;
@@ -57,6 +57,9 @@ pub use update::{Automatic as UpdateAutomatic, Update};
 pub mod watch;
 pub use watch::Watch;
 
+pub mod witness;
+pub use witness::Witness;
+
 // Aleo PM related commands
 pub mod package;
 
@@ -126,4 +129,17 @@ pub trait Command {
     {
         self.execute(context).map(|_| Ok(()))?
     }
+
+    /// Executes command in every member of the workspace that contains the current package.
+    fn try_execute_workspace(self, context: Context) -> Result<()>
+    where
+        Self: std::marker::Sized + Clone,
+    {
+        for member in context.workspace_members()? {
+            tracing::info!("Workspace member {}", member.dir()?.display());
+            self.clone().try_execute(member)?;
+        }
+
+        Ok(())
+    }
 }
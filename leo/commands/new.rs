@@ -28,6 +28,9 @@ use tracing::span::Span;
 pub struct New {
     #[structopt(name = "NAME", help = "Set package name")]
     name: String,
+
+    #[structopt(long, help = "Create a library package without a main function or inputs")]
+    lib: bool,
 }
 
 impl Command for New {
@@ -63,7 +66,11 @@ impl Command for New {
         // Create the package directory
         fs::create_dir_all(&path).map_err(CliError::package_could_not_create_directory)?;
 
-        LeoPackage::initialize(&package_name, &path, username)?;
+        if self.lib {
+            LeoPackage::initialize_library(&package_name, &path, username)?;
+        } else {
+            LeoPackage::initialize(&package_name, &path, username)?;
+        }
 
         Ok(())
     }
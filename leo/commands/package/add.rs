@@ -18,7 +18,7 @@
 // COMMAND TEMPORARILY DISABLED
 // !!!!!!!!!!!!!!!!!!!!!!!!!!!!
 
-use crate::{api::Fetch, commands::Command, context::Context};
+use crate::{commands::Command, context::Context};
 use leo_errors::{CliError, Result};
 use leo_package::imports::{ImportsDirectory, IMPORTS_DIRECTORY_NAME};
 
@@ -103,16 +103,9 @@ impl Command for Add {
 
         // Attempt to fetch the package.
         let reader = {
-            let fetch = Fetch {
-                author: author.clone(),
-                package_name: package_name.clone(),
-                version: self.version.clone(),
-            };
             let bytes = context
-                .api
-                .run_route(fetch)?
-                .bytes()
-                .map_err(CliError::cli_bytes_conversion_error)?;
+                .registry
+                .fetch(&author, &package_name, self.version.as_deref())?;
             std::io::Cursor::new(bytes)
         };
 
@@ -145,7 +138,7 @@ impl Command for Add {
             if file_name.ends_with('/') {
                 create_dir_all(file_path).map_err(CliError::cli_io_error)?;
             } else {
-                if let Some(parent_directory) = path.parent() {
+                if let Some(parent_directory) = file_path.parent() {
                     create_dir_all(parent_directory).map_err(CliError::cli_io_error)?;
                 }
 
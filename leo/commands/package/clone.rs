@@ -14,7 +14,7 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{api::Fetch, commands::Command, context::Context};
+use crate::{commands::Command, context::Context};
 use leo_errors::{CliError, Result};
 
 use std::{
@@ -107,16 +107,9 @@ impl Command for Clone {
 
         // Attempt to fetch the package.
         let reader = {
-            let fetch = Fetch {
-                author,
-                package_name: package_name.clone(),
-                version: self.version,
-            };
             let bytes = context
-                .api
-                .run_route(fetch)?
-                .bytes()
-                .map_err(CliError::cli_bytes_conversion_error)?;
+                .registry
+                .fetch(&author, &package_name, self.version.as_deref())?;
             std::io::Cursor::new(bytes)
         };
 
@@ -139,7 +132,7 @@ impl Command for Clone {
             if file_name.ends_with('/') {
                 fs::create_dir_all(file_path).map_err(CliError::cli_io_error)?;
             } else {
-                if let Some(parent_directory) = path.parent() {
+                if let Some(parent_directory) = file_path.parent() {
                     fs::create_dir_all(parent_directory).map_err(CliError::cli_io_error)?;
                 }
 
@@ -0,0 +1,107 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::tree::{describe, edge_json, read_lock_file};
+use crate::{commands::Command, context::Context};
+use leo_errors::{CliError, Result};
+use leo_package::{graph::DependencyGraph, root::lock_file::LockFile};
+
+use serde_json::json;
+use structopt::StructOpt;
+use tracing::span::Span;
+
+/// Inspect the resolved dependencies in Leo lock
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct Deps {
+    #[structopt(subcommand)]
+    command: DepsCommand,
+}
+
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub enum DepsCommand {
+    #[structopt(about = "Explain why a package is included in the dependency graph")]
+    Why {
+        #[structopt(name = "PACKAGE", help = "Package name or import name")]
+        package: String,
+
+        #[structopt(
+            long,
+            help = "Print the import chains as JSON to stdout, with `-q` to leave out the logs"
+        )]
+        json: bool,
+    },
+}
+
+impl Command for Deps {
+    type Input = LockFile;
+    type Output = String;
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Deps")
+    }
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        read_lock_file(&context)
+    }
+
+    fn apply(self, _context: Context, lock_file: Self::Input) -> Result<Self::Output> {
+        let DepsCommand::Why { package, json } = self.command;
+
+        let graph = DependencyGraph::new(&lock_file);
+        let chains = graph.why(&package);
+        if chains.is_empty() {
+            return Err(CliError::package_not_in_dependencies(package).into());
+        }
+
+        let output = match json {
+            true => {
+                let chains = chains
+                    .iter()
+                    .map(|chain| chain.iter().map(|edge| edge_json(*edge)).collect::<Vec<_>>())
+                    .collect::<Vec<_>>();
+                json!({ "package": package, "chains": chains }).to_string()
+            }
+            false => {
+                // A name may match several packages, group the chains by the package they lead to.
+                let mut output = String::new();
+                let mut previous = None;
+                for chain in chains.iter() {
+                    let target = chain[chain.len() - 1].package;
+                    if previous.is_none_or(|previous| !std::ptr::eq(previous, target)) {
+                        if previous.is_some() {
+                            output.push('\n');
+                        }
+                        output.push_str(&format!("{} is included by:", describe(target)));
+                        previous = Some(target);
+                    }
+
+                    let chain = chain.iter().map(|edge| edge.import_name).collect::<Vec<_>>();
+                    output.push_str(&format!("\n  {}", chain.join(" -> ")));
+                }
+                output
+            }
+        };
+
+        // JSON is printed without the log formatting, so tools can parse it.
+        match json {
+            true => println!("{}", output),
+            false => tracing::info!("{}", output),
+        }
+        Ok(output)
+    }
+}
@@ -16,16 +16,23 @@
 
 use crate::{
     commands::{package::Add, Command},
-    context::{create_context, Context},
+    context::Context,
 };
 
-use leo_package::root::{
-    lock_file::{LockFile, Package},
-    Dependency,
+use leo_package::{
+    imports::{ImportsDirectory, IMPORTS_DIRECTORY_NAME},
+    resolver::{Fetched, PackageIndex, Pinned, Resolver},
+    root::{
+        lock_file::{LockFile, Package, Source},
+        Dependency, Manifest,
+    },
 };
 
-use indexmap::{set::IndexSet, IndexMap};
 use leo_errors::{CliError, Result};
+
+use indexmap::IndexMap;
+use semver::Version;
+use std::{collections::HashMap, convert::TryFrom, fs, process};
 use structopt::StructOpt;
 use tracing::span::Span;
 
@@ -35,8 +42,7 @@ use tracing::span::Span;
 pub struct Fetch {}
 
 impl Command for Fetch {
-    /// Names of dependencies in the current branch of a dependency tree.
-    type Input = IndexSet<String>;
+    type Input = Option<LockFile>;
     type Output = ();
 
     fn log_span(&self) -> Span {
@@ -44,101 +50,247 @@ impl Command for Fetch {
     }
 
     fn prelude(&self, context: Context) -> Result<Self::Input> {
-        let package_name = context.manifest()?.get_package_name();
+        // Keep the versions of an existing lock file where possible.
+        if context.lock_file_exists()? {
+            Ok(Some(context.lock_file()?))
+        } else {
+            Ok(None)
+        }
+    }
 
-        let mut set = IndexSet::new();
-        set.insert(package_name);
+    fn apply(self, context: Context, lock_file: Self::Input) -> Result<Self::Output> {
+        resolve_dependencies(&context, lock_file.as_ref())
+    }
+}
 
-        Ok(set)
+///
+/// Resolves the dependencies in the manifest, pulls them, and writes the lock file.
+///
+/// Registry versions from `lock_file` are kept while they satisfy the manifest requirements.
+/// The members of a workspace are resolved together into the lock file of the workspace root.
+///
+pub(crate) fn resolve_dependencies(context: &Context, lock_file: Option<&LockFile>) -> Result<()> {
+    if let Some(root) = context.workspace_root()? {
+        return resolve_workspace(&context.with_path(root), lock_file);
     }
 
-    fn apply(self, context: Context, tree: Self::Input) -> Result<Self::Output> {
-        let dependencies = context
-            .manifest()
-            .map_err(|_| CliError::manifest_file_not_found())?
-            .get_package_dependencies();
+    let manifest = context.manifest().map_err(|_| CliError::manifest_file_not_found())?;
 
-        // If program has no dependencies in the Leo.toml, exit with success.
-        let dependencies = match dependencies {
-            Some(dependencies) => dependencies,
-            None => return Ok(()),
-        };
+    // If program has no dependencies in the Leo.toml, exit with success.
+    let dependencies = match manifest.get_package_dependencies() {
+        Some(dependencies) if !dependencies.is_empty() => dependencies,
+        _ => return Ok(()),
+    };
+
+    resolve(context, &manifest.get_package_name(), &dependencies, lock_file)
+}
+
+///
+/// Resolves the dependencies of every member of the workspace at the root of the context.
+///
+/// The members are resolved as path dependencies of the workspace root, so each of them has
+/// a record listing its own dependencies and every registry package is selected once.
+///
+fn resolve_workspace(context: &Context, lock_file: Option<&LockFile>) -> Result<()> {
+    let root = context.dir()?;
+
+    let mut dependencies = IndexMap::new();
+    for member in context.workspace_members()? {
+        let path = member.dir()?;
+        if path == root {
+            continue;
+        }
+
+        let manifest = member.manifest().map_err(|_| CliError::manifest_file_not_found())?;
+        dependencies.insert(manifest.get_package_name(), Dependency::Path { path });
+    }
 
-        if dependencies.is_empty() {
-            return Ok(());
+    // The workspace root may be a package itself.
+    let name = match context.manifest() {
+        Ok(manifest) => {
+            dependencies.extend(manifest.get_package_dependencies().unwrap_or_default());
+            manifest.get_package_name()
         }
+        Err(_) => root
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    };
+
+    resolve(context, &name, &dependencies, lock_file)
+}
 
-        let mut lock_file = LockFile::new();
-        self.add_dependencies(context.clone(), tree, &mut lock_file, dependencies)?;
-        lock_file.write_to(&context.dir()?)?;
+/// Resolves the given dependencies of the package in the context directory and writes the lock file there.
+fn resolve(
+    context: &Context,
+    name: &str,
+    dependencies: &IndexMap<String, Dependency>,
+    lock_file: Option<&LockFile>,
+) -> Result<()> {
+    let mut index = ContextIndex::new(context.clone());
+    let mut resolver = Resolver::new(&mut index);
+    if let Some(lock_file) = lock_file {
+        resolver = resolver.with_lock_file(lock_file);
+    }
+
+    let path = context.dir()?;
+    let resolved = resolver.resolve(name, &path, dependencies)?;
 
-        Ok(())
+    for package in resolved.package.iter() {
+        tracing::info!("Resolved {} v{}", package.name, package.version);
     }
+
+    resolved.write_to(&path)
 }
 
-impl Fetch {
-    /// Pulls dependencies and fills in the lock file. Also checks for
-    /// recursive dependencies with dependency tree.
-    fn add_dependencies(
-        &self,
-        context: Context,
-        mut tree: IndexSet<String>,
-        lock_file: &mut LockFile,
-        dependencies: IndexMap<String, Dependency>,
-    ) -> Result<()> {
-        // Go through each dependency in Leo.toml and add it to the imports.
-        // While adding, pull dependencies of this package as well and check for recursion.
-        for (import_name, dependency) in dependencies.into_iter() {
-            let mut package = Package::from(&dependency);
-            package.import_name = Some(import_name);
-
-            // Pull the dependency first.
-            let path = Add::new(
-                None,
-                Some(package.author.clone()),
-                Some(package.name.clone()),
-                Some(package.version.clone()),
-            )
-            .apply(context.clone(), ())?;
-
-            // Try inserting a new dependency to the branch. If not inserted,
-            // then fail because this dependency was added on a higher level.
-            if !tree.insert(package.name.clone()) {
-                // Pretty format for the message - show dependency structure.
-                let mut message: Vec<String> = tree
-                    .into_iter()
-                    .enumerate()
-                    .map(|(i, val)| format!("{}└─{}", " ".repeat(i * 2), val))
-                    .collect();
-
-                message.push(format!("{}└─{} (FAILURE)", " ".repeat(message.len() * 2), package.name));
-
-                return Err(CliError::recursive_dependency_found(message.join("\n")).into());
-            }
+/// Lists versions from the package registry and pulls dependencies into `imports/`.
+struct ContextIndex {
+    context: Context,
+    fetched: HashMap<Pinned, Fetched>,
+}
 
-            // Check imported dependency's dependencies.
-            let imported_dependencies = create_context(path, None)?
-                .manifest()
-                .map_err(|_| CliError::unable_to_read_imported_dependency_manifest())?
-                .get_package_dependencies();
+impl ContextIndex {
+    fn new(context: Context) -> Self {
+        Self {
+            context,
+            fetched: HashMap::new(),
+        }
+    }
 
-            if let Some(dependencies) = imported_dependencies {
-                if !dependencies.is_empty() {
-                    // Fill in the lock file with imported dependency and information about its dependencies.
-                    package.add_dependencies(&dependencies);
-                    lock_file.add_package(package);
+    ///
+    /// Clones a git dependency into `imports/`, under a directory named after the checked out commit.
+    ///
+    /// The URL and revision come from manifests that may not be trusted, so neither is passed
+    /// to git where it could be read as an option.
+    ///
+    fn clone_git(&self, import_name: &str, git: &str, rev: Option<&str>) -> Result<Fetched> {
+        if let Some(value) = std::iter::once(git).chain(rev).find(|value| value.starts_with('-')) {
+            return Err(CliError::invalid_git_dependency_argument(import_name, value).into());
+        }
 
-                    // Recursively call this method for imported program.
-                    self.add_dependencies(context.clone(), tree.clone(), lock_file, dependencies)?;
+        let package_path = self.context.dir()?;
+        ImportsDirectory::create(&package_path)?;
+        let imports_directory = package_path.join(IMPORTS_DIRECTORY_NAME);
 
-                    continue;
+        // Clone into a scratch directory, the final name depends on the checked out commit.
+        let checkout = imports_directory.join(format!(".{}-checkout", import_name));
+        if checkout.exists() {
+            fs::remove_dir_all(&checkout).map_err(CliError::cli_io_error)?;
+        }
+
+        run_git(
+            import_name,
+            process::Command::new("git")
+                .args(["clone", "--quiet", "--", git])
+                .arg(&checkout),
+        )?;
+        if let Some(rev) = rev {
+            let commit = run_git(
+                import_name,
+                process::Command::new("git").current_dir(&checkout).args([
+                    "rev-parse",
+                    "--verify",
+                    &format!("{}^{{commit}}", rev),
+                ]),
+            )?;
+            run_git(
+                import_name,
+                process::Command::new("git")
+                    .current_dir(&checkout)
+                    .args(["checkout", "--quiet", commit.trim()]),
+            )?;
+        }
+        let commit = run_git(
+            import_name,
+            process::Command::new("git")
+                .current_dir(&checkout)
+                .args(["rev-parse", "HEAD"]),
+        )?;
+
+        let source = Source::Git {
+            git: git.to_string(),
+            rev: commit.trim().to_string(),
+        };
+        let manifest = Manifest::try_from(checkout.as_path())
+            .map_err(|_| CliError::unable_to_read_imported_dependency_manifest())?;
+        let package = Package::new(
+            manifest.get_package_name(),
+            manifest.get_package_version(),
+            String::new(),
+            Some(source.clone()),
+        );
+
+        let path = imports_directory.join(package.import_path());
+        if path.exists() {
+            fs::remove_dir_all(&path).map_err(CliError::cli_io_error)?;
+        }
+        fs::rename(&checkout, &path).map_err(CliError::cli_io_error)?;
+
+        Ok(Fetched {
+            path,
+            source: Some(source),
+        })
+    }
+}
+
+impl PackageIndex for ContextIndex {
+    fn versions(&mut self, author: &str, package: &str) -> Result<Vec<Version>> {
+        let versions = self.context.registry.versions(author, package)?;
+
+        Ok(versions
+            .iter()
+            .filter_map(|version| Version::parse(version).ok())
+            .collect())
+    }
+
+    fn fetch(&mut self, import_name: &str, package: &Pinned) -> Result<Fetched> {
+        if let Some(fetched) = self.fetched.get(package) {
+            return Ok(fetched.clone());
+        }
+
+        let fetched = match package {
+            Pinned::Registry {
+                author,
+                package,
+                version,
+            } => Fetched {
+                path: Add::new(
+                    None,
+                    Some(author.clone()),
+                    Some(package.clone()),
+                    Some(version.to_string()),
+                )
+                .apply(self.context.clone(), ())?,
+                source: None,
+            },
+            Pinned::Path { path } => {
+                if !Manifest::exists_at(path) {
+                    return Err(CliError::dependency_path_not_found(import_name, path).into());
+                }
+
+                Fetched {
+                    path: path.clone(),
+                    source: Some(Source::Path { path: path.clone() }),
                 }
             }
+            Pinned::Git { git, rev } => self.clone_git(import_name, git, rev.as_deref())?,
+        };
 
-            // If there are no dependencies for the new import, add a single record.
-            lock_file.add_package(package);
-        }
+        self.fetched.insert(package.clone(), fetched.clone());
+        Ok(fetched)
+    }
+}
 
-        Ok(())
+/// Runs a git command and returns its standard output.
+fn run_git(import_name: &str, command: &mut process::Command) -> Result<String> {
+    let output = command
+        .output()
+        .map_err(|error| CliError::failed_to_fetch_git_dependency(import_name, error))?;
+
+    if !output.status.success() {
+        let error = String::from_utf8_lossy(&output.stderr);
+        return Err(CliError::failed_to_fetch_git_dependency(import_name, error.trim()).into());
     }
+
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
 }
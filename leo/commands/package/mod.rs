@@ -20,6 +20,9 @@ pub use add::Add;
 pub mod clone;
 pub use clone::Clone;
 
+pub mod deps;
+pub use deps::Deps;
+
 pub mod fetch;
 pub use fetch::Fetch;
 
@@ -35,4 +38,13 @@ pub use publish::Publish;
 pub mod remove;
 pub use remove::Remove;
 
+pub mod tree;
+pub use tree::Tree;
+
+pub mod update_deps;
+pub use update_deps::UpdateDeps;
+
+pub mod yank;
+pub use yank::Yank;
+
 pub use super::*;
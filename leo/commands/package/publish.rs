@@ -15,7 +15,7 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::build::Build;
-use crate::{api::Publish as PublishRoute, commands::Command, context::Context};
+use crate::{commands::Command, context::Context};
 use leo_errors::{CliError, Result};
 use leo_package::{
     outputs::OutputsDirectory,
@@ -23,18 +23,27 @@ use leo_package::{
 };
 use leo_parser::KEYWORD_TOKENS;
 
+use semver::Version;
+use std::fs;
 use structopt::StructOpt;
 
+/// Files larger than this are reported during a dry run, they are rarely meant to be published.
+const LARGE_FILE_SIZE: u64 = 1024 * 1024;
+
 /// Publish package to Aleo Package Manager
 #[derive(StructOpt, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
-pub struct Publish {}
+pub struct Publish {
+    #[structopt(long, help = "Validate the package and write its zip file without uploading it")]
+    dry_run: bool,
+}
 
 impl Command for Publish {
     type Input = <Build as Command>::Output;
     type Output = String;
 
-    /// Build program before publishing
+    /// Build program before publishing, which also checks that every import resolves
+    /// within the package and its dependencies.
     fn prelude(&self, context: Context) -> Result<Self::Input> {
         (Build {
             compiler_options: Default::default(),
@@ -53,6 +62,9 @@ impl Command for Publish {
         }
 
         let package_version = manifest.get_package_version();
+        if let Err(error) = Version::parse(&package_version) {
+            return Err(CliError::invalid_package_version(package_version, error).into());
+        }
 
         match (
             manifest.get_package_description(),
@@ -85,13 +97,35 @@ impl Command for Publish {
         }
         zip_file.write(&path)?;
 
-        // Make an API request with zip file and package data.
-        let package_id = context.api.run_route(PublishRoute {
-            name: package_name.clone(),
-            remote: format!("{}/{}", package_remote.author, package_name),
-            version: package_version,
-            file: zip_file.get_file_path(&path).into(),
-        })?;
+        if self.dry_run {
+            for file in zip_file.included_files(&path)? {
+                let size = fs::metadata(path.join(&file)).map_err(CliError::cli_io_error)?.len();
+                tracing::info!("Including {} ({} bytes)", file.display(), size);
+
+                if size > LARGE_FILE_SIZE {
+                    tracing::warn!(
+                        "{} is larger than {} bytes, make sure it is meant to be published",
+                        file.display(),
+                        LARGE_FILE_SIZE
+                    );
+                }
+            }
+
+            let zip_path = zip_file.get_file_path(&path);
+            tracing::info!("Dry run complete, package zip file written to {}", zip_path.display());
+            return Ok(format!(
+                "{}/{}@{}",
+                package_remote.author, package_name, package_version
+            ));
+        }
+
+        // Upload the zip file and package data to the registry.
+        let package_id = context.registry.publish(
+            &package_remote.author,
+            &package_name,
+            &package_version,
+            &zip_file.get_file_path(&path),
+        )?;
 
         tracing::info!("Package published successfully with id: {}", &package_id);
         Ok(package_id)
@@ -0,0 +1,203 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+use leo_errors::{CliError, Result};
+use leo_package::{
+    graph::{DependencyGraph, Edge},
+    root::{
+        lock_file::{LockFile, Package, Source},
+        Manifest,
+    },
+};
+
+use serde_json::{json, Value};
+use std::{collections::HashSet, convert::TryFrom};
+use structopt::StructOpt;
+use tracing::span::Span;
+
+/// Print the resolved dependency graph from Leo lock
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct Tree {
+    #[structopt(long, help = "Print the graph as JSON to stdout, with `-q` to leave out the logs")]
+    json: bool,
+}
+
+impl Command for Tree {
+    type Input = LockFile;
+    type Output = String;
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Tree")
+    }
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        read_lock_file(&context)
+    }
+
+    fn apply(self, context: Context, lock_file: Self::Input) -> Result<Self::Output> {
+        let graph = DependencyGraph::new(&lock_file);
+        let root = root_name(&context)?;
+
+        let output = match self.json {
+            true => {
+                let duplicates = graph
+                    .duplicates()
+                    .into_iter()
+                    .map(|(name, packages)| (name.to_string(), packages.into_iter().map(package_json).collect()))
+                    .collect::<serde_json::Map<String, Value>>();
+
+                let dependencies = graph
+                    .roots()
+                    .into_iter()
+                    .map(|edge| tree_json(&graph, edge, &mut Vec::new()))
+                    .collect::<Vec<_>>();
+
+                json!({
+                    "name": root,
+                    "dependencies": dependencies,
+                    "duplicates": duplicates,
+                })
+                .to_string()
+            }
+            false => {
+                let mut output = root;
+                let mut expanded = HashSet::new();
+                render_edges(&graph, graph.roots(), "", &mut expanded, &mut output);
+
+                let duplicates = graph.duplicates();
+                if !duplicates.is_empty() {
+                    output.push_str("\n\nDuplicate packages:");
+                    for packages in duplicates.values() {
+                        for package in packages {
+                            output.push_str(&format!("\n{}", describe(package)));
+                        }
+                    }
+                }
+                output
+            }
+        };
+
+        // JSON is printed without the log formatting, so tools can parse it.
+        match self.json {
+            true => println!("{}", output),
+            false => tracing::info!("{}", output),
+        }
+        Ok(output)
+    }
+}
+
+/// Reads the lock file, which only exists once the dependencies have been fetched.
+pub(crate) fn read_lock_file(context: &Context) -> Result<LockFile> {
+    if !context.lock_file_exists()? {
+        return Err(CliError::dependencies_are_not_installed().into());
+    }
+    context.lock_file()
+}
+
+/// Returns the name of the package owning the lock file, or of its directory for a workspace.
+fn root_name(context: &Context) -> Result<String> {
+    let directory = context.lock_file_dir()?;
+    Ok(match Manifest::try_from(directory.as_path()) {
+        Ok(manifest) => format!("{} v{}", manifest.get_package_name(), manifest.get_package_version()),
+        Err(_) => directory
+            .file_name()
+            .map(|name| name.to_string_lossy().into_owned())
+            .unwrap_or_default(),
+    })
+}
+
+/// Describes a package with its version and where it comes from.
+pub(crate) fn describe(package: &Package) -> String {
+    match &package.source {
+        None => format!("{}/{} v{}", package.author, package.name, package.version),
+        Some(Source::Path { path }) => format!("{} v{} ({})", package.name, package.version, path.display()),
+        Some(Source::Git { git, rev }) => format!(
+            "{} v{} ({}#{})",
+            package.name,
+            package.version,
+            git,
+            &rev[..rev.len().min(12)]
+        ),
+    }
+}
+
+/// Describes a dependency, along with the name it is imported under if that differs.
+fn describe_edge(edge: Edge) -> String {
+    match edge.import_name == edge.package.name {
+        true => describe(edge.package),
+        false => format!("{} as {}", describe(edge.package), edge.import_name),
+    }
+}
+
+pub(crate) fn package_json(package: &Package) -> Value {
+    json!({
+        "name": package.name,
+        "version": package.version,
+        "author": package.author,
+        "source": package.source,
+    })
+}
+
+pub(crate) fn edge_json(edge: Edge) -> Value {
+    let mut value = package_json(edge.package);
+    value["import_name"] = json!(edge.import_name);
+    value
+}
+
+fn tree_json<'a>(graph: &DependencyGraph<'a>, edge: Edge<'a>, stack: &mut Vec<&'a Package>) -> Value {
+    stack.push(edge.package);
+    let mut dependencies = Vec::new();
+    for dependency in graph.dependencies(edge.package) {
+        if !stack.iter().any(|package| std::ptr::eq(*package, dependency.package)) {
+            dependencies.push(tree_json(graph, dependency, stack));
+        }
+    }
+    stack.pop();
+
+    let mut value = edge_json(edge);
+    value["dependencies"] = json!(dependencies);
+    value
+}
+
+/// Renders the dependencies below `prefix`, packages already shown are marked with `(*)`.
+fn render_edges<'a>(
+    graph: &DependencyGraph<'a>,
+    edges: Vec<Edge<'a>>,
+    prefix: &str,
+    expanded: &mut HashSet<*const Package>,
+    output: &mut String,
+) {
+    let count = edges.len();
+    for (i, edge) in edges.into_iter().enumerate() {
+        let last = i + 1 == count;
+        let (branch, indent) = match last {
+            true => ("└── ", "    "),
+            false => ("├── ", "│   "),
+        };
+
+        let dependencies = graph.dependencies(edge.package);
+        let repeated = !dependencies.is_empty() && !expanded.insert(edge.package as *const Package);
+
+        output.push_str(&format!("\n{}{}{}", prefix, branch, describe_edge(edge)));
+        if repeated {
+            output.push_str(" (*)");
+        } else {
+            render_edges(graph, dependencies, &format!("{}{}", prefix, indent), expanded, output);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{commands::Command, context::Context};
+use leo_errors::{CliError, Result};
+
+use structopt::StructOpt;
+use tracing::Span;
+
+/// Yank a published version of the current package, or restore it with `--undo`
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct Yank {
+    #[structopt(name = "VERSION", help = "Version of the current package to yank")]
+    version: String,
+
+    #[structopt(long, help = "Restore a yanked version")]
+    undo: bool,
+}
+
+impl Command for Yank {
+    type Input = ();
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Yanking")
+    }
+
+    fn prelude(&self, _: Context) -> Result<Self::Input> {
+        Ok(())
+    }
+
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let manifest = context.manifest()?;
+        let package_name = manifest.get_package_name();
+        let package_remote = manifest
+            .get_package_remote()
+            .ok_or_else(CliError::missing_package_remote)?;
+
+        context
+            .registry
+            .yank(&package_remote.author, &package_name, &self.version, !self.undo)?;
+
+        match self.undo {
+            true => tracing::info!("Restored {} v{}", package_name, self.version),
+            false => tracing::info!("Yanked {} v{}", package_name, self.version),
+        }
+        Ok(())
+    }
+}
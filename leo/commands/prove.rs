@@ -14,18 +14,52 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use super::{build::BuildOptions, setup::Setup};
-use crate::{commands::Command, context::Context};
-use leo_errors::{CliError, Result, SnarkVMError};
-use leo_package::outputs::ProofFile;
+use super::{
+    build::{read_circuit, resolved_imports_map, BuildOptions},
+    setup::{ProgramKeys, Setup, SynthesisFailure},
+};
+use crate::{
+    commands::Command,
+    context::Context,
+    rng::{proving_rng, SEED_HELP},
+};
+use leo_compiler::{
+    compiler::{Compiler, CompilerSession},
+    group::targets::edwards_bls12::EdwardsGroupType,
+    CompilerOptions, OutputFile,
+};
+use leo_errors::{CliError, LeoError, PackageError, Result, SendableLeoError, SnarkVMError};
+use leo_package::{
+    inputs::{InputPair, InputPairs, INPUTS_DIRECTORY_NAME, INPUT_FILE_EXTENSION, STATE_FILE_EXTENSION},
+    outputs::{
+        CircuitFile, Envelope, MetadataFile, ProofFile, Provenance, ProvingKeyFile, VerificationKeyFile, WitnessFile,
+        MARLIN_PROVING_KEY_FILE_EXTENSION, MARLIN_VERIFICATION_KEY_FILE_EXTENSION, METADATA_FILE_EXTENSION,
+        OUTPUTS_DIRECTORY_NAME, PROOF_FILE_EXTENSION, PROVING_KEY_FILE_EXTENSION, VERIFICATION_KEY_FILE_EXTENSION,
+        WITNESS_FILE_EXTENSION,
+    },
+    root::{Curve, Manifest, ProvingSystem, Target},
+    source::{MAIN_FILENAME, SOURCE_DIRECTORY_NAME},
+};
+use leo_synthesizer::{CircuitSynthesizer, SerializedWitness, SummarizedCircuit};
 use snarkvm_algorithms::{
-    snark::groth16::{Groth16, PreparedVerifyingKey, Proof},
+    snark::groth16::{Groth16, PreparedVerifyingKey, Proof, ProvingKey, VerifyingKey},
     traits::SNARK,
 };
 use snarkvm_curves::bls12_377::{Bls12_377, Fr};
-use snarkvm_utilities::bytes::ToBytes;
+use snarkvm_marlin::{
+    marlin::Proof as MarlinProof, MarlinTestnet1, MultiPC, ProvingKey as MarlinProvingKey,
+    VerifyingKey as MarlinVerifyingKey,
+};
+use snarkvm_utilities::bytes::{FromBytes, ToBytes};
 
-use rand::thread_rng;
+use indexmap::IndexMap;
+use rand::{RngCore, SeedableRng};
+use rand_chacha::ChaChaRng;
+use rayon::prelude::*;
+use std::{
+    convert::TryFrom,
+    path::{Path, PathBuf},
+};
 use structopt::StructOpt;
 use tracing::span::Span;
 
@@ -36,45 +70,530 @@ pub struct Prove {
     #[structopt(long = "skip-key-check", help = "Skip key verification on Setup stage")]
     pub(crate) skip_key_check: bool,
 
+    #[structopt(long, help = SEED_HELP)]
+    pub(crate) seed: Option<u64>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        help = "Prove a `.json` or `.circuit` file written by `leo build`, using the keys next to it, without compiling the package"
+    )]
+    pub(crate) circuit: Option<PathBuf>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        requires = "circuit",
+        help = "The witness to prove the circuit with, by default the `.witness` file next to the circuit"
+    )]
+    pub(crate) witness: Option<PathBuf>,
+
+    #[structopt(
+        long = "proving-system",
+        requires = "circuit",
+        parse(try_from_str = parse_proving_system),
+        help = "The proving system of the circuit, `groth16` or `marlin`, by default the one the package targets"
+    )]
+    pub(crate) proving_system: Option<ProvingSystem>,
+
+    #[structopt(
+        long = "all-inputs",
+        conflicts_with = "circuit",
+        help = "Prove every input pair in `inputs/`, writing `outputs/<input>.proof` and `outputs/<input>.out` for each"
+    )]
+    pub(crate) all_inputs: bool,
+
+    #[structopt(
+        long,
+        requires = "all-inputs",
+        help = "The number of input pairs to prove at once, by default one per CPU"
+    )]
+    pub(crate) jobs: Option<usize>,
+
     #[structopt(flatten)]
     pub(crate) compiler_options: BuildOptions,
 }
 
+fn parse_proving_system(proving_system: &str) -> std::result::Result<ProvingSystem, String> {
+    match proving_system {
+        "groth16" => Ok(ProvingSystem::Groth16),
+        "marlin" => Ok(ProvingSystem::Marlin),
+        _ => Err(format!(
+            "unknown proving system `{}`, expected `groth16` or `marlin`",
+            proving_system
+        )),
+    }
+}
+
+/// The circuit to prove: the compiled package with its keys, or a serialized circuit file, its
+/// witness and the proving system of its keys.
+pub enum ProveInput {
+    Program(Box<<Setup as Command>::Output>),
+    Circuit(PathBuf, Option<PathBuf>, ProvingSystem),
+}
+
+/// A proof of a program, with the key to verify it in the proving system it was produced by.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
+pub enum ProgramProof {
+    Groth16(Proof<Bls12_377>, PreparedVerifyingKey<Bls12_377>),
+    Marlin(MarlinProof<Fr, MultiPC<Bls12_377>>, MarlinVerifyingKey<Bls12_377>),
+}
+
+impl ProgramProof {
+    /// Returns `true` if the proof is valid for its verifying key.
+    pub fn verify(&self) -> Result<bool> {
+        let is_valid = match self {
+            ProgramProof::Groth16(proof, prepared_verifying_key) => {
+                Groth16::<Bls12_377, Compiler<Fr, EdwardsGroupType>, Vec<Fr>>::verify(
+                    prepared_verifying_key,
+                    &vec![],
+                    proof,
+                )
+                .map_err(|_| SnarkVMError::default())?
+            }
+            ProgramProof::Marlin(proof, verifying_key) => {
+                MarlinTestnet1::<Bls12_377>::verify(verifying_key, &[], proof).map_err(|_| SnarkVMError::default())?
+            }
+        };
+        Ok(is_valid)
+    }
+
+    /// Returns the proof in the given bytes, with the verifying key in the given bytes.
+    pub fn read(proving_system: ProvingSystem, proof: &[u8], verifying_key: &[u8]) -> Result<Self> {
+        let program_proof = match proving_system {
+            ProvingSystem::Groth16 => {
+                let proof = Proof::<Bls12_377>::read_le(proof).map_err(CliError::cli_io_error)?;
+                let verifying_key = VerifyingKey::<Bls12_377>::read(verifying_key).map_err(CliError::cli_io_error)?;
+                ProgramProof::Groth16(proof, PreparedVerifyingKey::from(verifying_key))
+            }
+            ProvingSystem::Marlin => {
+                let proof = MarlinProof::read_le(proof).map_err(CliError::cli_io_error)?;
+                let verifying_key =
+                    MarlinVerifyingKey::<Bls12_377>::read_le(verifying_key).map_err(CliError::cli_io_error)?;
+                ProgramProof::Marlin(proof, verifying_key)
+            }
+        };
+        Ok(program_proof)
+    }
+
+    /// Returns the proof as bytes, as it is written to the proof file.
+    fn to_bytes(&self) -> Result<Vec<u8>> {
+        let mut proof = vec![];
+        match self {
+            ProgramProof::Groth16(proof_object, _) => proof_object.write_le(&mut proof),
+            ProgramProof::Marlin(proof_object, _) => proof_object.write_le(&mut proof),
+        }
+        .map_err(CliError::cli_io_error)?;
+        Ok(proof)
+    }
+}
+
 impl Command for Prove {
-    type Input = <Setup as Command>::Output;
-    type Output = (Proof<Bls12_377>, PreparedVerifyingKey<Bls12_377>);
+    type Input = ProveInput;
+    type Output = ProgramProof;
 
     fn log_span(&self) -> Span {
         tracing::span!(tracing::Level::INFO, "Proving")
     }
 
     fn prelude(&self, context: Context) -> Result<Self::Input> {
+        // A serialized circuit is proven as is, so the package is neither built nor set up
+        if let Some(circuit) = &self.circuit {
+            let path = context.dir()?;
+            let circuit = path.join(circuit);
+            let witness = self.witness.as_ref().map(|witness| path.join(witness));
+
+            // Keys of another proving system may be left next to the circuit, so it is not guessed from them
+            let proving_system = match self.proving_system {
+                Some(proving_system) => proving_system,
+                None if Manifest::exists_at(&path) => context.target()?.proving_system,
+                None => return Err(CliError::circuit_proving_system_unknown(circuit).into()),
+            };
+            return Ok(ProveInput::Circuit(circuit, witness, proving_system));
+        }
+
         (Setup {
             skip_key_check: self.skip_key_check,
+            seed: self.seed,
+            import: None,
+            circuit_hash: None,
+            universal_srs: None,
+            replace_imported_keys: false,
             compiler_options: self.compiler_options.clone(),
         })
         .execute(context)
+        .map(|setup| ProveInput::Program(Box::new(setup)))
     }
 
     fn apply(self, context: Context, input: Self::Input) -> Result<Self::Output> {
-        let (program, parameters, prepared_verifying_key) = input;
+        let (program, keys, envelope) = match input {
+            ProveInput::Program(setup) => *setup,
+            ProveInput::Circuit(circuit_path, witness_path, proving_system) => {
+                return self.prove_circuit(&circuit_path, witness_path, proving_system);
+            }
+        };
 
         // Get the package name
         let path = context.dir()?;
         let package_name = context.manifest()?.get_package_name();
 
+        if self.all_inputs {
+            return self.prove_all_inputs(&context, &package_name, &keys, &envelope);
+        }
+
         tracing::info!("Starting...");
 
-        let rng = &mut thread_rng();
-        // TODO fix this once snarkvm has better errors.
-        let program_proof =
-            Groth16::<Bls12_377, _, Vec<Fr>>::prove(&parameters, &program, rng).map_err(|_| SnarkVMError::default())?;
+        let rng = &mut proving_rng(self.seed);
+        let program_proof = match keys {
+            ProgramKeys::Groth16(parameters, prepared_verifying_key) => {
+                let proof = Groth16::<Bls12_377, _, Vec<Fr>>::prove(&parameters, &program, rng).map_err(prove_error)?;
+                ProgramProof::Groth16(proof, prepared_verifying_key)
+            }
+            ProgramKeys::Marlin(proving_key, verifying_key) => {
+                let proof = MarlinTestnet1::<Bls12_377>::prove(&proving_key, &program, rng).map_err(prove_error)?;
+                ProgramProof::Marlin(proof, verifying_key)
+            }
+        };
 
         // Write the proof file to the output directory
-        let mut proof = vec![];
-        program_proof.write_le(&mut proof).map_err(CliError::cli_io_error)?;
-        ProofFile::new(&package_name).write_to(&path, &proof)?;
+        ProofFile::new(&package_name).write_to(&path, &envelope, &program_proof.to_bytes()?)?;
+        self.record_proof(&MetadataFile::new(&package_name), &path)?;
+
+        Ok(program_proof)
+    }
+}
+
+impl Prove {
+    /// Returns a proof of the package with keys and a proof generated from the given seed, as in `Setup::with_seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            skip_key_check: false,
+            seed: Some(seed),
+            circuit: None,
+            witness: None,
+            proving_system: None,
+            all_inputs: false,
+            jobs: None,
+            compiler_options: Default::default(),
+        }
+    }
+
+    ///
+    /// Proves the given serialized circuit file with the given witness, using the keys of the
+    /// given proving system.
+    ///
+    /// The keys, the proof and the default witness are named after the circuit file and kept
+    /// next to it, as `leo build` and `leo setup` write them in the outputs directory.
+    ///
+    fn prove_circuit(
+        &self,
+        circuit_path: &Path,
+        witness_path: Option<PathBuf>,
+        proving_system: ProvingSystem,
+    ) -> Result<<Self as Command>::Output> {
+        let name = circuit_path
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().to_string())
+            .unwrap_or_default();
+        let directory = circuit_path.parent().unwrap_or_else(|| Path::new("."));
+
+        tracing::info!("Loading circuit... ({:?})", circuit_path);
+        let (mut circuit, has_assignments) = read_circuit(&CircuitFile::new(&name), circuit_path)?;
+
+        // The keys must have been set up for the shape of the circuit, which a witness does not change
+        let summary = SummarizedCircuit::from(&circuit);
+
+        // A circuit shape is proven with a witness, by default the one `leo build` writes next to it
+        let explicit = witness_path.is_some();
+        let witness_path =
+            witness_path.unwrap_or_else(|| directory.join(format!("{}{}", name, WITNESS_FILE_EXTENSION)));
+        if explicit || witness_path.exists() {
+            tracing::info!("Loading witness... ({:?})", witness_path);
+            let witness = WitnessFile::new(&name).read_from(&witness_path)?;
+            circuit.set_witness(&SerializedWitness::from_json_string(&witness)?)?;
+        } else if !has_assignments {
+            return Err(CliError::circuit_witness_not_found(witness_path.display()).into());
+        }
+
+        // An unsatisfied witness would only produce a proof that fails to verify
+        if let Some(constraint) = circuit.which_is_unsatisfied() {
+            return Err(CliError::circuit_not_satisfied(constraint).into());
+        }
 
-        Ok((program_proof, prepared_verifying_key))
+        let (proving_key_extension, verifying_key_extension) = match proving_system {
+            ProvingSystem::Groth16 => (PROVING_KEY_FILE_EXTENSION, VERIFICATION_KEY_FILE_EXTENSION),
+            ProvingSystem::Marlin => (
+                MARLIN_PROVING_KEY_FILE_EXTENSION,
+                MARLIN_VERIFICATION_KEY_FILE_EXTENSION,
+            ),
+        };
+        let proving_key_path = directory.join(format!("{}{}", name, proving_key_extension));
+        let verifying_key_path = directory.join(format!("{}{}", name, verifying_key_extension));
+        if !proving_key_path.exists() || !verifying_key_path.exists() {
+            return Err(CliError::circuit_keys_not_found(circuit_path, proving_system).into());
+        }
+
+        tracing::info!("Loading proving key...");
+        if self.skip_key_check {
+            tracing::info!("Skipping curve check");
+        }
+        let (proving_key_envelope, proving_key_bytes) =
+            ProvingKeyFile::with_proving_system(&name, proving_system).read_from(&proving_key_path)?;
+        let (verifying_key_envelope, verifying_key_bytes) =
+            VerificationKeyFile::with_proving_system(&name, proving_system).read_from(&verifying_key_path)?;
+
+        // The circuit file does not name its package, so the keys must agree with each other on it
+        let envelope = Envelope::new(
+            &proving_key_envelope.package_name,
+            &proving_key_envelope.package_version,
+            Target {
+                curve: Curve::Bls12_377,
+                proving_system,
+            },
+            &summary.checksum(),
+            summary.num_public_variables,
+        );
+        proving_key_envelope.check(&envelope, &proving_key_path)?;
+        verifying_key_envelope.check(&envelope, &verifying_key_path)?;
+
+        tracing::info!("Starting...");
+
+        let rng = &mut proving_rng(self.seed);
+        let circuit_proof = match proving_system {
+            ProvingSystem::Groth16 => {
+                let proving_key = ProvingKey::<Bls12_377>::read(proving_key_bytes.as_slice(), !self.skip_key_check)
+                    .map_err(CliError::cli_io_error)?;
+                let verifying_key =
+                    VerifyingKey::<Bls12_377>::read(verifying_key_bytes.as_slice()).map_err(CliError::cli_io_error)?;
+                let proof =
+                    Groth16::<Bls12_377, CircuitSynthesizer<Bls12_377>, Vec<Fr>>::prove(&proving_key, &circuit, rng)
+                        .map_err(|_| SnarkVMError::default())?;
+                ProgramProof::Groth16(proof, PreparedVerifyingKey::from(verifying_key))
+            }
+            ProvingSystem::Marlin => {
+                let proving_key = MarlinProvingKey::<Bls12_377>::read_le(proving_key_bytes.as_slice())
+                    .map_err(CliError::cli_io_error)?;
+                let verifying_key = MarlinVerifyingKey::<Bls12_377>::read_le(verifying_key_bytes.as_slice())
+                    .map_err(CliError::cli_io_error)?;
+                let proof = MarlinTestnet1::<Bls12_377>::prove(&proving_key, &circuit, rng)
+                    .map_err(|_| SnarkVMError::default())?;
+                ProgramProof::Marlin(proof, verifying_key)
+            }
+        };
+
+        // Write the proof file next to the circuit
+        let proof_path = directory.join(format!("{}{}", name, PROOF_FILE_EXTENSION));
+        ProofFile::new(&name).write_to(&proof_path, &envelope, &circuit_proof.to_bytes()?)?;
+        let metadata_path = directory.join(format!("{}{}", name, METADATA_FILE_EXTENSION));
+        self.record_proof(&MetadataFile::new(&name), &metadata_path)?;
+
+        Ok(circuit_proof)
     }
+
+    ///
+    /// Proves the program for every input pair in the inputs directory, with keys loaded once.
+    ///
+    /// The input pairs are proven in parallel, each compiled, synthesized and proven by one
+    /// worker, so that at most one circuit per job is in memory. A pair that fails does not
+    /// stop the others: the failures are summarized once every proof is written. Returns the
+    /// proof of the package input pair, which `leo prove` proves by default.
+    ///
+    fn prove_all_inputs(
+        &self,
+        context: &Context,
+        package_name: &str,
+        keys: &ProgramKeys,
+        envelope: &Envelope,
+    ) -> Result<ProgramProof> {
+        let path = context.dir()?;
+        let input_pairs = InputPairs::try_from(path.as_path())?;
+        let mut input_names = input_pairs.pairs.keys().cloned().collect::<Vec<_>>();
+        input_names.sort();
+        let total = input_names.len();
+
+        let sources = ProgramSources::new(context, package_name, &self.compiler_options)?;
+
+        // Each circuit is proven from its own generator, drawn in order so seeded batches are reproducible
+        let mut rng = proving_rng(self.seed);
+        let input_names = input_names
+            .into_iter()
+            .map(|name| (name, rng.next_u64()))
+            .collect::<Vec<_>>();
+
+        tracing::info!("Proving {} input pairs...", total);
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.jobs.unwrap_or_default())
+            .build()
+            .map_err(CliError::failed_to_build_thread_pool)?;
+        let results = pool.install(|| {
+            input_names
+                .into_par_iter()
+                .map(|(name, seed)| {
+                    let pair = &input_pairs.pairs[&name];
+                    let proof = self
+                        .prove_input_pair(&sources, &path, &name, pair, keys, envelope, seed)
+                        .map(|proof| (name == package_name).then_some(proof))
+                        .map_err(SendableLeoError::new);
+                    (name, proof)
+                })
+                .collect::<Vec<_>>()
+        });
+
+        let mut package_proof = None;
+        let mut failures = vec![];
+        for (name, result) in results {
+            match result {
+                Ok(proof) => package_proof = package_proof.or(proof),
+                Err(error) => failures.push((name, error.into_inner())),
+            }
+        }
+
+        tracing::info!("Proved {} of {} input pairs", total - failures.len(), total);
+        if !failures.is_empty() {
+            for (name, error) in failures.iter() {
+                tracing::error!("Failed to prove inputs `{}` - {}", name, error);
+            }
+            let names = failures
+                .iter()
+                .map(|(name, _)| format!("`{}`", name))
+                .collect::<Vec<_>>()
+                .join(", ");
+            return Err(CliError::inputs_not_proven(failures.len(), total, names).into());
+        }
+
+        package_proof.ok_or_else(|| {
+            let input_path = path
+                .join(INPUTS_DIRECTORY_NAME)
+                .join(format!("{}{}", package_name, INPUT_FILE_EXTENSION));
+            PackageError::failed_to_read_input_file(input_path).into()
+        })
+    }
+
+    ///
+    /// Proves the program for the given input pair, and writes the proof of the pair to
+    /// `outputs/<input>.proof`.
+    ///
+    #[allow(clippy::too_many_arguments)]
+    fn prove_input_pair(
+        &self,
+        sources: &ProgramSources,
+        path: &Path,
+        name: &str,
+        pair: &InputPair,
+        keys: &ProgramKeys,
+        envelope: &Envelope,
+        seed: u64,
+    ) -> Result<ProgramProof> {
+        tracing::info!("Proving inputs `{}`...", name);
+        let circuit = synthesize_input_pair(sources, path, name, pair, envelope)?;
+        let proof = prove_synthesized(keys, &circuit, &mut ChaChaRng::seed_from_u64(seed))?;
+
+        ProofFile::new(name).write_to(path, envelope, &proof.to_bytes()?)?;
+        self.record_proof(&MetadataFile::new(name), path)?;
+        Ok(proof)
+    }
+
+    /// Records in the given metadata file whether the proof was produced deterministically.
+    fn record_proof(&self, metadata_file: &MetadataFile, path: &Path) -> Result<()> {
+        let mut metadata = metadata_file.read_from(path)?;
+        metadata.proof = Some(Provenance::from_seed(self.seed));
+        metadata_file.write_to(path, &metadata)
+    }
+}
+
+/// Returns the error that made proving the program fail.
+fn prove_error(error: impl SynthesisFailure) -> LeoError {
+    error.into_leo_error().unwrap_or_else(|| SnarkVMError::default().into())
+}
+
+///
+/// The sources of a package program, from which each worker compiles the program again, as a
+/// compiled program cannot be shared between threads.
+///
+struct ProgramSources {
+    package_name: String,
+    main_file_path: PathBuf,
+    output_directory: PathBuf,
+    options: CompilerOptions,
+    imports_map: IndexMap<String, String>,
+}
+
+impl ProgramSources {
+    fn new(context: &Context, package_name: &str, compiler_options: &BuildOptions) -> Result<Self> {
+        let path = context.dir()?;
+        Ok(Self {
+            package_name: package_name.to_string(),
+            main_file_path: path.join(SOURCE_DIRECTORY_NAME).join(MAIN_FILENAME),
+            output_directory: path.join(OUTPUTS_DIRECTORY_NAME),
+            options: compiler_options.clone().into(),
+            imports_map: resolved_imports_map(context, &path)?,
+        })
+    }
+}
+
+///
+/// Synthesizes the circuit of the program for the given input pair, compiling the program in
+/// a session of its own, and writes its output registers to `outputs/<input>.out`.
+///
+fn synthesize_input_pair(
+    sources: &ProgramSources,
+    path: &Path,
+    name: &str,
+    pair: &InputPair,
+    envelope: &Envelope,
+) -> Result<CircuitSynthesizer<Bls12_377>> {
+    let inputs_directory = path.join(INPUTS_DIRECTORY_NAME);
+    let input_path = inputs_directory.join(format!("{}{}", name, INPUT_FILE_EXTENSION));
+    let state_path = inputs_directory.join(format!("{}{}", name, STATE_FILE_EXTENSION));
+
+    let session = CompilerSession::new();
+    let program = Compiler::<Fr, EdwardsGroupType>::parse_program_with_input(
+        sources.package_name.clone(),
+        sources.main_file_path.clone(),
+        sources.output_directory.clone(),
+        &pair.input_file,
+        &input_path,
+        &pair.state_file,
+        &state_path,
+        session.context(),
+        Some(sources.options.clone()),
+        sources.imports_map.clone(),
+        None,
+    )?;
+    let mut circuit = CircuitSynthesizer::<Bls12_377>::default();
+    let output = program.compile_constraints(&mut circuit)?;
+
+    // The keys only prove circuits of the shape they were set up for
+    if SummarizedCircuit::from(&circuit).checksum() != envelope.circuit_hash {
+        return Err(CliError::witness_circuit_mismatch(name).into());
+    }
+
+    // An unsatisfied circuit would only produce a proof that fails to verify
+    if let Some(constraint) = circuit.which_is_unsatisfied() {
+        return Err(CliError::circuit_not_satisfied(constraint).into());
+    }
+
+    OutputFile::new(name).write(path, output.to_string().as_bytes())?;
+    Ok(circuit)
+}
+
+/// Proves the given synthesized circuit with the given keys.
+fn prove_synthesized(
+    keys: &ProgramKeys,
+    circuit: &CircuitSynthesizer<Bls12_377>,
+    rng: &mut ChaChaRng,
+) -> Result<ProgramProof> {
+    let program_proof = match keys {
+        ProgramKeys::Groth16(proving_key, prepared_verifying_key) => {
+            let proof = Groth16::<Bls12_377, CircuitSynthesizer<Bls12_377>, Vec<Fr>>::prove(proving_key, circuit, rng)
+                .map_err(prove_error)?;
+            ProgramProof::Groth16(proof, prepared_verifying_key.clone())
+        }
+        ProgramKeys::Marlin(proving_key, verifying_key) => {
+            let proof = MarlinTestnet1::<Bls12_377>::prove(proving_key, circuit, rng).map_err(prove_error)?;
+            ProgramProof::Marlin(proof, verifying_key.clone())
+        }
+    };
+    Ok(program_proof)
 }
@@ -14,13 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use super::{build::BuildOptions, prove::Prove};
-use crate::{commands::Command, context::Context};
-use leo_compiler::{compiler::Compiler, group::targets::edwards_bls12::EdwardsGroupType};
-use leo_errors::{Result, SnarkVMError};
+use super::{
+    build::BuildOptions,
+    prove::{ProgramProof, Prove},
+};
+use crate::{commands::Command, context::Context, rng::SEED_HELP};
+use leo_errors::Result;
+use leo_package::outputs::{ProofFile, VerificationKeyFile};
 
-use snarkvm_algorithms::{snark::groth16::Groth16, traits::SNARK};
-use snarkvm_curves::bls12_377::{Bls12_377, Fr};
 use structopt::StructOpt;
 use tracing::span::Span;
 
@@ -31,10 +32,24 @@ pub struct Run {
     #[structopt(long = "skip-key-check", help = "Skip key verification on Setup stage")]
     pub(crate) skip_key_check: bool,
 
+    #[structopt(long, help = SEED_HELP)]
+    pub(crate) seed: Option<u64>,
+
     #[structopt(flatten)]
     pub(crate) compiler_options: BuildOptions,
 }
 
+impl Run {
+    /// Returns a run of the package that proves it from the given seed, as in `Prove::with_seed`.
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            skip_key_check: false,
+            seed: Some(seed),
+            compiler_options: Default::default(),
+        }
+    }
+}
+
 impl Command for Run {
     type Input = <Prove as Command>::Output;
     type Output = ();
@@ -46,23 +61,33 @@ impl Command for Run {
     fn prelude(&self, context: Context) -> Result<Self::Input> {
         (Prove {
             skip_key_check: self.skip_key_check,
+            seed: self.seed,
+            circuit: None,
+            witness: None,
+            proving_system: None,
+            all_inputs: false,
+            jobs: None,
             compiler_options: self.compiler_options.clone(),
         })
         .execute(context)
     }
 
-    fn apply(self, _context: Context, input: Self::Input) -> Result<Self::Output> {
-        let (proof, prepared_verifying_key) = input;
+    fn apply(self, context: Context, _: Self::Input) -> Result<Self::Output> {
+        let path = context.dir()?;
+        let package_name = context.manifest()?.get_package_name();
+        let proving_system = context.target()?.proving_system;
 
         tracing::info!("Starting...");
 
+        // Verify the proof as it was written, against the verification key it was produced for
+        let verification_key_file = VerificationKeyFile::with_proving_system(&package_name, proving_system);
+        let (verifying_key_envelope, verifying_key) = verification_key_file.read_from(&path)?;
+        let proof_file = ProofFile::new(&package_name);
+        let (proof_envelope, proof) = proof_file.read_from(&path)?;
+        proof_envelope.check(&verifying_key_envelope, &proof_file.full_path(&path))?;
+
         // Run the verifier
-        let is_success = Groth16::<Bls12_377, Compiler<Fr, EdwardsGroupType>, Vec<Fr>>::verify(
-            &prepared_verifying_key,
-            &vec![],
-            &proof,
-        )
-        .map_err(|_| SnarkVMError::default())?;
+        let is_success = ProgramProof::read(proving_system, &proof, &verifying_key)?.verify()?;
 
         // Log the verifier output
         match is_success {
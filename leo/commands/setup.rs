@@ -15,22 +15,53 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use super::build::{Build, BuildOptions};
-use crate::{commands::Command, context::Context};
-use leo_compiler::{compiler::Compiler, group::targets::edwards_bls12::EdwardsGroupType};
-use leo_errors::{CliError, Result};
-use leo_package::outputs::{ProvingKeyFile, VerificationKeyFile};
+use crate::{
+    commands::Command,
+    context::Context,
+    rng::{proving_rng, SEED_HELP},
+};
+use leo_compiler::{
+    compiler::{synthesis_leo_error, Compiler},
+    group::targets::edwards_bls12::EdwardsGroupType,
+};
+use leo_errors::{CliError, LeoError, Result};
+use leo_package::{
+    outputs::{Envelope, Metadata, MetadataFile, Provenance, ProvingKeyFile, UniversalSrsFile, VerificationKeyFile},
+    root::ProvingSystem,
+    source::{LibFile, MainFile},
+};
+use leo_synthesizer::SummarizedCircuit;
 
 use snarkvm_algorithms::{
+    errors::SNARKError,
+    fft::EvaluationDomain,
     snark::groth16::{Groth16, PreparedVerifyingKey, ProvingKey, VerifyingKey},
     traits::snark::SNARK,
 };
 use snarkvm_curves::bls12_377::{Bls12_377, Fr};
-use snarkvm_utilities::ToBytes;
+use snarkvm_marlin::{
+    marlin::MarlinError, AHPError, AHPForR1CS, MarlinTestnet1, MultiPC, PolynomialCommitment,
+    ProvingKey as MarlinProvingKey, VerifyingKey as MarlinVerifyingKey, SRS,
+};
+use snarkvm_polycommit::PCUniversalParams;
+use snarkvm_r1cs::SynthesisError;
+use snarkvm_utilities::{CanonicalDeserialize, FromBytes, ToBytes};
 
-use rand::thread_rng;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
 use structopt::StructOpt;
 use tracing::span::Span;
 
+/// The circuit-specific keys of a program, for the proving system its package targets.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone)]
+pub enum ProgramKeys {
+    Groth16(ProvingKey<Bls12_377>, PreparedVerifyingKey<Bls12_377>),
+    Marlin(MarlinProvingKey<Bls12_377>, MarlinVerifyingKey<Bls12_377>),
+}
+
 /// Executes the setup command for a Leo program
 #[derive(StructOpt, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
@@ -38,23 +69,62 @@ pub struct Setup {
     #[structopt(long = "skip-key-check", help = "Skip key verification")]
     pub(crate) skip_key_check: bool,
 
+    #[structopt(long, help = SEED_HELP)]
+    pub(crate) seed: Option<u64>,
+
+    #[structopt(
+        long,
+        parse(from_os_str),
+        conflicts_with = "seed",
+        help = "Import Groth16 parameters produced by a ceremony, in the canonical serialization of snarkVM and arkworks, instead of generating keys"
+    )]
+    pub(crate) import: Option<PathBuf>,
+
+    #[structopt(
+        long = "circuit-hash",
+        requires = "import",
+        help = "The hash of the circuit the imported parameters were produced for, as reported by `leo build`, to check before they are read"
+    )]
+    pub(crate) circuit_hash: Option<String>,
+
+    #[structopt(
+        long = "universal-srs",
+        parse(from_os_str),
+        conflicts_with = "seed",
+        help = "Index the circuit against the Marlin universal SRS at the given path, as `leo setup` writes it, instead of the saved or a generated one"
+    )]
+    pub(crate) universal_srs: Option<PathBuf>,
+
+    #[structopt(
+        long = "replace-imported-keys",
+        conflicts_with = "import",
+        help = "Generate new keys even if the saved keys were imported, such as from a ceremony"
+    )]
+    pub(crate) replace_imported_keys: bool,
+
     #[structopt(flatten)]
     pub(crate) compiler_options: BuildOptions,
 }
 
 impl Command for Setup {
     type Input = <Build as Command>::Output;
-    type Output = (
-        Compiler<'static, Fr, EdwardsGroupType>,
-        ProvingKey<Bls12_377>,
-        PreparedVerifyingKey<Bls12_377>,
-    );
+    type Output = (Compiler<'static, Fr, EdwardsGroupType>, ProgramKeys, Envelope);
 
     fn log_span(&self) -> Span {
         tracing::span!(tracing::Level::INFO, "Setup")
     }
 
     fn prelude(&self, context: Context) -> Result<Self::Input> {
+        // Library packages have no circuit to set up
+        let path = context.dir()?;
+        if !MainFile::exists_at(&path) && LibFile::exists_at(&path) {
+            return Err(CliError::library_package_has_no_circuit(context.manifest()?.get_package_name()).into());
+        }
+
+        // Fail before building if the package targets a backend that cannot be set up
+        let target = context.target()?;
+        tracing::debug!("Targeting {} over {}", target.proving_system, target.curve);
+
         (Build {
             compiler_options: self.compiler_options.clone(),
         })
@@ -63,49 +133,104 @@ impl Command for Setup {
 
     fn apply(self, context: Context, input: Self::Input) -> Result<Self::Output> {
         let path = context.dir()?;
-        let package_name = context.manifest()?.get_package_name();
+        let manifest = context.manifest()?;
+        let package_name = manifest.get_package_name();
+        let target = context.target()?;
 
         // Check if leo build failed
-        let (program, checksum_differs) = input;
+        let (program, _, circuit) = input;
+        let circuit = circuit.ok_or_else(|| CliError::library_package_has_no_circuit(&package_name))?;
 
-        // Check if a proving key and verification key already exists
-        let keys_exist = ProvingKeyFile::new(&package_name).exists_at(&path)
-            && VerificationKeyFile::new(&package_name).exists_at(&path);
+        // The keys are written in an envelope naming the circuit they are set up for
+        let envelope = Envelope::new(
+            &package_name,
+            &manifest.get_package_version(),
+            target,
+            &circuit.checksum(),
+            circuit.num_public_variables,
+        );
 
-        // If keys do not exist or the checksum differs, run the program setup
-        let (proving_key, prepared_verifying_key) = if !keys_exist || checksum_differs {
-            tracing::info!("Starting...");
+        // Saved keys are reused while their envelope names the circuit, if they were produced the way
+        // this setup would produce them. Imported keys stand in for keys from fresh randomness.
+        let metadata_file = MetadataFile::new(&package_name);
+        let mut metadata = metadata_file.read_from(&path)?;
+        let saved_provenance = metadata.keys.unwrap_or_default();
+        let keep_imported = saved_provenance.imported && self.import.is_none() && !self.replace_imported_keys;
+        let provenance = match (&self.import, self.seed) {
+            (Some(_), _) => Provenance::imported(),
+            (None, None) if keep_imported => saved_provenance,
+            (None, seed) => Provenance::from_seed(seed),
+        };
+        let keys_outdated = saved_provenance != provenance;
 
-            // Run the program setup operation
-            let rng = &mut thread_rng();
-            let (proving_key, prepared_verifying_key) =
-                Groth16::<Bls12_377, Compiler<Fr, _>, Vec<Fr>>::setup(&program, rng)
-                    .map_err(|_| CliError::unable_to_setup())?;
-
-            // TODO (howardwu): Convert parameters to a 'proving key' struct for serialization.
-            // Write the proving key file to the output directory
-            let proving_key_file = ProvingKeyFile::new(&package_name);
-            tracing::info!("Saving proving key ({:?})", proving_key_file.full_path(&path));
-            let mut proving_key_bytes = vec![];
-            proving_key
-                .write_le(&mut proving_key_bytes)
-                .map_err(CliError::cli_io_error)?;
-            let _ = proving_key_file.write_to(&path, &proving_key_bytes)?;
-            tracing::info!("Complete");
+        if self.universal_srs.is_some() && target.proving_system != ProvingSystem::Marlin {
+            return Err(CliError::universal_srs_unsupported(target.proving_system).into());
+        }
 
-            // Write the verification key file to the output directory
-            let verification_key_file = VerificationKeyFile::new(&package_name);
-            tracing::info!("Saving verification key ({:?})", verification_key_file.full_path(&path));
-            let mut verification_key = vec![];
-            proving_key
-                .vk
-                .write_le(&mut verification_key)
-                .map_err(CliError::cli_io_error)?;
-            let _ = verification_key_file.write_to(&path, &verification_key)?;
-            tracing::info!("Complete");
+        let keys = match (&self.import, target.proving_system) {
+            (Some(parameters), ProvingSystem::Groth16) => {
+                self.import_groth16(&program, &circuit, &path.join(parameters), &path, &envelope)?
+            }
+            (Some(_), proving_system) => return Err(CliError::parameters_import_unsupported(proving_system).into()),
+            (None, ProvingSystem::Groth16) => {
+                self.setup_groth16(&program, &path, &envelope, keys_outdated, keep_imported)?
+            }
+            (None, ProvingSystem::Marlin) => {
+                self.setup_marlin(&program, &path, &envelope, keys_outdated, &mut metadata)?
+            }
+        };
 
-            (proving_key, prepared_verifying_key)
-        } else {
+        // Record whether the keys were generated deterministically or imported
+        metadata.keys = Some(provenance);
+        metadata_file.write_to(&path, &metadata)?;
+
+        Ok((program, keys, envelope))
+    }
+}
+
+impl Setup {
+    ///
+    /// Returns a setup that generates the keys from the given seed, so they are reproducible.
+    ///
+    /// This is INSECURE: anyone who knows the seed can forge proofs, so it is only meant for tests.
+    ///
+    pub fn with_seed(seed: u64) -> Self {
+        Self {
+            skip_key_check: false,
+            seed: Some(seed),
+            import: None,
+            circuit_hash: None,
+            universal_srs: None,
+            replace_imported_keys: false,
+            compiler_options: Default::default(),
+        }
+    }
+
+    ///
+    /// Returns the Groth16 keys of the program, generating new ones from a circuit-specific
+    /// trusted setup if there are none or they are outdated. Saved keys that were imported are
+    /// not replaced unless `keep_imported` is false.
+    ///
+    fn setup_groth16(
+        &self,
+        program: &Compiler<'static, Fr, EdwardsGroupType>,
+        path: &Path,
+        envelope: &Envelope,
+        keys_outdated: bool,
+        keep_imported: bool,
+    ) -> Result<ProgramKeys> {
+        let package_name = &envelope.package_name;
+        let saved_keys = saved_keys(
+            &ProvingKeyFile::new(package_name),
+            &VerificationKeyFile::new(package_name),
+            path,
+            envelope,
+            keys_outdated,
+            keep_imported,
+        )?;
+
+        // If keys do not exist or are outdated, run the program setup
+        let (proving_key, prepared_verifying_key) = if let Some((proving_key_bytes, verifying_key_bytes)) = saved_keys {
             tracing::info!("Detected saved setup");
 
             // Read the proving key file from the output directory
@@ -114,16 +239,12 @@ impl Command for Setup {
             if self.skip_key_check {
                 tracing::info!("Skipping curve check");
             }
-            let proving_key_bytes = ProvingKeyFile::new(&package_name).read_from(&path)?;
             let proving_key = ProvingKey::<Bls12_377>::read(proving_key_bytes.as_slice(), !self.skip_key_check)
                 .map_err(CliError::cli_io_error)?;
             tracing::info!("Complete");
 
             // Read the verification key file from the output directory
             tracing::info!("Loading verification key...");
-            let verifying_key_bytes = VerificationKeyFile::new(&package_name)
-                .read_from(&path)
-                .map_err(CliError::cli_io_error)?;
             let verifying_key =
                 VerifyingKey::<Bls12_377>::read(verifying_key_bytes.as_slice()).map_err(CliError::cli_io_error)?;
 
@@ -131,9 +252,381 @@ impl Command for Setup {
             let prepared_verifying_key = PreparedVerifyingKey::<Bls12_377>::from(verifying_key);
             tracing::info!("Complete");
 
+            (proving_key, prepared_verifying_key)
+        } else {
+            tracing::info!("Starting...");
+
+            // Run the program setup operation
+            let rng = &mut proving_rng(self.seed);
+            let (proving_key, prepared_verifying_key) =
+                Groth16::<Bls12_377, Compiler<Fr, _>, Vec<Fr>>::setup(program, rng).map_err(setup_error)?;
+
+            write_groth16_keys(&proving_key, path, envelope)?;
+
             (proving_key, prepared_verifying_key)
         };
 
-        Ok((program, proving_key, prepared_verifying_key))
+        Ok(ProgramKeys::Groth16(proving_key, prepared_verifying_key))
+    }
+
+    ///
+    /// Returns the Groth16 keys of the program from parameters produced externally, such as by a
+    /// multi-party ceremony, after checking that they were produced for its circuit.
+    ///
+    /// Parameters of another circuit may have the same shape, so the package inputs are proven
+    /// with them, and the proof must verify against their verification key.
+    ///
+    fn import_groth16(
+        &self,
+        program: &Compiler<'static, Fr, EdwardsGroupType>,
+        circuit: &SummarizedCircuit,
+        parameters_path: &Path,
+        path: &Path,
+        envelope: &Envelope,
+    ) -> Result<ProgramKeys> {
+        // The hash given on the command line is checked before the parameters are read
+        if let Some(expected_hash) = &self.circuit_hash {
+            let expected_hash = expected_hash.trim().to_lowercase();
+            if expected_hash != envelope.circuit_hash {
+                return Err(CliError::parameters_circuit_hash_mismatch(expected_hash, &envelope.circuit_hash).into());
+            }
+        }
+
+        tracing::info!("Importing parameters... ({:?})", parameters_path);
+        let parameters_bytes =
+            fs::read(parameters_path).map_err(|error| CliError::failed_to_read_parameters(parameters_path, error))?;
+        let parameters = ProvingKey::<Bls12_377>::deserialize(&mut parameters_bytes.as_slice())
+            .map_err(|error| CliError::failed_to_read_parameters(parameters_path, error))?;
+        check_groth16_shape(&parameters, circuit)?;
+
+        // Convert the parameters to the layout of snarkVM, checking their points unless asked not to
+        if self.skip_key_check {
+            tracing::info!("Skipping curve check");
+        }
+        let mut proving_key_bytes = vec![];
+        parameters
+            .write_le(&mut proving_key_bytes)
+            .map_err(CliError::cli_io_error)?;
+        let proving_key = ProvingKey::<Bls12_377>::read(proving_key_bytes.as_slice(), !self.skip_key_check)
+            .map_err(|error| CliError::failed_to_read_parameters(parameters_path, error))?;
+        tracing::info!("Complete");
+
+        tracing::info!("Checking the parameters against the circuit...");
+        let prepared_verifying_key = PreparedVerifyingKey::from(proving_key.vk.clone());
+        let proof = Groth16::<Bls12_377, _, Vec<Fr>>::prove(&proving_key, program, &mut proving_rng(None))
+            .map_err(setup_error)?;
+        let is_valid = Groth16::<Bls12_377, Compiler<Fr, EdwardsGroupType>, Vec<Fr>>::verify(
+            &prepared_verifying_key,
+            &vec![],
+            &proof,
+        )
+        .map_err(setup_error)?;
+        if !is_valid {
+            return Err(CliError::parameters_circuit_mismatch(parameters_path).into());
+        }
+        tracing::info!("Complete");
+
+        write_groth16_keys(&proving_key, path, envelope)?;
+
+        Ok(ProgramKeys::Groth16(proving_key, prepared_verifying_key))
     }
+
+    ///
+    /// Returns the Marlin keys of the program, indexing the circuit against the universal SRS
+    /// of the package if there are none or they are outdated.
+    ///
+    fn setup_marlin(
+        &self,
+        program: &Compiler<'static, Fr, EdwardsGroupType>,
+        path: &Path,
+        envelope: &Envelope,
+        keys_outdated: bool,
+        metadata: &mut Metadata,
+    ) -> Result<ProgramKeys> {
+        let package_name = &envelope.package_name;
+        let proving_key_file = ProvingKeyFile::with_proving_system(package_name, ProvingSystem::Marlin);
+        let verification_key_file = VerificationKeyFile::with_proving_system(package_name, ProvingSystem::Marlin);
+
+        // The circuit is indexed again against a given universal SRS
+        let keys_outdated = keys_outdated || self.universal_srs.is_some();
+        let saved_keys = saved_keys(
+            &proving_key_file,
+            &verification_key_file,
+            path,
+            envelope,
+            keys_outdated,
+            false,
+        )?;
+        if let Some((proving_key_bytes, verifying_key_bytes)) = saved_keys {
+            tracing::info!("Detected saved setup");
+
+            // Marlin keys are always checked as they are read
+            tracing::info!("Loading proving key...");
+            let proving_key =
+                MarlinProvingKey::<Bls12_377>::read_le(proving_key_bytes.as_slice()).map_err(CliError::cli_io_error)?;
+            tracing::info!("Complete");
+
+            tracing::info!("Loading verification key...");
+            let verifying_key = MarlinVerifyingKey::<Bls12_377>::read_le(verifying_key_bytes.as_slice())
+                .map_err(CliError::cli_io_error)?;
+            tracing::info!("Complete");
+
+            return Ok(ProgramKeys::Marlin(proving_key, verifying_key));
+        }
+
+        tracing::info!("Starting...");
+        let universal_srs_path = self
+            .universal_srs
+            .as_ref()
+            .map(|universal_srs| path.join(universal_srs));
+        let universal_srs = universal_srs(program, path, package_name, universal_srs_path, self.seed, metadata)?;
+
+        // Index the circuit, which is deterministic given the universal SRS
+        let (proving_key, verifying_key) =
+            MarlinTestnet1::<Bls12_377>::circuit_setup(&universal_srs, program).map_err(setup_error)?;
+
+        tracing::info!("Saving proving key ({:?})", proving_key_file.full_path(path));
+        let mut proving_key_bytes = vec![];
+        proving_key
+            .write_le(&mut proving_key_bytes)
+            .map_err(CliError::cli_io_error)?;
+        let _ = proving_key_file.write_to(path, envelope, &proving_key_bytes)?;
+        tracing::info!("Complete");
+
+        tracing::info!("Saving verification key ({:?})", verification_key_file.full_path(path));
+        let mut verification_key = vec![];
+        verifying_key
+            .write_le(&mut verification_key)
+            .map_err(CliError::cli_io_error)?;
+        let _ = verification_key_file.write_to(path, envelope, &verification_key)?;
+        tracing::info!("Complete");
+
+        Ok(ProgramKeys::Marlin(proving_key, verifying_key))
+    }
+}
+
+///
+/// Returns the bytes of the saved keys of the program, unless they are outdated or their envelopes
+/// name another circuit, in which case the keys are set up again.
+///
+/// Imported keys are kept when `keep_imported` is set, so setting them up again is an error.
+///
+fn saved_keys(
+    proving_key_file: &ProvingKeyFile,
+    verification_key_file: &VerificationKeyFile,
+    path: &Path,
+    envelope: &Envelope,
+    keys_outdated: bool,
+    keep_imported: bool,
+) -> Result<Option<(Vec<u8>, Vec<u8>)>> {
+    if !proving_key_file.exists_at(path) || !verification_key_file.exists_at(path) {
+        return Ok(None);
+    }
+    if keys_outdated {
+        return match keep_imported {
+            true => {
+                Err(CliError::imported_keys_outdated(&envelope.package_name, "keys from a seed were requested").into())
+            }
+            false => Ok(None),
+        };
+    }
+
+    let read_keys = || -> Result<(Vec<u8>, Vec<u8>)> {
+        let (proving_key_envelope, proving_key) = proving_key_file.read_from(path)?;
+        proving_key_envelope.check(envelope, &proving_key_file.full_path(path))?;
+        let (verification_key_envelope, verification_key) = verification_key_file.read_from(path)?;
+        verification_key_envelope.check(envelope, &verification_key_file.full_path(path))?;
+        Ok((proving_key, verification_key))
+    };
+    match read_keys() {
+        Ok(keys) => Ok(Some(keys)),
+        Err(error) if keep_imported => {
+            Err(CliError::imported_keys_outdated(&envelope.package_name, error.message()).into())
+        }
+        Err(error) => {
+            tracing::info!("Setting up the keys again, as the saved keys do not match the circuit");
+            tracing::debug!("{}", error);
+            Ok(None)
+        }
+    }
+}
+
+/// Writes the Groth16 proving and verification keys to the output directory, in the given envelope.
+fn write_groth16_keys(proving_key: &ProvingKey<Bls12_377>, path: &Path, envelope: &Envelope) -> Result<()> {
+    let package_name = &envelope.package_name;
+
+    // TODO (howardwu): Convert parameters to a 'proving key' struct for serialization.
+    // Write the proving key file to the output directory
+    let proving_key_file = ProvingKeyFile::new(package_name);
+    tracing::info!("Saving proving key ({:?})", proving_key_file.full_path(path));
+    let mut proving_key_bytes = vec![];
+    proving_key
+        .write_le(&mut proving_key_bytes)
+        .map_err(CliError::cli_io_error)?;
+    let _ = proving_key_file.write_to(path, envelope, &proving_key_bytes)?;
+    tracing::info!("Complete");
+
+    // Write the verification key file to the output directory
+    let verification_key_file = VerificationKeyFile::new(package_name);
+    tracing::info!("Saving verification key ({:?})", verification_key_file.full_path(path));
+    let mut verification_key = vec![];
+    proving_key
+        .vk
+        .write_le(&mut verification_key)
+        .map_err(CliError::cli_io_error)?;
+    let _ = verification_key_file.write_to(path, envelope, &verification_key)?;
+    tracing::info!("Complete");
+
+    Ok(())
+}
+
+///
+/// Checks that Groth16 parameters have the shape snarkVM generates for the given circuit.
+///
+/// The constant one is a public variable of the parameters, but not of the circuit. The
+/// evaluation domain also covers a constraint for each public input.
+///
+fn check_groth16_shape(parameters: &ProvingKey<Bls12_377>, circuit: &SummarizedCircuit) -> Result<()> {
+    let num_public_variables = circuit.num_public_variables + 1;
+    let num_variables = num_public_variables + circuit.num_private_variables;
+    let domain_size = EvaluationDomain::<Fr>::compute_size_of_domain(circuit.num_constraints + num_public_variables)
+        .ok_or_else(CliError::unable_to_setup)?;
+
+    let queries = [
+        ("gamma_abc_g1", parameters.vk.gamma_abc_g1.len(), num_public_variables),
+        ("a_query", parameters.a_query.len(), num_variables),
+        ("b_g1_query", parameters.b_g1_query.len(), num_variables),
+        ("b_g2_query", parameters.b_g2_query.len(), num_variables),
+        ("h_query", parameters.h_query.len(), domain_size - 1),
+        ("l_query", parameters.l_query.len(), circuit.num_private_variables),
+    ];
+    for (query, found, expected) in queries {
+        if found != expected {
+            return Err(CliError::parameters_circuit_shape_mismatch(query, expected, found).into());
+        }
+    }
+    Ok(())
+}
+
+///
+/// Returns the universal SRS at the given path, or else the one saved in the outputs directory
+/// if it supports the circuit of the program and was generated from the given seed, and
+/// otherwise generates a new one. A universal SRS that is not the saved one is saved in its place.
+///
+/// The SRS is generated for twice the degree the circuit needs, so that it keeps being reused
+/// while the circuit changes.
+///
+fn universal_srs(
+    program: &Compiler<'static, Fr, EdwardsGroupType>,
+    path: &Path,
+    package_name: &str,
+    universal_srs_path: Option<PathBuf>,
+    seed: Option<u64>,
+    metadata: &mut Metadata,
+) -> Result<SRS<Bls12_377>> {
+    let max_degree = AHPForR1CS::<Fr>::index(program).map_err(setup_error)?.max_degree();
+
+    let provenance = Provenance::from_seed(seed);
+    let universal_srs_file = UniversalSrsFile::new(package_name);
+    if let Some(universal_srs_path) = universal_srs_path {
+        tracing::info!("Loading universal SRS... ({:?})", universal_srs_path);
+        let universal_srs_bytes = fs::read(&universal_srs_path)
+            .map_err(|error| CliError::failed_to_read_universal_srs(&universal_srs_path, error))?;
+        let universal_srs = SRS::<Bls12_377>::read_le(universal_srs_bytes.as_slice())
+            .map_err(|error| CliError::failed_to_read_universal_srs(&universal_srs_path, error))?;
+        if universal_srs.max_degree() < max_degree {
+            return Err(
+                CliError::universal_srs_too_small(&universal_srs_path, universal_srs.max_degree(), max_degree).into(),
+            );
+        }
+        tracing::info!("Complete");
+
+        tracing::info!("Saving universal SRS ({:?})", universal_srs_file.full_path(path));
+        let _ = universal_srs_file.write_to(path, &universal_srs_bytes)?;
+        metadata.universal_srs = Some(provenance);
+        tracing::info!("Complete");
+
+        return Ok(universal_srs);
+    }
+
+    if universal_srs_file.exists_at(path) && metadata.universal_srs.unwrap_or_default() == provenance {
+        tracing::info!("Loading universal SRS...");
+        let universal_srs_bytes = universal_srs_file.read_from(path)?;
+        let universal_srs =
+            SRS::<Bls12_377>::read_le(universal_srs_bytes.as_slice()).map_err(CliError::cli_io_error)?;
+        if universal_srs.max_degree() >= max_degree {
+            tracing::info!("Complete");
+            return Ok(universal_srs);
+        }
+        tracing::info!(
+            "The universal SRS supports degree {}, but the circuit needs degree {}",
+            universal_srs.max_degree(),
+            max_degree
+        );
+    }
+
+    tracing::info!("Generating universal SRS...");
+    let rng = &mut proving_rng(seed);
+    let universal_srs =
+        MultiPC::<Bls12_377>::setup(2 * max_degree, rng).map_err(|_| LeoError::from(CliError::unable_to_setup()))?;
+
+    tracing::info!("Saving universal SRS ({:?})", universal_srs_file.full_path(path));
+    let mut universal_srs_bytes = vec![];
+    universal_srs
+        .write_le(&mut universal_srs_bytes)
+        .map_err(CliError::cli_io_error)?;
+    let _ = universal_srs_file.write_to(path, &universal_srs_bytes)?;
+    metadata.universal_srs = Some(provenance);
+    tracing::info!("Complete");
+
+    Ok(universal_srs)
+}
+
+///
+/// An error from snarkVM that may have been caused by the synthesis of a Leo program.
+///
+pub(crate) trait SynthesisFailure {
+    /// Returns the Leo error that made the synthesis of the program fail, if any.
+    fn into_leo_error(self) -> Option<LeoError>;
+}
+
+impl SynthesisFailure for SynthesisError {
+    fn into_leo_error(self) -> Option<LeoError> {
+        synthesis_leo_error(self)
+    }
+}
+
+impl SynthesisFailure for SNARKError {
+    fn into_leo_error(self) -> Option<LeoError> {
+        match self {
+            SNARKError::SynthesisError(error) => error.into_leo_error(),
+            _ => None,
+        }
+    }
+}
+
+impl SynthesisFailure for AHPError {
+    fn into_leo_error(self) -> Option<LeoError> {
+        match self {
+            AHPError::ConstraintSystemError(error) => error.into_leo_error(),
+            _ => None,
+        }
+    }
+}
+
+impl SynthesisFailure for MarlinError {
+    fn into_leo_error(self) -> Option<LeoError> {
+        match self {
+            MarlinError::R1CSError(error) => error.into_leo_error(),
+            MarlinError::AHPError(error) => error.into_leo_error(),
+            _ => None,
+        }
+    }
+}
+
+/// Returns the error that made the setup of the program fail.
+fn setup_error(error: impl SynthesisFailure) -> LeoError {
+    error
+        .into_leo_error()
+        .unwrap_or_else(|| CliError::unable_to_setup().into())
 }
@@ -14,27 +14,26 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use super::build::BuildOptions;
+use super::build::{resolved_imports_map, BuildOptions};
 use crate::{commands::Command, context::Context};
 use leo_compiler::{
-    compiler::{thread_leaked_context, Compiler},
+    compiler::{Compiler, CompilerSession},
     group::targets::edwards_bls12::EdwardsGroupType,
 };
 use leo_errors::{CliError, Result};
 use leo_package::{
     inputs::*,
     outputs::{OutputsDirectory, OUTPUTS_DIRECTORY_NAME},
-    source::{MainFile, MAIN_FILENAME, SOURCE_DIRECTORY_NAME},
+    source::{LibFile, MainFile, LIB_FILENAME, MAIN_FILENAME, SOURCE_DIRECTORY_NAME},
 };
 
-use indexmap::IndexMap;
 use snarkvm_curves::edwards_bls12::Fq;
 use std::{convert::TryFrom, path::PathBuf, time::Instant};
 use structopt::StructOpt;
 use tracing::span::Span;
 
 /// Build program and run tests command
-#[derive(StructOpt, Debug)]
+#[derive(StructOpt, Clone, Debug)]
 #[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
 pub struct Test {
     #[structopt(short = "f", long = "file", name = "file")]
@@ -79,6 +78,13 @@ impl Command for Test {
             file_path.push(MAIN_FILENAME);
             to_test.push(file_path);
 
+        // library packages are tested through their lib file
+        } else if LibFile::exists_at(&package_path) {
+            let mut file_path = package_path.clone();
+            file_path.push(SOURCE_DIRECTORY_NAME);
+            file_path.push(LIB_FILENAME);
+            to_test.push(file_path);
+
         // when no main file and no files marked - error
         } else {
             return Err(CliError::program_file_does_not_exist(package_path.to_string_lossy()).into());
@@ -91,6 +97,8 @@ impl Command for Test {
         // Create the output directory
         OutputsDirectory::create(&package_path)?;
 
+        let imports_map = resolved_imports_map(&context, &package_path)?;
+
         // Finally test every passed file
         for file_path in to_test {
             tracing::info!("Running tests in file {:?}", file_path);
@@ -104,13 +112,14 @@ impl Command for Test {
             };
 
             let timer = Instant::now();
+            let session = CompilerSession::new();
             let program = Compiler::<Fq, EdwardsGroupType>::parse_program_without_input(
                 package_name.clone(),
                 file_path,
                 output_directory.clone(),
-                thread_leaked_context(),
+                session.context(),
                 Some(self.compiler_options.clone().into()),
-                IndexMap::new(),
+                imports_map.clone(),
                 Some(self.compiler_options.clone().into()),
             )?;
 
@@ -16,6 +16,7 @@
 
 use super::build::{Build, BuildOptions};
 use crate::{commands::Command, context::Context};
+use leo_compiler::compiler::CompilerSession;
 use leo_errors::{CliError, Result};
 
 use std::{sync::mpsc::channel, time::Duration};
@@ -64,11 +65,15 @@ impl Command for Watch {
             match rx.recv() {
                 // See changes on the write event
                 Ok(DebouncedEvent::Write(_write)) => {
-                    match (Build {
+                    let build = Build {
                         compiler_options: self.compiler_options.clone(),
-                    })
-                    .execute(context.clone())
-                    {
+                    };
+                    let span = build.log_span();
+                    let _span = span.enter();
+
+                    // Every rebuild gets its own session, so its ASG is freed once the build is done.
+                    let session = CompilerSession::new();
+                    match build.compile(&context, session.context()) {
                         Ok(_output) => tracing::info!("Built successfully"),
                         Err(e) => tracing::error!("Error {:?}", e),
                     };
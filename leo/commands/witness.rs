@@ -0,0 +1,97 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use super::build::{read_circuit, Build, BuildOptions};
+use crate::{commands::Command, context::Context};
+use leo_errors::{CliError, Result};
+use leo_package::{
+    inputs::{InputFile, StateFile},
+    outputs::{CircuitFile, CircuitFormat, WitnessFile},
+    source::{LibFile, MainFile},
+};
+use leo_synthesizer::{CircuitSynthesizer, SerializedWitness, SummarizedCircuit};
+
+use snarkvm_curves::bls12_377::Bls12_377;
+use structopt::StructOpt;
+use tracing::span::Span;
+
+/// Generate the witness of the built circuit for an input pair
+#[derive(StructOpt, Debug)]
+#[structopt(setting = structopt::clap::AppSettings::ColoredHelp)]
+pub struct Witness {
+    #[structopt(long, help = "The name of the input pair in `inputs/`, by default the package name")]
+    pub(crate) input: Option<String>,
+
+    #[structopt(flatten)]
+    pub(crate) compiler_options: BuildOptions,
+}
+
+impl Command for Witness {
+    type Input = <Build as Command>::Output;
+    type Output = ();
+
+    fn log_span(&self) -> Span {
+        tracing::span!(tracing::Level::INFO, "Witness")
+    }
+
+    fn prelude(&self, context: Context) -> Result<Self::Input> {
+        // Library packages have no circuit to assign
+        let path = context.dir()?;
+        if !MainFile::exists_at(&path) && LibFile::exists_at(&path) {
+            return Err(CliError::library_package_has_no_circuit(context.manifest()?.get_package_name()).into());
+        }
+
+        (Build {
+            compiler_options: self.compiler_options.clone(),
+        })
+        .execute(context)
+    }
+
+    fn apply(self, context: Context, input: Self::Input) -> Result<Self::Output> {
+        let path = context.dir()?;
+        let package_name = context.manifest()?.get_package_name();
+        let input_name = self.input.unwrap_or_else(|| package_name.clone());
+        let (mut program, ..) = input;
+
+        // Load the input pair at `input_name.in` and `input_name.state`
+        let (input_string, input_path) = InputFile::new(&input_name).read_from(&path)?;
+        let (state_string, state_path) = StateFile::new(&input_name).read_from(&path)?;
+        program.parse_input(&input_string, &input_path, &state_string, &state_path)?;
+
+        tracing::info!("Generating witness for inputs `{}`...", input_name);
+
+        let mut cs = CircuitSynthesizer::<Bls12_377>::default();
+        program.compile_constraints(&mut cs)?;
+
+        // The witness is only proven with the built circuit and its keys if the inputs keep its shape
+        let format = if CircuitFile::with_format(&package_name, CircuitFormat::Binary).exists_at(&path) {
+            CircuitFormat::Binary
+        } else {
+            CircuitFormat::Json
+        };
+        let (built, _) = read_circuit(&CircuitFile::with_format(&package_name, format), &path)?;
+        if SummarizedCircuit::from(&cs) != SummarizedCircuit::from(&built) {
+            return Err(CliError::witness_circuit_mismatch(input_name).into());
+        }
+
+        let witness = SerializedWitness::from(&cs);
+        WitnessFile::new(&input_name).write_to(&path, witness.to_json_string()?)?;
+
+        tracing::info!("Witness saved ({:?})", path);
+
+        Ok(())
+    }
+}
@@ -14,11 +14,15 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{api::Api, config};
+use crate::{
+    api::Api,
+    config,
+    registry::{registry_from_url, Registry},
+};
 use leo_errors::{CliError, Result};
-use leo_package::root::{LockFile, Manifest};
+use leo_package::root::{Curve, LockFile, Manifest, ProvingSystem, Target, Workspace};
 
-use std::{convert::TryFrom, env::current_dir, path::PathBuf};
+use std::{convert::TryFrom, env::current_dir, path::PathBuf, sync::Arc};
 
 pub const PACKAGE_MANAGER_URL: &str = "https://api.aleo.pm/";
 
@@ -29,6 +33,9 @@ pub struct Context {
     /// Api client for Aleo PM
     pub api: Api,
 
+    /// Registry that packages are fetched from and published to
+    pub registry: Arc<dyn Registry>,
+
     /// Path at which the command is called, None when default
     pub path: Option<PathBuf>,
 }
@@ -46,14 +53,56 @@ impl Context {
         Ok(Manifest::try_from(self.dir()?.as_path())?)
     }
 
+    /// Get the `[target]` of the package manifest, if its curve and proving system are supported.
+    pub fn target(&self) -> Result<Target> {
+        let target = self.manifest()?.get_target();
+        match (target.curve, target.proving_system) {
+            (Curve::Bls12_377, ProvingSystem::Groth16 | ProvingSystem::Marlin) => Ok(target),
+            (curve, proving_system) => Err(CliError::unsupported_target(curve, proving_system).into()),
+        }
+    }
+
+    /// Returns a context for the package at the given path.
+    pub fn with_path(&self, path: PathBuf) -> Context {
+        Context {
+            path: Some(path),
+            ..self.clone()
+        }
+    }
+
+    /// Get the root of the workspace the current package belongs to.
+    pub fn workspace_root(&self) -> Result<Option<PathBuf>> {
+        Workspace::find_root(&self.dir()?)
+    }
+
+    /// Get contexts for every member of the workspace the current package belongs to.
+    pub fn workspace_members(&self) -> Result<Vec<Context>> {
+        let root = self.workspace_root()?.ok_or_else(CliError::not_in_workspace)?;
+        let workspace = Workspace::read_from(&root)?.unwrap_or_default();
+
+        Ok(workspace
+            .member_paths(&root)?
+            .into_iter()
+            .map(|path| self.with_path(path))
+            .collect())
+    }
+
+    /// Get the directory of the lock file, which is shared by all members of a workspace.
+    pub fn lock_file_dir(&self) -> Result<PathBuf> {
+        match self.workspace_root()? {
+            Some(root) => Ok(root),
+            None => self.dir(),
+        }
+    }
+
     /// Get lock file for current context.
     pub fn lock_file(&self) -> Result<LockFile> {
-        Ok(LockFile::try_from(self.dir()?.as_path())?)
+        Ok(LockFile::try_from(self.lock_file_dir()?.as_path())?)
     }
 
     /// Check if lock file exists.
     pub fn lock_file_exists(&self) -> Result<bool> {
-        Ok(LockFile::exists_at(&self.dir()?))
+        Ok(LockFile::exists_at(&self.lock_file_dir()?))
     }
 }
 
@@ -61,16 +110,28 @@ impl Context {
 pub fn create_context(path: PathBuf, api_url: Option<String>) -> Result<Context> {
     let token = config::read_token().ok();
 
-    let api = Api::new(api_url.unwrap_or_else(|| PACKAGE_MANAGER_URL.to_string()), token);
+    let url = api_url.unwrap_or_else(|| PACKAGE_MANAGER_URL.to_string());
+    let api = Api::new(url.clone(), token);
+    let registry = registry_from_url(&url, &api)?;
 
-    Ok(Context { api, path: Some(path) })
+    Ok(Context {
+        api,
+        registry,
+        path: Some(path),
+    })
 }
 
 /// Returns project context.
 pub fn get_context(api_url: Option<String>) -> Result<Context> {
     let token = config::read_token().ok();
 
-    let api = Api::new(api_url.unwrap_or_else(|| PACKAGE_MANAGER_URL.to_string()), token);
+    let url = api_url.unwrap_or_else(|| PACKAGE_MANAGER_URL.to_string());
+    let api = Api::new(url.clone(), token);
+    let registry = registry_from_url(&url, &api)?;
 
-    Ok(Context { api, path: None })
+    Ok(Context {
+        api,
+        registry,
+        path: None,
+    })
 }
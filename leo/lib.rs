@@ -21,6 +21,8 @@ pub mod commands;
 pub mod config;
 pub mod context;
 pub mod logger;
+pub mod registry;
+pub mod rng;
 pub mod updater;
 
 #[cfg(test)]
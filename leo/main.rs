@@ -19,11 +19,13 @@ pub mod commands;
 pub mod config;
 pub mod context;
 pub mod logger;
+pub mod registry;
+pub mod rng;
 pub mod updater;
 
 use commands::{
-    package::{Clone, Fetch, Login, Logout, Publish},
-    Build, Clean, Command, Deploy, Init, Lint, New, Prove, Run, Setup, Test, Update, Watch,
+    package::{Clone, Deps, Fetch, Login, Logout, Publish, Tree, UpdateDeps, Yank},
+    Build, Clean, Command, Deploy, Init, Lint, New, Prove, Run, Setup, Test, Update, Watch, Witness,
 };
 use leo_errors::Result;
 
@@ -46,6 +48,14 @@ struct Opt {
     #[structopt(help = "Custom Aleo PM backend URL", env = "APM_URL")]
     api: Option<String>,
 
+    #[structopt(
+        long,
+        global = true,
+        help = "Package registry to use, an Aleo PM URL or a file:// directory",
+        env = "LEO_REGISTRY"
+    )]
+    registry: Option<String>,
+
     #[structopt(
         long,
         global = true,
@@ -73,6 +83,9 @@ enum CommandOpts {
 
     #[structopt(about = "Compile the current package as a program")]
     Build {
+        #[structopt(long, help = "Build every member of the workspace")]
+        workspace: bool,
+
         #[structopt(flatten)]
         command: Build,
     },
@@ -83,6 +96,12 @@ enum CommandOpts {
         command: Setup,
     },
 
+    #[structopt(about = "Generate the witness of the built circuit for an input pair")]
+    Witness {
+        #[structopt(flatten)]
+        command: Witness,
+    },
+
     #[structopt(about = "Run the program and produce a proof")]
     Prove {
         #[structopt(flatten)]
@@ -115,6 +134,9 @@ enum CommandOpts {
 
     #[structopt(about = "Compile and run all tests in the current package")]
     Test {
+        #[structopt(long, help = "Run the tests of every member of the workspace")]
+        workspace: bool,
+
         #[structopt(flatten)]
         command: Test,
     },
@@ -130,6 +152,24 @@ enum CommandOpts {
         command: Fetch,
     },
 
+    #[structopt(about = "Update dependencies to the newest versions allowed by the manifest")]
+    UpdateDeps {
+        #[structopt(flatten)]
+        command: UpdateDeps,
+    },
+
+    #[structopt(about = "Print the resolved dependency graph")]
+    Tree {
+        #[structopt(flatten)]
+        command: Tree,
+    },
+
+    #[structopt(about = "Inspect the resolved dependencies")]
+    Deps {
+        #[structopt(flatten)]
+        command: Deps,
+    },
+
     #[structopt(about = "Clone a package from the Aleo Package Manager")]
     Clone {
         #[structopt(flatten)]
@@ -154,6 +194,12 @@ enum CommandOpts {
         command: Publish,
     },
 
+    #[structopt(about = "Yank a published version of the current package")]
+    Yank {
+        #[structopt(flatten)]
+        command: Yank,
+    },
+
     // #[structopt(about = "Uninstall a package from the current package")]
     // Remove {
     //     #[structopt(flatten)]
@@ -191,18 +237,26 @@ fn run_with_args(opt: Opt) -> Result<()> {
 
     // Get custom root folder and create context for it.
     // If not specified, default context will be created in cwd.
+    let registry = opt.registry.or(opt.api);
     let context = handle_error(match opt.path {
-        Some(path) => context::create_context(path, opt.api),
-        None => context::get_context(opt.api),
+        Some(path) => context::create_context(path, registry),
+        None => context::get_context(registry),
     });
 
     match opt.command {
         CommandOpts::Init { command } => command.try_execute(context),
         CommandOpts::New { command } => command.try_execute(context),
-        CommandOpts::Build { command } => command.try_execute(context),
+        CommandOpts::Build { command, workspace } => match workspace {
+            true => command.try_execute_workspace(context),
+            false => command.try_execute(context),
+        },
         CommandOpts::Setup { command } => command.try_execute(context),
+        CommandOpts::Witness { command } => command.try_execute(context),
         CommandOpts::Prove { command } => command.try_execute(context),
-        CommandOpts::Test { command } => command.try_execute(context),
+        CommandOpts::Test { command, workspace } => match workspace {
+            true => command.try_execute_workspace(context),
+            false => command.try_execute(context),
+        },
         CommandOpts::Run { command } => command.try_execute(context),
         CommandOpts::Clean { command } => command.try_execute(context),
         CommandOpts::Watch { command } => command.try_execute(context),
@@ -210,10 +264,14 @@ fn run_with_args(opt: Opt) -> Result<()> {
 
         // CommandOpts::Add { command } => command.try_execute(context),
         CommandOpts::Fetch { command } => command.try_execute(context),
+        CommandOpts::UpdateDeps { command } => command.try_execute(context),
+        CommandOpts::Tree { command } => command.try_execute(context),
+        CommandOpts::Deps { command } => command.try_execute(context),
         CommandOpts::Clone { command } => command.try_execute(context),
         CommandOpts::Login { command } => command.try_execute(context),
         CommandOpts::Logout { command } => command.try_execute(context),
         CommandOpts::Publish { command } => command.try_execute(context),
+        CommandOpts::Yank { command } => command.try_execute(context),
         // CommandOpts::Remove { command } => command.try_execute(context),
         CommandOpts::Lint { command } => command.try_execute(context),
         CommandOpts::Deploy { command } => command.try_execute(context),
@@ -232,7 +290,14 @@ fn handle_error<T>(res: Result<T>) -> T {
 
 #[cfg(test)]
 mod cli_tests {
-    use crate::{run_with_args, Opt};
+    use crate::{
+        commands::{
+            package::{Deps, Tree},
+            Command,
+        },
+        context::create_context,
+        run_with_args, Opt,
+    };
     use leo_errors::{CliError, Result};
 
     use snarkvm_utilities::Write;
@@ -257,6 +322,12 @@ mod cli_tests {
         run_with_args(opts)
     }
 
+    // Runs the command from cmd-like arguments "tree --json" in the package at the path and returns its output.
+    fn cmd_output<C: Command<Output = String> + StructOpt>(args: &str, path: &Option<PathBuf>) -> Result<String> {
+        let command = C::from_iter_safe(args.split(' ')).map_err(CliError::opt_args_error)?;
+        command.execute(create_context(path.clone().unwrap(), None)?)
+    }
+
     // Create a test directory with name.
     fn testdir(name: &str) -> TestDir {
         TestDir::temp().create(name, FileType::Dir)
@@ -395,6 +466,767 @@ mod cli_tests {
         assert!(run_cmd("leo test -f examples/silly-sudoku/src/main.leo", path).is_ok());
     }
 
+    #[test]
+    fn test_local_dependencies() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new app", &Some(path.clone())).is_ok());
+        assert!(run_cmd("leo new shared", &Some(path.clone())).is_ok());
+        assert!(run_cmd("leo new upstream", &Some(path.clone())).is_ok());
+
+        std::fs::write(
+            path.join("shared/src/main.leo"),
+            "function double(x: u32) -> u32 {\n    return x * 2u32;\n}\n",
+        )
+        .unwrap();
+        std::fs::write(
+            path.join("upstream/src/main.leo"),
+            "function triple(x: u32) -> u32 {\n    return x * 3u32;\n}\n",
+        )
+        .unwrap();
+
+        // Publish the upstream package as a local git repository.
+        let upstream = path.join("upstream");
+        for args in [
+            &["init", "--quiet"][..],
+            &["add", "."],
+            &[
+                "-c",
+                "user.name=leo",
+                "-c",
+                "user.email=leo@localhost",
+                "commit",
+                "--quiet",
+                "-m",
+                "init",
+            ],
+        ] {
+            let status = std::process::Command::new("git")
+                .current_dir(&upstream)
+                .args(args)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        }
+
+        let app_path = &Some(path.join("app"));
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(path.join("app/Leo.toml"))
+            .unwrap();
+        assert!(file
+            .write_all(
+                format!(
+                    "shared = {{ path = \"../shared\" }}\nupstream = {{ git = \"file://{}\" }}\n",
+                    upstream.display()
+                )
+                .as_bytes()
+            )
+            .is_ok());
+        std::fs::write(
+            path.join("app/src/main.leo"),
+            "import shared.double;\nimport upstream.triple;\n\nfunction main(a: u32, b: u32) -> u32 {\n    return double(triple(a + b));\n}\n",
+        )
+        .unwrap();
+
+        assert!(run_cmd("leo build", app_path).is_err());
+        assert!(run_cmd("leo fetch", app_path).is_ok());
+        assert!(run_cmd("leo build", app_path).is_ok());
+
+        let lock_file = std::fs::read_to_string(path.join("app/Leo.lock")).unwrap();
+        assert!(lock_file.contains("checksum = "));
+        assert!(lock_file.contains("git = \"file://"));
+
+        // Fetched dependencies are verified against the lock file before every build.
+        let checkout = std::fs::read_dir(path.join("app/imports"))
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .find(|entry| entry.file_name().unwrap().to_string_lossy().starts_with("upstream@"))
+            .unwrap();
+        std::fs::write(
+            checkout.join("src/main.leo"),
+            "function triple(x: u32) -> u32 {\n    return x * 4u32;\n}\n",
+        )
+        .unwrap();
+
+        assert!(run_cmd("leo build", app_path).is_err());
+        assert!(run_cmd("leo fetch", app_path).is_ok());
+        assert!(run_cmd("leo build", app_path).is_ok());
+
+        // A git dependency without a revision stays at its locked commit until it is updated.
+        std::fs::write(
+            upstream.join("src/main.leo"),
+            "function triple(x: u32) -> u32 {\n    return x * 5u32;\n}\n",
+        )
+        .unwrap();
+        let status = std::process::Command::new("git")
+            .current_dir(&upstream)
+            .args([
+                "-c",
+                "user.name=leo",
+                "-c",
+                "user.email=leo@localhost",
+                "commit",
+                "--quiet",
+                "-am",
+                "five",
+            ])
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        assert!(run_cmd("leo fetch", app_path).is_ok());
+        assert_eq!(std::fs::read_to_string(path.join("app/Leo.lock")).unwrap(), lock_file);
+        assert!(run_cmd("leo update-deps", app_path).is_ok());
+        assert_ne!(std::fs::read_to_string(path.join("app/Leo.lock")).unwrap(), lock_file);
+
+        // The URL and revision of a git dependency are never read as git options.
+        let pwned = path.join("pwned");
+        let manifest = std::fs::read_to_string(path.join("app/Leo.toml")).unwrap();
+        for dependency in [
+            format!("{{ git = \"--upload-pack=touch {}\" }}", pwned.display()),
+            format!(
+                "{{ git = \"file://{}\", rev = \"--output={}\" }}",
+                upstream.display(),
+                pwned.display()
+            ),
+        ] {
+            std::fs::write(
+                path.join("app/Leo.toml"),
+                format!("{}evil = {}\n", manifest, dependency),
+            )
+            .unwrap();
+            assert!(run_cmd("leo update-deps", app_path).is_err());
+            assert!(!pwned.exists());
+        }
+    }
+
+    #[test]
+    fn test_file_registry() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+        let registry = format!("--registry file://{}", path.join("registry").display());
+
+        assert!(run_cmd("leo new app", &Some(path.clone())).is_ok());
+        assert!(run_cmd("leo new shared", &Some(path.clone())).is_ok());
+
+        let manifest = std::fs::read_to_string(path.join("shared/Leo.toml")).unwrap();
+        std::fs::write(path.join("shared/Leo.toml"), manifest.replace("[AUTHOR]", "leo")).unwrap();
+        std::fs::write(
+            path.join("shared/src/main.leo"),
+            "function double(x: u32) -> u32 {\n    return x * 2u32;\n}\n\nfunction main(a: u32) -> u32 {\n    return double(a);\n}\n",
+        )
+        .unwrap();
+
+        let shared_path = &Some(path.join("shared"));
+        assert!(run_cmd(&format!("leo publish {}", registry), shared_path).is_ok());
+        assert!(run_cmd(&format!("leo publish {}", registry), shared_path).is_err()); // 2nd time
+
+        let app_path = &Some(path.join("app"));
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(path.join("app/Leo.toml"))
+            .unwrap();
+        assert!(file
+            .write_all(b"shared = { author = \"leo\", package = \"shared\", version = \"^0.1\" }\n")
+            .is_ok());
+        std::fs::write(
+            path.join("app/src/main.leo"),
+            "import shared.double;\n\nfunction main(a: u32, b: u32) -> u32 {\n    return double(a + b);\n}\n",
+        )
+        .unwrap();
+
+        assert!(run_cmd(&format!("leo fetch {}", registry), app_path).is_ok());
+        assert!(run_cmd("leo build", app_path).is_ok());
+
+        // Yanked versions are no longer selected for new lock files.
+        assert!(run_cmd(&format!("leo yank 0.1.0 {}", registry), shared_path).is_ok());
+        assert!(run_cmd(&format!("leo update-deps {}", registry), app_path).is_err());
+        assert!(run_cmd(&format!("leo yank 0.1.0 --undo {}", registry), shared_path).is_ok());
+        assert!(run_cmd(&format!("leo update-deps {}", registry), app_path).is_ok());
+
+        // Packages outside of the registry directory cannot be reached.
+        std::fs::create_dir_all(path.join("outside")).unwrap();
+        std::fs::copy(
+            path.join("registry/leo/shared/0.1.0.zip"),
+            path.join("outside/0.1.0.zip"),
+        )
+        .unwrap();
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(path.join("app/Leo.toml"))
+            .unwrap();
+        assert!(file
+            .write_all(b"outside = { author = \"..\", package = \"outside\", version = \"0.1.0\" }\n")
+            .is_ok());
+        let error = run_cmd(&format!("leo update-deps {}", registry), app_path).unwrap_err();
+        assert!(error.to_string().contains("invalid author `..`"), "{}", error);
+        assert!(run_cmd(&format!("leo yank ../0.1.0 {}", registry), shared_path).is_err());
+    }
+
+    #[test]
+    fn test_publish_dry_run() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+        let registry = format!("--registry file://{}", path.join("registry").display());
+
+        assert!(run_cmd("leo new dry", &Some(path.clone())).is_ok());
+        let dry_path = &Some(path.join("dry"));
+
+        // The author placeholder must be replaced before publishing.
+        assert!(run_cmd(&format!("leo publish --dry-run {}", registry), dry_path).is_err());
+
+        let manifest = std::fs::read_to_string(path.join("dry/Leo.toml")).unwrap();
+        let manifest = manifest.replace("[AUTHOR]", "leo");
+        std::fs::write(path.join("dry/Leo.toml"), manifest.replace("0.1.0", "0.1")).unwrap();
+        assert!(run_cmd(&format!("leo publish --dry-run {}", registry), dry_path).is_err());
+
+        std::fs::write(path.join("dry/Leo.toml"), manifest).unwrap();
+        assert!(run_cmd(&format!("leo publish --dry-run {}", registry), dry_path).is_ok());
+        assert!(path.join("dry/outputs/dry.zip").exists());
+        assert!(!path.join("registry").exists());
+    }
+
+    #[test]
+    fn test_workspace() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+        let packages = path.join("packages");
+        std::fs::create_dir_all(&packages).unwrap();
+        std::fs::write(path.join("Leo.toml"), "[workspace]\nmembers = [\"packages/*\"]\n").unwrap();
+
+        assert!(run_cmd("leo new app", &Some(packages.clone())).is_ok());
+        assert!(run_cmd("leo new math", &Some(packages.clone())).is_ok());
+        std::fs::write(
+            packages.join("math/src/main.leo"),
+            "function double(x: u32) -> u32 {\n    return x * 2u32;\n}\n\nfunction main(a: u32) -> u32 {\n    return double(a);\n}\n",
+        )
+        .unwrap();
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(packages.join("app/Leo.toml"))
+            .unwrap();
+        assert!(file.write_all(b"math = { path = \"../math\" }\n").is_ok());
+        std::fs::write(
+            packages.join("app/src/main.leo"),
+            "import math.double;\n\nfunction main(a: u32, b: u32) -> u32 {\n    return double(a + b);\n}\n",
+        )
+        .unwrap();
+
+        // Members share the lock file at the workspace root.
+        let app_path = &Some(packages.join("app"));
+        assert!(run_cmd("leo fetch", app_path).is_ok());
+        assert!(path.join("Leo.lock").exists());
+        assert!(!packages.join("app/Leo.lock").exists());
+
+        let lock_file = std::fs::read_to_string(path.join("Leo.lock")).unwrap();
+        assert!(lock_file.contains("path = \"./packages/math\""));
+
+        assert!(run_cmd("leo build", app_path).is_ok());
+        assert!(run_cmd("leo build --workspace", &Some(path.clone())).is_ok());
+        assert!(run_cmd("leo test --workspace", app_path).is_ok());
+        assert!(packages.join("math/outputs/math.sum").exists());
+
+        // Packages outside of a workspace cannot use workspace commands.
+        let single = testdir("single");
+        assert!(run_cmd("leo new single", &Some(single.path("single"))).is_ok());
+        let single_path = &Some(single.path("single").join("single"));
+        assert!(run_cmd("leo build", single_path).is_ok());
+        assert!(run_cmd("leo build --workspace", single_path).is_err());
+    }
+
+    #[test]
+    fn test_dependency_tree() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new app", &Some(path.clone())).is_ok());
+        assert!(run_cmd("leo new math", &Some(path.clone())).is_ok());
+        assert!(run_cmd("leo new util", &Some(path.clone())).is_ok());
+
+        let dependencies = [
+            ("app", "math = { path = \"../math\" }\nutil = { path = \"../util\" }\n"),
+            ("math", "util = { path = \"../util\" }\n"),
+        ];
+        for (package, dependencies) in dependencies {
+            let mut file = std::fs::OpenOptions::new()
+                .append(true)
+                .open(path.join(package).join("Leo.toml"))
+                .unwrap();
+            assert!(file.write_all(dependencies.as_bytes()).is_ok());
+        }
+
+        let app_path = &Some(path.join("app"));
+        assert!(run_cmd("leo tree", app_path).is_err()); // not fetched yet
+        assert!(run_cmd("leo fetch", app_path).is_ok());
+
+        let math = path.join("math").display().to_string();
+        let util = path.join("util").display().to_string();
+        assert_eq!(
+            cmd_output::<Tree>("tree", app_path).unwrap(),
+            format!(
+                "app v0.1.0\n├── math v0.1.0 ({})\n│   └── util v0.1.0 ({})\n└── util v0.1.0 ({})",
+                math, util, util
+            )
+        );
+
+        let tree = cmd_output::<Tree>("tree --json", app_path).unwrap();
+        let tree = serde_json::from_str::<serde_json::Value>(&tree).unwrap();
+        assert_eq!(tree["name"], "app v0.1.0");
+        assert_eq!(tree["dependencies"][0]["import_name"], "math");
+        assert_eq!(tree["dependencies"][0]["dependencies"][0]["import_name"], "util");
+        assert_eq!(tree["dependencies"][1]["source"]["path"], util.as_str());
+        assert_eq!(tree["duplicates"], serde_json::json!({}));
+
+        assert_eq!(
+            cmd_output::<Deps>("deps why util", app_path).unwrap(),
+            format!("util v0.1.0 ({}) is included by:\n  math -> util\n  util", util)
+        );
+
+        let why = cmd_output::<Deps>("deps why util --json", app_path).unwrap();
+        let why = serde_json::from_str::<serde_json::Value>(&why).unwrap();
+        assert_eq!(why["package"], "util");
+        assert_eq!(why["chains"][0][0]["import_name"], "math");
+        assert_eq!(why["chains"][0][1]["import_name"], "util");
+        assert_eq!(why["chains"][1][0]["import_name"], "util");
+        assert!(run_cmd("leo deps why missing", app_path).is_err());
+    }
+
+    #[test]
+    fn test_library_package() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new --lib gadgets", &Some(path.clone())).is_ok());
+        assert!(run_cmd("leo new app", &Some(path.clone())).is_ok());
+        assert!(path.join("gadgets/src/lib.leo").exists());
+        assert!(!path.join("gadgets/src/main.leo").exists());
+        assert!(!path.join("gadgets/inputs").exists());
+
+        // Libraries are type checked without synthesizing a circuit.
+        let gadgets_path = &Some(path.join("gadgets"));
+        assert!(run_cmd("leo build", gadgets_path).is_ok());
+        assert!(!path.join("gadgets/outputs/gadgets.json").exists());
+        assert!(run_cmd("leo prove", gadgets_path).is_err());
+
+        let manifest = std::fs::read_to_string(path.join("gadgets/Leo.toml")).unwrap();
+        std::fs::write(path.join("gadgets/Leo.toml"), manifest.replace("[AUTHOR]", "leo")).unwrap();
+        assert!(run_cmd("leo publish --dry-run", gadgets_path).is_ok());
+
+        let mut file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(path.join("app/Leo.toml"))
+            .unwrap();
+        assert!(file.write_all(b"gadgets = { path = \"../gadgets\" }\n").is_ok());
+        std::fs::write(
+            path.join("app/src/main.leo"),
+            "import gadgets.add;\n\nfunction main(a: u32, b: u32) -> u32 {\n    return add(a, b);\n}\n",
+        )
+        .unwrap();
+
+        let app_path = &Some(path.join("app"));
+        assert!(run_cmd("leo fetch", app_path).is_ok());
+        assert!(run_cmd("leo build", app_path).is_ok());
+    }
+
+    #[test]
+    fn test_target() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new target", &Some(path.clone())).is_ok());
+        let target_path = &Some(path.join("target"));
+        let manifest = std::fs::read_to_string(path.join("target/Leo.toml")).unwrap();
+
+        // Unsupported curves fail before the setup runs.
+        std::fs::write(
+            path.join("target/Leo.toml"),
+            manifest.replace("curve = \"bls12_377\"", "curve = \"bw6_761\""),
+        )
+        .unwrap();
+        assert!(run_cmd("leo build", target_path).is_ok());
+        assert!(run_cmd("leo setup", target_path).is_err());
+        assert!(!path.join("target/outputs/target.lpk").exists());
+
+        std::fs::write(path.join("target/Leo.toml"), manifest).unwrap();
+        assert!(run_cmd("leo setup", target_path).is_ok());
+    }
+
+    #[test]
+    fn test_marlin() {
+        use leo_package::{outputs::Envelope, root::ProvingSystem};
+        use std::path::Path;
+
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new marlin", &Some(path.clone())).is_ok());
+        let marlin_path = &Some(path.join("marlin"));
+
+        // Groth16 keys are left behind when the package moves to Marlin.
+        assert!(run_cmd("leo setup", marlin_path).is_ok());
+        let manifest = std::fs::read_to_string(path.join("marlin/Leo.toml")).unwrap();
+        std::fs::write(
+            path.join("marlin/Leo.toml"),
+            manifest.replace("proving_system = \"groth16\"", "proving_system = \"marlin\""),
+        )
+        .unwrap();
+
+        assert!(run_cmd("leo run", marlin_path).is_ok());
+        assert!(path.join("marlin/outputs/marlin.srs").exists());
+        assert!(path.join("marlin/outputs/marlin.mpk").exists());
+        assert!(path.join("marlin/outputs/marlin.mvk").exists());
+        assert!(path.join("marlin/outputs/marlin.proof").exists());
+
+        // A changed circuit is indexed again against the saved universal SRS.
+        let srs = std::fs::read(path.join("marlin/outputs/marlin.srs")).unwrap();
+        let main = std::fs::read_to_string(path.join("marlin/src/main.leo")).unwrap();
+        std::fs::write(path.join("marlin/src/main.leo"), main.replace("a + b", "b + a")).unwrap();
+        assert!(run_cmd("leo run", marlin_path).is_ok());
+        assert_eq!(std::fs::read(path.join("marlin/outputs/marlin.srs")).unwrap(), srs);
+
+        // The serialized circuit is proven with the keys of the proving system the package targets.
+        assert!(run_cmd("leo prove --circuit outputs/marlin.json", marlin_path).is_ok());
+        let proof = std::fs::read(path.join("marlin/outputs/marlin.proof")).unwrap();
+        let (envelope, _) = Envelope::open(&proof, Path::new("marlin.proof")).unwrap();
+        assert_eq!(envelope.proving_system, ProvingSystem::Marlin);
+
+        // Outside a package, the proving system is given.
+        for file in ["marlin.json", "marlin.witness", "marlin.mpk", "marlin.mvk"] {
+            std::fs::copy(path.join("marlin/outputs").join(file), path.join(file)).unwrap();
+        }
+        assert!(run_cmd("leo prove --circuit marlin.json", &Some(path.clone())).is_err());
+        assert!(run_cmd(
+            "leo prove --circuit marlin.json --proving-system marlin",
+            &Some(path.clone())
+        )
+        .is_ok());
+
+        // The universal SRS outlives the keys.
+        assert!(run_cmd("leo clean", marlin_path).is_ok());
+        assert_eq!(std::fs::read(path.join("marlin/outputs/marlin.srs")).unwrap(), srs);
+        assert!(!path.join("marlin/outputs/marlin.mpk").exists());
+        assert!(!path.join("marlin/outputs/marlin.mvk").exists());
+        assert!(run_cmd("leo setup", marlin_path).is_ok());
+        assert_eq!(std::fs::read(path.join("marlin/outputs/marlin.srs")).unwrap(), srs);
+
+        // Another package is indexed against the given universal SRS.
+        assert!(run_cmd("leo new indexed", &Some(path.clone())).is_ok());
+        let indexed_path = &Some(path.join("indexed"));
+        let universal_srs = "leo setup --universal-srs ../marlin/outputs/marlin.srs";
+        assert!(run_cmd(universal_srs, indexed_path).is_err());
+        std::fs::write(
+            path.join("indexed/Leo.toml"),
+            std::fs::read_to_string(path.join("indexed/Leo.toml"))
+                .unwrap()
+                .replace("proving_system = \"groth16\"", "proving_system = \"marlin\""),
+        )
+        .unwrap();
+        assert!(run_cmd(&format!("{} --seed 1", universal_srs), indexed_path).is_err());
+        assert!(run_cmd("leo setup --universal-srs missing.srs", indexed_path).is_err());
+        assert!(run_cmd(universal_srs, indexed_path).is_ok());
+        assert_eq!(std::fs::read(path.join("indexed/outputs/indexed.srs")).unwrap(), srs);
+        assert!(run_cmd("leo run", indexed_path).is_ok());
+    }
+
+    #[test]
+    fn test_seed() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new seeded", &Some(path.clone())).is_ok());
+        let seeded_path = &Some(path.join("seeded"));
+        let read = |file: &str| std::fs::read(path.join("seeded/outputs").join(file)).unwrap();
+
+        // The same seed produces the same keys and proof.
+        assert!(run_cmd("leo prove --seed 7", seeded_path).is_ok());
+        let (proving_key, proof) = (read("seeded.lpk"), read("seeded.proof"));
+        let metadata = String::from_utf8(read("seeded.meta")).unwrap();
+        assert!(metadata.contains("\"deterministic\": true"));
+        assert!(metadata.contains("\"seed\": 7"));
+
+        assert!(run_cmd("leo clean", seeded_path).is_ok());
+        assert!(!path.join("seeded/outputs/seeded.meta").exists());
+        assert!(run_cmd("leo prove --seed 7", seeded_path).is_ok());
+        assert_eq!(read("seeded.lpk"), proving_key);
+        assert_eq!(read("seeded.proof"), proof);
+
+        // Seeded keys are not reused without the seed.
+        assert!(run_cmd("leo prove", seeded_path).is_ok());
+        assert_ne!(read("seeded.lpk"), proving_key);
+        let metadata = String::from_utf8(read("seeded.meta")).unwrap();
+        assert!(metadata.contains("\"deterministic\": false"));
+        assert!(!metadata.contains("\"seed\""));
+    }
+
+    #[test]
+    fn test_setup_import() {
+        use leo_package::outputs::Envelope;
+        use leo_synthesizer::{SerializedCircuit, SummarizedCircuit};
+        use snarkvm_algorithms::snark::groth16::ProvingKey;
+        use snarkvm_curves::bls12_377::Bls12_377;
+        use snarkvm_utilities::CanonicalSerialize;
+
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new imported", &Some(path.clone())).is_ok());
+        let imported_path = &Some(path.join("imported"));
+        let outputs = path.join("imported/outputs");
+        let circuit_hash = || {
+            let circuit = std::fs::read_to_string(outputs.join("imported.json")).unwrap();
+            SummarizedCircuit::from(SerializedCircuit::from_json_string(&circuit).unwrap()).checksum()
+        };
+
+        // Stand in for a ceremony with parameters in the canonical serialization.
+        assert!(run_cmd("leo setup", imported_path).is_ok());
+        let proving_key = std::fs::read(outputs.join("imported.lpk")).unwrap();
+        let (_, parameters) = Envelope::open(&proving_key, &outputs.join("imported.lpk")).unwrap();
+        let parameters = ProvingKey::<Bls12_377>::read(parameters, true).unwrap();
+        let mut ceremony = vec![];
+        parameters.serialize(&mut ceremony).unwrap();
+        std::fs::write(path.join("imported/ceremony.params"), ceremony).unwrap();
+        assert!(run_cmd("leo clean", imported_path).is_ok());
+
+        // A hash of the circuit given with the parameters is checked first.
+        assert!(run_cmd("leo build", imported_path).is_ok());
+        assert!(run_cmd("leo setup --import ceremony.params --circuit-hash 00", imported_path).is_err());
+        let import = format!("leo setup --import ceremony.params --circuit-hash {}", circuit_hash());
+        assert!(run_cmd(&import, imported_path).is_ok());
+        assert_eq!(std::fs::read(outputs.join("imported.lpk")).unwrap(), proving_key);
+
+        assert!(run_cmd("leo clean", imported_path).is_ok());
+        assert!(run_cmd("leo setup --import ceremony.params", imported_path).is_ok());
+        assert_eq!(std::fs::read(outputs.join("imported.lpk")).unwrap(), proving_key);
+        assert!(run_cmd("leo run", imported_path).is_ok());
+        assert_eq!(std::fs::read(outputs.join("imported.lpk")).unwrap(), proving_key);
+        let metadata = std::fs::read_to_string(outputs.join("imported.meta")).unwrap();
+        let metadata: serde_json::Value = serde_json::from_str(&metadata).unwrap();
+        assert_eq!(metadata["keys"]["imported"], true);
+
+        // Imported keys are not replaced by generated keys unless asked to.
+        let error = run_cmd("leo prove --seed 1", imported_path).unwrap_err();
+        assert!(error.to_string().contains("were imported"), "{}", error);
+        let manifest = std::fs::read_to_string(path.join("imported/Leo.toml")).unwrap();
+        std::fs::write(path.join("imported/Leo.toml"), manifest.replace("0.1.0", "0.2.0")).unwrap();
+        let error = run_cmd("leo setup", imported_path).unwrap_err();
+        assert!(error.to_string().contains("were imported"), "{}", error);
+        assert_eq!(std::fs::read(outputs.join("imported.lpk")).unwrap(), proving_key);
+        assert!(run_cmd("leo setup --replace-imported-keys", imported_path).is_ok());
+        assert_ne!(std::fs::read(outputs.join("imported.lpk")).unwrap(), proving_key);
+        std::fs::write(path.join("imported/Leo.toml"), manifest).unwrap();
+        assert!(run_cmd("leo setup --import ceremony.params", imported_path).is_ok());
+
+        // Parameters of another circuit of the same shape do not prove it, even when the hash is forced.
+        let main = std::fs::read_to_string(path.join("imported/src/main.leo")).unwrap();
+        std::fs::write(path.join("imported/src/main.leo"), main.replace("a + b", "a + a")).unwrap();
+        assert!(run_cmd("leo build", imported_path).is_ok());
+        let import = format!("leo setup --import ceremony.params --circuit-hash {}", circuit_hash());
+        let error = run_cmd(&import, imported_path).unwrap_err();
+        assert!(error.to_string().contains("does not verify"), "{}", error);
+        assert_eq!(std::fs::read(outputs.join("imported.lpk")).unwrap(), proving_key);
+
+        // Parameters of a circuit of another shape are rejected before proving.
+        std::fs::write(path.join("imported/src/main.leo"), main.replace("a + b", "a * b")).unwrap();
+        assert!(run_cmd("leo build", imported_path).is_ok());
+        let error = run_cmd("leo setup --import ceremony.params", imported_path).unwrap_err();
+        assert!(error.to_string().contains("the circuit needs"), "{}", error);
+    }
+
+    #[test]
+    fn test_envelope() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new enveloped", &Some(path.clone())).is_ok());
+        let enveloped_path = &Some(path.join("enveloped"));
+        let read = |file: &str| std::fs::read(path.join("enveloped/outputs").join(file)).unwrap();
+
+        assert!(run_cmd("leo run", enveloped_path).is_ok());
+        let proving_key = read("enveloped.lpk");
+        assert!(String::from_utf8_lossy(&proving_key).contains("\"package_version\":\"0.1.0\""));
+
+        // Keys set up for another version of the package are set up again.
+        let manifest = std::fs::read_to_string(path.join("enveloped/Leo.toml")).unwrap();
+        std::fs::write(
+            path.join("enveloped/Leo.toml"),
+            manifest.replace("version = \"0.1.0\"", "version = \"0.2.0\""),
+        )
+        .unwrap();
+        assert!(run_cmd("leo setup", enveloped_path).is_ok());
+        assert_ne!(read("enveloped.lpk"), proving_key);
+        assert!(String::from_utf8_lossy(&read("enveloped.lpk")).contains("\"package_version\":\"0.2.0\""));
+
+        // Keys are not used to prove another circuit.
+        let main = std::fs::read_to_string(path.join("enveloped/src/main.leo")).unwrap();
+        std::fs::write(path.join("enveloped/src/main.leo"), main.replace("a + b", "a * b")).unwrap();
+        assert!(run_cmd("leo build", enveloped_path).is_ok());
+        assert!(run_cmd("leo prove --circuit outputs/enveloped.json", enveloped_path).is_err());
+    }
+
+    #[test]
+    fn test_prove_all_inputs() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new batch", &Some(path.clone())).is_ok());
+        let batch_path = &Some(path.join("batch"));
+        let inputs = path.join("batch/inputs");
+        let outputs = path.join("batch/outputs");
+
+        let input = std::fs::read_to_string(inputs.join("batch.in")).unwrap();
+        let state = std::fs::read_to_string(inputs.join("batch.state")).unwrap();
+        std::fs::write(inputs.join("second.in"), input.replace("a: u32 = 1", "a: u32 = 5")).unwrap();
+        std::fs::write(inputs.join("second.state"), &state).unwrap();
+        std::fs::write(inputs.join("broken.in"), "[main]\na: u32 = ;\n").unwrap();
+        std::fs::write(inputs.join("broken.state"), &state).unwrap();
+
+        // A pair that fails does not stop the others.
+        assert!(run_cmd("leo prove --all-inputs --jobs 2", batch_path).is_err());
+        assert!(outputs.join("batch.proof").exists());
+        assert!(outputs.join("second.proof").exists());
+        assert!(!outputs.join("broken.proof").exists());
+        assert!(std::fs::read_to_string(outputs.join("second.out"))
+            .unwrap()
+            .contains('7'));
+
+        std::fs::remove_file(inputs.join("broken.in")).unwrap();
+        std::fs::remove_file(inputs.join("broken.state")).unwrap();
+        assert!(run_cmd("leo prove --all-inputs", batch_path).is_ok());
+
+        assert!(run_cmd("leo clean", batch_path).is_ok());
+        assert!(!outputs.join("second.proof").exists());
+        assert!(!outputs.join("second.out").exists());
+    }
+
+    #[test]
+    fn test_build_profile() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new profiled", &Some(path.clone())).is_ok());
+        let profiled_path = &Some(path.join("profiled"));
+
+        assert!(run_cmd("leo build", profiled_path).is_ok());
+        assert!(!path.join("profiled/outputs/profiled.folded").exists());
+
+        assert!(run_cmd("leo build --profile", profiled_path).is_ok());
+        let folded = std::fs::read_to_string(path.join("profiled/outputs/profiled.folded")).unwrap();
+        assert!(folded.lines().all(|line| line.starts_with("main;")));
+
+        assert!(run_cmd("leo clean", profiled_path).is_ok());
+        assert!(!path.join("profiled/outputs/profiled.folded").exists());
+    }
+
+    #[test]
+    fn test_prove_circuit() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new circuit", &Some(path.clone())).is_ok());
+        let circuit_path = &Some(path.join("circuit"));
+        assert!(run_cmd("leo setup", circuit_path).is_ok());
+
+        // The circuit is proven from the build outputs alone.
+        std::fs::remove_dir_all(path.join("circuit/src")).unwrap();
+        assert!(run_cmd("leo prove --circuit outputs/circuit.json", circuit_path).is_ok());
+        assert!(path.join("circuit/outputs/circuit.proof").exists());
+
+        // Keys are expected next to the circuit file.
+        std::fs::copy(path.join("circuit/outputs/circuit.json"), path.join("circuit.json")).unwrap();
+        let prove = "leo prove --circuit circuit.json --proving-system groth16";
+        assert!(run_cmd(prove, &Some(path.clone())).is_err());
+
+        // Assignments that do not satisfy the constraints are rejected.
+        let json = std::fs::read_to_string(path.join("circuit/outputs/circuit.witness")).unwrap();
+        let mut witness: serde_json::Value = serde_json::from_str(&json).unwrap();
+        witness["private_variables"][0] = serde_json::Value::String("2".to_string());
+        std::fs::write(path.join("circuit/outputs/circuit.witness"), witness.to_string()).unwrap();
+        assert!(run_cmd("leo prove --circuit outputs/circuit.json", circuit_path).is_err());
+    }
+
+    #[test]
+    fn test_witness() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new witness", &Some(path.clone())).is_ok());
+        let witness_path = &Some(path.join("witness"));
+        assert!(run_cmd("leo setup", witness_path).is_ok());
+
+        // The circuit file holds only the shape, the witness of the package inputs is written apart.
+        let circuit = std::fs::read_to_string(path.join("witness/outputs/witness.json")).unwrap();
+        assert!(!circuit.contains("\"private_variables\""));
+        assert!(path.join("witness/outputs/witness.witness").exists());
+
+        let inputs = path.join("witness/inputs");
+        std::fs::write(
+            inputs.join("other.in"),
+            "[main]\na: u32 = 5;\nb: u32 = 7;\n\n[registers]\nr0: u32 = 0;\n",
+        )
+        .unwrap();
+        std::fs::copy(inputs.join("witness.state"), inputs.join("other.state")).unwrap();
+        assert!(run_cmd("leo witness --input other", witness_path).is_ok());
+        assert!(run_cmd("leo witness --input missing", witness_path).is_err());
+
+        // Other inputs are proven with the same circuit and keys.
+        assert!(run_cmd(
+            "leo prove --circuit outputs/witness.json --witness outputs/other.witness",
+            witness_path
+        )
+        .is_ok());
+
+        // A circuit shape cannot be proven without a witness.
+        std::fs::remove_file(path.join("witness/outputs/witness.witness")).unwrap();
+        assert!(run_cmd("leo prove --circuit outputs/witness.json", witness_path).is_err());
+
+        assert!(run_cmd("leo clean", witness_path).is_ok());
+        assert!(!path.join("witness/outputs/other.witness").exists());
+    }
+
+    #[test]
+    fn test_binary_circuit() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new binary", &Some(path.clone())).is_ok());
+        let binary_path = &Some(path.join("binary"));
+        assert!(run_cmd("leo build --circuit-format binary", binary_path).is_ok());
+        assert!(path.join("binary/outputs/binary.circuit").exists());
+        assert!(!path.join("binary/outputs/binary.json").exists());
+        assert!(run_cmd("leo build --circuit-format yaml", binary_path).is_err());
+
+        assert!(run_cmd("leo setup --circuit-format binary", binary_path).is_ok());
+        assert!(run_cmd("leo prove --circuit outputs/binary.circuit", binary_path).is_ok());
+        assert!(path.join("binary/outputs/binary.proof").exists());
+
+        // Switching formats leaves a single circuit file behind.
+        assert!(run_cmd("leo build --circuit-format json", binary_path).is_ok());
+        assert!(path.join("binary/outputs/binary.json").exists());
+        assert!(!path.join("binary/outputs/binary.circuit").exists());
+    }
+
+    #[test]
+    fn test_build_emit() {
+        let dir = testdir("test");
+        let path = dir.path("test");
+
+        assert!(run_cmd("leo new emit", &Some(path.clone())).is_ok());
+        let emit_path = &Some(path.join("emit"));
+        assert!(run_cmd("leo build --emit r1cs,wtns,zkif", emit_path).is_ok());
+        for extension in ["r1cs", "wtns", "zkif"] {
+            assert!(path.join(format!("emit/outputs/emit.{}", extension)).exists());
+        }
+        assert!(run_cmd("leo build --emit r1cs,json", emit_path).is_err());
+
+        assert!(run_cmd("leo clean", emit_path).is_ok());
+        for extension in ["r1cs", "wtns", "zkif"] {
+            assert!(!path.join(format!("emit/outputs/emit.{}", extension)).exists());
+        }
+    }
+
     #[test]
     fn test_install() {
         let dir = testdir("test");
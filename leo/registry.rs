@@ -0,0 +1,282 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::api::{Api, Fetch, Publish};
+use leo_errors::{CliError, Result};
+use leo_package::{
+    root::{Manifest, MANIFEST_FILENAME},
+    LeoPackage,
+};
+
+use reqwest::Url;
+use semver::Version;
+use std::{
+    collections::HashMap,
+    fs,
+    io::{Cursor, Read},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+/// The package operations the CLI needs from a package registry.
+pub trait Registry: Send + Sync {
+    /// Returns the zip archive of a package, at the given version or the latest one.
+    fn fetch(&self, author: &str, package: &str, version: Option<&str>) -> Result<Vec<u8>>;
+
+    /// Returns the versions of a package that have not been yanked.
+    fn versions(&self, author: &str, package: &str) -> Result<Vec<String>>;
+
+    /// Publishes the zip archive of a package version and returns the id of the published package.
+    fn publish(&self, author: &str, package: &str, version: &str, file: &Path) -> Result<String>;
+
+    /// Yanks a package version so it is no longer selected for new lock files,
+    /// or restores it if `yanked` is false. Yanked versions can still be fetched.
+    fn yank(&self, author: &str, package: &str, version: &str, yanked: bool) -> Result<()>;
+}
+
+///
+/// Returns the registry behind the given URL.
+///
+/// `file://` URLs point at a directory used as a registry, any other URL is an Aleo PM host.
+///
+pub fn registry_from_url(url: &str, api: &Api) -> Result<Arc<dyn Registry>> {
+    if url.starts_with("file:") {
+        let path = Url::parse(url)
+            .ok()
+            .and_then(|url| url.to_file_path().ok())
+            .ok_or_else(|| CliError::invalid_registry_url(url))?;
+        Ok(Arc::new(FileRegistry::new(path)))
+    } else {
+        Ok(Arc::new(PackageManager::new(api.clone())))
+    }
+}
+
+///
+/// The Aleo Package Manager.
+///
+/// The Aleo PM does not list the versions of a package, so the latest archive is downloaded
+/// to read its version, and is kept until that version is fetched.
+///
+#[derive(Debug)]
+pub struct PackageManager {
+    api: Api,
+    /// The latest archive of each listed package, by author and package name.
+    latest: Mutex<HashMap<(String, String), LatestArchive>>,
+}
+
+/// The latest version of a package and its zip archive.
+type LatestArchive = (String, Vec<u8>);
+
+impl PackageManager {
+    pub fn new(api: Api) -> Self {
+        Self {
+            api,
+            latest: Mutex::new(HashMap::new()),
+        }
+    }
+}
+
+impl Registry for PackageManager {
+    fn fetch(&self, author: &str, package: &str, version: Option<&str>) -> Result<Vec<u8>> {
+        let key = (author.to_string(), package.to_string());
+        let mut latest = self.latest.lock().unwrap_or_else(|error| error.into_inner());
+        if let Some((latest_version, _)) = latest.get(&key) {
+            if version.is_none_or(|version| version == latest_version) {
+                return Ok(latest.remove(&key).unwrap().1);
+            }
+        }
+        drop(latest);
+
+        let bytes = self
+            .api
+            .run_route(Fetch {
+                author: author.to_string(),
+                package_name: package.to_string(),
+                version: version.map(str::to_string),
+            })?
+            .bytes()
+            .map_err(CliError::cli_bytes_conversion_error)?;
+
+        Ok(bytes.to_vec())
+    }
+
+    /// Only the latest version is offered.
+    fn versions(&self, author: &str, package: &str) -> Result<Vec<String>> {
+        let archive = self.fetch(author, package, None)?;
+        let mut zip_archive = zip::ZipArchive::new(Cursor::new(&archive)).map_err(CliError::cli_zip_error)?;
+
+        let mut manifest = String::new();
+        zip_archive
+            .by_name(MANIFEST_FILENAME)
+            .map_err(|_| CliError::unable_to_read_imported_dependency_manifest())?
+            .read_to_string(&mut manifest)
+            .map_err(CliError::cli_io_error)?;
+        let manifest = toml::from_str::<Manifest>(&manifest)
+            .map_err(|_| CliError::unable_to_read_imported_dependency_manifest())?;
+        let version = manifest.get_package_version();
+
+        self.latest
+            .lock()
+            .unwrap_or_else(|error| error.into_inner())
+            .insert((author.to_string(), package.to_string()), (version.clone(), archive));
+        Ok(vec![version])
+    }
+
+    fn publish(&self, author: &str, package: &str, version: &str, file: &Path) -> Result<String> {
+        self.api.run_route(Publish {
+            name: package.to_string(),
+            remote: format!("{}/{}", author, package),
+            version: version.to_string(),
+            file: file.to_path_buf(),
+        })
+    }
+
+    fn yank(&self, _author: &str, _package: &str, _version: &str, _yanked: bool) -> Result<()> {
+        Err(CliError::yank_unsupported().into())
+    }
+}
+
+///
+/// A registry stored in a local directory, for internal mirrors and machines without network access.
+///
+/// Versions are stored as `<author>/<package>/<version>.zip`, and a yanked version
+/// has an empty `<version>.yanked` file next to its archive.
+///
+#[derive(Clone, Debug)]
+pub struct FileRegistry {
+    root: PathBuf,
+}
+
+impl FileRegistry {
+    pub fn new(root: PathBuf) -> Self {
+        Self { root }
+    }
+
+    ///
+    /// Returns the directory holding the versions of a package.
+    ///
+    /// Authors and package names are checked before they are joined to the root, so they
+    /// cannot point outside of the registry.
+    ///
+    fn package_directory(&self, author: &str, package: &str) -> Result<PathBuf> {
+        if !is_author_valid(author) {
+            return Err(CliError::invalid_registry_path_segment("author", author).into());
+        }
+        if !LeoPackage::is_package_name_valid(package) {
+            return Err(CliError::invalid_registry_path_segment("package name", package).into());
+        }
+
+        Ok(self.root.join(author).join(package))
+    }
+
+    fn archive(&self, author: &str, package: &str, version: &str) -> Result<PathBuf> {
+        check_version(version)?;
+        Ok(self
+            .package_directory(author, package)?
+            .join(format!("{}.zip", version)))
+    }
+
+    fn yank_marker(&self, author: &str, package: &str, version: &str) -> Result<PathBuf> {
+        check_version(version)?;
+        Ok(self
+            .package_directory(author, package)?
+            .join(format!("{}.yanked", version)))
+    }
+}
+
+impl Registry for FileRegistry {
+    fn fetch(&self, author: &str, package: &str, version: Option<&str>) -> Result<Vec<u8>> {
+        let version = match version {
+            Some(version) => version.to_string(),
+            None => self
+                .versions(author, package)?
+                .into_iter()
+                .filter_map(|version| Version::parse(&version).ok())
+                .max()
+                .ok_or_else(CliError::package_not_found)?
+                .to_string(),
+        };
+
+        let archive = self.archive(author, package, &version)?;
+        if !archive.is_file() {
+            return Err(CliError::package_not_found().into());
+        }
+
+        Ok(fs::read(archive).map_err(CliError::cli_io_error)?)
+    }
+
+    fn versions(&self, author: &str, package: &str) -> Result<Vec<String>> {
+        let directory = self.package_directory(author, package)?;
+        if !directory.is_dir() {
+            return Err(CliError::package_not_found().into());
+        }
+
+        let mut versions = Vec::new();
+        for entry in fs::read_dir(&directory).map_err(CliError::cli_io_error)? {
+            let path = entry.map_err(CliError::cli_io_error)?.path();
+            if path.extension().is_some_and(|extension| extension == "zip") {
+                // Archives that are not named after a version are not versions of the package
+                let version = path.file_stem().unwrap_or_default().to_string_lossy().into_owned();
+                if Version::parse(&version).is_ok() && !self.yank_marker(author, package, &version)?.exists() {
+                    versions.push(version);
+                }
+            }
+        }
+
+        Ok(versions)
+    }
+
+    fn publish(&self, author: &str, package: &str, version: &str, file: &Path) -> Result<String> {
+        let archive = self.archive(author, package, version)?;
+        if archive.exists() {
+            return Err(CliError::already_published().into());
+        }
+
+        fs::create_dir_all(self.package_directory(author, package)?).map_err(CliError::cli_io_error)?;
+        fs::copy(file, &archive).map_err(CliError::cli_io_error)?;
+
+        Ok(format!("{}/{}@{}", author, package, version))
+    }
+
+    fn yank(&self, author: &str, package: &str, version: &str, yanked: bool) -> Result<()> {
+        if !self.archive(author, package, version)?.is_file() {
+            return Err(CliError::package_not_found().into());
+        }
+
+        let marker = self.yank_marker(author, package, version)?;
+        if yanked {
+            fs::write(marker, "").map_err(CliError::cli_io_error)?;
+        } else if marker.exists() {
+            fs::remove_file(marker).map_err(CliError::cli_io_error)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns `true` if the author is a single ASCII alphanumeric word, possibly separated by dashes or underscores.
+fn is_author_valid(author: &str) -> bool {
+    author.chars().next().is_some_and(|first| first.is_ascii_alphanumeric())
+        && author
+            .chars()
+            .all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_')
+}
+
+/// Checks that the version is a semver version, so it names a single file in the registry.
+fn check_version(version: &str) -> Result<()> {
+    Version::parse(version).map_err(|_| CliError::invalid_registry_path_segment("version", version))?;
+    Ok(())
+}
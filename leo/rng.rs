@@ -0,0 +1,44 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The randomness that programs are set up and proven with.
+
+use rand::SeedableRng;
+use rand_chacha::ChaChaRng;
+
+/// The help of the `--seed` option of the commands that set up or prove a program.
+pub(crate) const SEED_HELP: &str =
+    "Set up and prove from a fixed seed, for reproducible tests. INSECURE: anyone who knows the seed can forge proofs";
+
+///
+/// Returns the generator of the randomness to set up or prove a program with.
+///
+/// Without a seed, the generator is seeded from the operating system. With a seed, keys and
+/// proofs are reproducible, which is INSECURE: anyone who knows the seed can forge proofs, so
+/// seeds are only meant for tests.
+///
+pub fn proving_rng(seed: Option<u64>) -> ChaChaRng {
+    match seed {
+        Some(seed) => {
+            tracing::warn!(
+                "Using the fixed seed {}, which is insecure and only meant for tests",
+                seed
+            );
+            ChaChaRng::seed_from_u64(seed)
+        }
+        None => ChaChaRng::from_entropy(),
+    }
+}
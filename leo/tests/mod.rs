@@ -15,12 +15,17 @@
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
 use leo_errors::Result;
+use leo_package::outputs::{CircuitFile, ProvingKeyFile, VerificationKeyFile};
+use leo_synthesizer::{CircuitSynthesizer, SerializedCircuit};
+use snarkvm_curves::bls12_377::Bls12_377;
+use snarkvm_utilities::ToBytes;
 use std::path::PathBuf;
+use test_dir::{DirBuilder, FileType, TestDir};
 
 use crate::{
     commands::{
         package::{Login, Logout},
-        Build, Command, Prove, Run, Setup, Test,
+        Build, Command, ProgramKeys, ProgramProof, Prove, ProveInput, Run, Setup, Test,
     },
     context::{create_context, Context},
 };
@@ -59,11 +64,21 @@ pub fn setup_pedersen_hash() -> Result<()> {
     .apply(context()?, ())?;
     (Setup {
         skip_key_check: false,
+        seed: None,
+        import: None,
+        circuit_hash: None,
+        universal_srs: None,
+        replace_imported_keys: false,
         compiler_options: Default::default(),
     })
     .apply(context()?, build.clone())?;
     (Setup {
         skip_key_check: true,
+        seed: None,
+        import: None,
+        circuit_hash: None,
+        universal_srs: None,
+        replace_imported_keys: false,
         compiler_options: Default::default(),
     })
     .apply(context()?, build)?;
@@ -78,19 +93,121 @@ pub fn prove_pedersen_hash() -> Result<()> {
     .apply(context()?, ())?;
     let setup = (Setup {
         skip_key_check: false,
+        seed: None,
+        import: None,
+        circuit_hash: None,
+        universal_srs: None,
+        replace_imported_keys: false,
         compiler_options: Default::default(),
     })
     .apply(context()?, build)?;
     (Prove {
         skip_key_check: false,
+        seed: None,
+        circuit: None,
+        witness: None,
+        proving_system: None,
+        all_inputs: false,
+        jobs: None,
         compiler_options: Default::default(),
     })
-    .apply(context()?, setup.clone())?;
+    .apply(context()?, ProveInput::Program(Box::new(setup.clone())))?;
     (Prove {
         skip_key_check: true,
+        seed: None,
+        circuit: None,
+        witness: None,
+        proving_system: None,
+        all_inputs: false,
+        jobs: None,
         compiler_options: Default::default(),
     })
-    .apply(context()?, setup)?;
+    .apply(context()?, ProveInput::Program(Box::new(setup)))?;
+    Ok(())
+}
+
+#[test]
+pub fn prove_pedersen_hash_circuit() -> Result<()> {
+    let build = (Build {
+        compiler_options: Default::default(),
+    })
+    .apply(context()?, ())?;
+    let (program, keys, envelope) = (Setup {
+        skip_key_check: false,
+        seed: None,
+        import: None,
+        circuit_hash: None,
+        universal_srs: None,
+        replace_imported_keys: false,
+        compiler_options: Default::default(),
+    })
+    .apply(context()?, build)?;
+    let proving_key = match keys {
+        ProgramKeys::Groth16(proving_key, _) => proving_key,
+        ProgramKeys::Marlin(..) => panic!("the example targets Groth16"),
+    };
+
+    // Write the circuit and its keys where the package source is not available.
+    let dir = TestDir::temp().create("circuit", FileType::Dir);
+    let directory = dir.path("circuit");
+    let mut cs = CircuitSynthesizer::<Bls12_377>::default();
+    program.compile_constraints(&mut cs)?;
+    let circuit_path = directory.join("pedersen-hash.json");
+    CircuitFile::new("pedersen-hash").write_to(&circuit_path, SerializedCircuit::from(cs).to_json_string()?)?;
+
+    let mut proving_key_bytes = vec![];
+    proving_key.write_le(&mut proving_key_bytes).unwrap();
+    ProvingKeyFile::new("pedersen-hash").write_to(
+        &directory.join("pedersen-hash.lpk"),
+        &envelope,
+        &proving_key_bytes,
+    )?;
+    let mut verifying_key_bytes = vec![];
+    proving_key.vk.write_le(&mut verifying_key_bytes).unwrap();
+    VerificationKeyFile::new("pedersen-hash").write_to(
+        &directory.join("pedersen-hash.lvk"),
+        &envelope,
+        &verifying_key_bytes,
+    )?;
+
+    let proof = (Prove {
+        skip_key_check: false,
+        seed: None,
+        circuit: Some(circuit_path),
+        witness: None,
+        proving_system: None,
+        all_inputs: false,
+        jobs: None,
+        compiler_options: Default::default(),
+    })
+    .execute(context()?)?;
+    assert!(directory.join("pedersen-hash.proof").exists());
+
+    // The replayed circuit matches the keys generated from the program.
+    assert!(matches!(proof, ProgramProof::Groth16(..)));
+    assert!(proof.verify()?);
+    Ok(())
+}
+
+#[test]
+pub fn prove_pedersen_hash_with_seed() -> Result<()> {
+    let build = (Build {
+        compiler_options: Default::default(),
+    })
+    .apply(context()?, ())?;
+    let proof = |seed| -> Result<Vec<u8>> {
+        let setup = Setup::with_seed(seed).apply(context()?, build.clone())?;
+        let mut proof_bytes = vec![];
+        match Prove::with_seed(seed).apply(context()?, ProveInput::Program(Box::new(setup)))? {
+            ProgramProof::Groth16(proof, _) => proof.write_le(&mut proof_bytes).unwrap(),
+            ProgramProof::Marlin(..) => panic!("the example targets Groth16"),
+        }
+        Ok(proof_bytes)
+    };
+
+    // The same seed produces the same keys and proof.
+    assert_eq!(proof(7)?, proof(7)?);
+    assert_ne!(proof(7)?, proof(8)?);
     Ok(())
 }
 
@@ -102,21 +219,34 @@ pub fn run_pedersen_hash() -> Result<()> {
     .apply(context()?, ())?;
     let setup = (Setup {
         skip_key_check: false,
+        seed: None,
+        import: None,
+        circuit_hash: None,
+        universal_srs: None,
+        replace_imported_keys: false,
         compiler_options: Default::default(),
     })
     .apply(context()?, build)?;
     let prove = (Prove {
         skip_key_check: false,
+        seed: None,
+        circuit: None,
+        witness: None,
+        proving_system: None,
+        all_inputs: false,
+        jobs: None,
         compiler_options: Default::default(),
     })
-    .apply(context()?, setup)?;
+    .apply(context()?, ProveInput::Program(Box::new(setup)))?;
     (Run {
         skip_key_check: false,
+        seed: None,
         compiler_options: Default::default(),
     })
     .apply(context()?, prove.clone())?;
     (Run {
         skip_key_check: true,
+        seed: None,
         compiler_options: Default::default(),
     })
     .apply(context()?, prove)?;
@@ -0,0 +1,110 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{span_to_range, SymbolIndex};
+use leo_asg::Asg;
+use leo_ast::AstPass;
+use leo_ast_passes::{Canonicalizer, Importer};
+use leo_errors::{LeoError, Result};
+use leo_imports::ImportParser;
+use leo_package::root::{LockFile, Manifest};
+
+use indexmap::IndexMap;
+use lsp_types::{Diagnostic, DiagnosticSeverity, Position, Range};
+use std::{convert::TryFrom, path::Path};
+
+/// The result of running the compiler frontend over a single buffer.
+#[derive(Clone, Debug, Default)]
+pub struct Analysis {
+    /// Errors reported by the parser, import resolution, or the ASG.
+    pub diagnostics: Vec<Diagnostic>,
+    /// The symbol index, if the buffer made it through the ASG.
+    pub index: Option<SymbolIndex>,
+}
+
+impl Analysis {
+    ///
+    /// Analyzes the contents of the buffer at the given path.
+    ///
+    /// Imports are resolved from disk relative to the package containing the buffer.
+    /// The compiler stops at the first error, so a buffer has at most one diagnostic.
+    ///
+    pub fn new(path: &Path, source: &str) -> Self {
+        match Self::index(path, source) {
+            Ok(index) => Self {
+                diagnostics: Vec::new(),
+                index: Some(index),
+            },
+            Err(error) => Self {
+                diagnostics: vec![to_diagnostic(path, &error)],
+                index: None,
+            },
+        }
+    }
+
+    fn index(path: &Path, source: &str) -> Result<SymbolIndex> {
+        let ast = leo_parser::parse_ast(path.to_str().unwrap_or_default(), source)?;
+        let ast = Importer::do_pass(
+            ast.into_repr(),
+            &mut ImportParser::new(path.to_path_buf(), imports_map(path)),
+        )?;
+        let ast = Canonicalizer::do_pass(ast.into_repr())?;
+
+        // The arena only lives as long as this analysis, the index keeps owned copies.
+        let arena = leo_asg::new_alloc_context();
+        let context = leo_asg::new_context(&arena);
+        let asg = Asg::new(context, ast.as_repr())?;
+
+        Ok(SymbolIndex::new(asg.as_repr()))
+    }
+}
+
+/// Returns the imports map from the lock file of the package containing the path, if any.
+fn imports_map(path: &Path) -> IndexMap<String, String> {
+    path.ancestors()
+        .find(|directory| Manifest::exists_at(directory))
+        .filter(|directory| LockFile::exists_at(directory))
+        .and_then(|directory| LockFile::try_from(directory).ok())
+        .and_then(|lock_file| lock_file.to_import_map().ok())
+        .unwrap_or_default()
+}
+
+/// Converts a Leo error into a diagnostic on the analyzed buffer.
+fn to_diagnostic(path: &Path, error: &LeoError) -> Diagnostic {
+    let (range, message) = match error.span() {
+        Some(span) if Path::new(span.path.as_str()) == path => (span_to_range(span), error.message()),
+        Some(span) => (
+            Range::default(),
+            format!(
+                "{}:{}:{}: {}",
+                span.path,
+                span.line_start,
+                span.col_start,
+                error.message()
+            ),
+        ),
+        None => (Range::new(Position::new(0, 0), Position::new(0, 0)), error.message()),
+    };
+
+    Diagnostic {
+        range,
+        severity: Some(DiagnosticSeverity::ERROR),
+        code: Some(lsp_types::NumberOrString::String(error.error_code())),
+        source: Some("leo".to_string()),
+        message,
+        ..Default::default()
+    }
+}
@@ -0,0 +1,438 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_asg::*;
+use leo_errors::Span;
+
+use indexmap::IndexMap;
+use lsp_types::{Location, Position, Range, Url};
+use std::{cell::Cell, path::Path};
+
+///
+/// Converts a 1-based Leo span into a 0-based LSP range.
+///
+/// Leo columns count bytes, while LSP columns count UTF-16 code units, so each column is
+/// converted on its line, which the span content starts and ends with.
+///
+pub fn span_to_range(span: &Span) -> Range {
+    let first_line = span.content.lines().next().unwrap_or_default();
+    let last_line = span.content.lines().last().unwrap_or_default();
+    Range::new(
+        Position::new(
+            span.line_start.saturating_sub(1) as u32,
+            utf16_column(first_line, span.col_start),
+        ),
+        Position::new(
+            span.line_stop.saturating_sub(1) as u32,
+            utf16_column(last_line, span.col_stop),
+        ),
+    )
+}
+
+/// Converts a 1-based byte column on the line into a 0-based UTF-16 column.
+fn utf16_column(line: &str, column: usize) -> u32 {
+    let column = column.saturating_sub(1);
+    let mut end = column.min(line.len());
+    while !line.is_char_boundary(end) {
+        end -= 1;
+    }
+
+    // Columns past the end of the line are kept as they are.
+    (line[..end].encode_utf16().count() + (column - end)) as u32
+}
+
+/// Converts a 0-based UTF-16 column on the line into a byte offset into the line.
+pub fn byte_offset(line: &str, column: u32) -> usize {
+    let mut units = 0;
+    for (offset, character) in line.char_indices() {
+        if units >= column as usize {
+            return offset;
+        }
+        units += character.len_utf16();
+    }
+    line.len()
+}
+
+/// Converts a Leo span into an LSP location, if the span points into a file on disk.
+pub fn span_to_location(span: &Span) -> Option<Location> {
+    let path = Path::new(span.path.as_str());
+    if !path.is_absolute() {
+        return None;
+    }
+
+    Some(Location::new(Url::from_file_path(path).ok()?, span_to_range(span)))
+}
+
+/// Returns `true` if the location is in the given document and contains the position.
+pub fn contains(location: &Location, uri: &Url, position: Position) -> bool {
+    &location.uri == uri && location.range.start <= position && position <= location.range.end
+}
+
+/// Orders ranges so that the innermost range is the smallest.
+fn range_size(range: &Range) -> (u32, u32) {
+    (
+        range.end.line - range.start.line,
+        if range.end.line == range.start.line {
+            range.end.character - range.start.character
+        } else {
+            range.end.character
+        },
+    )
+}
+
+/// A variable, function argument or loop variable found in the ASG.
+#[derive(Clone, Debug)]
+pub struct Symbol {
+    pub name: String,
+    pub type_: String,
+    /// The name of the circuit if the variable has a circuit type.
+    pub circuit: Option<String>,
+    pub mutable: bool,
+    pub declaration: Location,
+    pub references: Vec<Location>,
+    pub assignments: Vec<Location>,
+}
+
+impl Symbol {
+    /// Returns `true` if the declaration or any use of the symbol contains the position.
+    pub fn contains(&self, uri: &Url, position: Position) -> bool {
+        contains(&self.declaration, uri, position)
+            || self.references.iter().any(|location| contains(location, uri, position))
+            || self
+                .assignments
+                .iter()
+                .any(|location| contains(location, uri, position))
+    }
+}
+
+/// The kind of a circuit member, as offered for completion.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum MemberKind {
+    Variable,
+    Method,
+    Static,
+}
+
+/// A member variable or function of a circuit.
+#[derive(Clone, Debug)]
+pub struct Member {
+    pub name: String,
+    pub kind: MemberKind,
+    /// The member type or function signature.
+    pub detail: String,
+}
+
+/// A circuit and its members.
+#[derive(Clone, Debug)]
+pub struct CircuitInfo {
+    pub name: String,
+    pub location: Option<Location>,
+    pub members: Vec<Member>,
+}
+
+/// A function body, used to resolve `self` to the enclosing circuit.
+#[derive(Clone, Debug)]
+pub struct FunctionInfo {
+    pub name: String,
+    pub location: Location,
+    pub circuit: Option<String>,
+}
+
+/// A use of a function or circuit pointing at its declaration.
+#[derive(Clone, Debug)]
+pub struct Link {
+    pub usage: Location,
+    pub target: Location,
+}
+
+/// An expression and its resolved type.
+#[derive(Clone, Debug)]
+pub struct TypedExpression {
+    pub location: Location,
+    pub type_: String,
+}
+
+/// An owned index of a program's ASG that outlives the ASG arena.
+#[derive(Clone, Debug, Default)]
+pub struct SymbolIndex {
+    pub symbols: Vec<Symbol>,
+    pub expressions: Vec<TypedExpression>,
+    pub links: Vec<Link>,
+    pub circuits: IndexMap<String, CircuitInfo>,
+    pub functions: Vec<FunctionInfo>,
+}
+
+impl SymbolIndex {
+    /// Builds an index from every program and import reachable from the given ASG.
+    pub fn new(program: &Program) -> Self {
+        let mut director = VisitorDirector::new(IndexBuilder::default());
+        director.visit_program(program).ok();
+        director.visitor().finish()
+    }
+
+    /// Returns the symbol declared or used at the position.
+    pub fn symbol_at(&self, uri: &Url, position: Position) -> Option<&Symbol> {
+        self.symbols.iter().find(|symbol| symbol.contains(uri, position))
+    }
+
+    /// Returns the type of the innermost expression at the position.
+    pub fn type_at(&self, uri: &Url, position: Position) -> Option<&str> {
+        self.expressions
+            .iter()
+            .filter(|expression| contains(&expression.location, uri, position))
+            .min_by_key(|expression| range_size(&expression.location.range))
+            .map(|expression| expression.type_.as_str())
+    }
+
+    /// Returns the declaration of the innermost function or circuit use at the position.
+    pub fn link_at(&self, uri: &Url, position: Position) -> Option<&Location> {
+        self.links
+            .iter()
+            .filter(|link| contains(&link.usage, uri, position))
+            .min_by_key(|link| range_size(&link.usage.range))
+            .map(|link| &link.target)
+    }
+
+    /// Returns the circuit whose function body contains the position.
+    pub fn enclosing_circuit(&self, uri: &Url, position: Position) -> Option<&CircuitInfo> {
+        self.functions
+            .iter()
+            .filter(|function| contains(&function.location, uri, position))
+            .find_map(|function| function.circuit.as_ref())
+            .and_then(|name| self.circuits.get(name))
+    }
+
+    /// Returns the closest symbol with the given name declared before the position in the document.
+    pub fn visible_symbol(&self, name: &str, uri: &Url, position: Position) -> Option<&Symbol> {
+        self.symbols
+            .iter()
+            .filter(|symbol| {
+                symbol.name == name && &symbol.declaration.uri == uri && symbol.declaration.range.start <= position
+            })
+            .max_by_key(|symbol| symbol.declaration.range.start)
+    }
+}
+
+/// Collects a [`SymbolIndex`] while visiting the ASG.
+#[derive(Default)]
+struct IndexBuilder<'a> {
+    variables: IndexMap<u32, &'a Variable<'a>>,
+    expressions: Vec<TypedExpression>,
+    links: Vec<Link>,
+    circuits: IndexMap<String, CircuitInfo>,
+    functions: Vec<FunctionInfo>,
+}
+
+impl<'a> IndexBuilder<'a> {
+    fn add_variable(&mut self, variable: &'a Variable<'a>) {
+        let id = variable.borrow().id;
+        self.variables.entry(id).or_insert(variable);
+    }
+
+    fn add_link(&mut self, usage: Option<&Span>, target: &Span) {
+        if let (Some(usage), Some(target)) = (usage.and_then(span_to_location), span_to_location(target)) {
+            self.links.push(Link { usage, target });
+        }
+    }
+
+    fn finish(self) -> SymbolIndex {
+        let symbols = self
+            .variables
+            .values()
+            .filter_map(|variable| {
+                let variable = variable.borrow();
+                Some(Symbol {
+                    name: variable.name.name.to_string(),
+                    type_: variable.type_.to_string(),
+                    circuit: match &variable.type_ {
+                        Type::Circuit(circuit) => Some(circuit.name.borrow().name.to_string()),
+                        _ => None,
+                    },
+                    mutable: variable.mutable,
+                    declaration: span_to_location(&variable.name.span)?,
+                    references: variable
+                        .references
+                        .iter()
+                        .filter_map(|reference| reference.span().and_then(span_to_location))
+                        .collect(),
+                    assignments: variable
+                        .assignments
+                        .iter()
+                        .filter_map(|assignment| match assignment {
+                            // Only point at the assigned name, the definition itself is the declaration.
+                            Statement::Assign(assign) => {
+                                let mut span = assign.span.clone()?;
+                                span.line_stop = span.line_start;
+                                span.col_stop = span.col_start + variable.name.name.len();
+                                span_to_location(&span)
+                            }
+                            _ => None,
+                        })
+                        .collect(),
+                })
+            })
+            .collect();
+
+        SymbolIndex {
+            symbols,
+            expressions: self.expressions,
+            links: self.links,
+            circuits: self.circuits,
+            functions: self.functions,
+        }
+    }
+}
+
+/// Formats a function signature as it would be declared in Leo.
+fn signature(function: &Function) -> String {
+    let arguments = function
+        .arguments
+        .values()
+        .map(|argument| {
+            let argument = argument.get().borrow();
+            format!("{}: {}", argument.name.name, argument.type_)
+        })
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    format!(
+        "function {}({}) -> {}",
+        function.name.borrow().name,
+        arguments,
+        function.output
+    )
+}
+
+impl<'a> ExpressionVisitor<'a> for IndexBuilder<'a> {
+    fn visit_expression(&mut self, input: &Cell<&'a Expression<'a>>) -> VisitResult {
+        let expression = input.get();
+        if let (Some(location), Some(type_)) = (expression.span().and_then(span_to_location), expression.get_type()) {
+            self.expressions.push(TypedExpression {
+                location,
+                type_: type_.to_string(),
+            });
+        }
+        VisitResult::VisitChildren
+    }
+
+    fn visit_call(&mut self, input: &CallExpression<'a>) -> VisitResult {
+        let function = input.function.get();
+        let target = function.name.borrow().span.clone();
+        self.add_link(input.span.as_ref(), &target);
+        VisitResult::VisitChildren
+    }
+
+    fn visit_circuit_access(&mut self, input: &CircuitAccessExpression<'a>) -> VisitResult {
+        let circuit = input.circuit.get();
+        let target = match circuit.members.borrow().get(input.member.name.as_ref()) {
+            Some(CircuitMember::Function(function)) => function.name.borrow().span.clone(),
+            _ => circuit.name.borrow().span.clone(),
+        };
+        self.add_link(Some(&input.member.span), &target);
+        VisitResult::VisitChildren
+    }
+
+    fn visit_circuit_init(&mut self, input: &CircuitInitExpression<'a>) -> VisitResult {
+        let target = input.circuit.get().name.borrow().span.clone();
+        self.add_link(input.span.as_ref(), &target);
+        VisitResult::VisitChildren
+    }
+
+    fn visit_variable_ref(&mut self, input: &VariableRef<'a>) -> VisitResult {
+        self.add_variable(input.variable);
+        VisitResult::VisitChildren
+    }
+}
+
+impl<'a> StatementVisitor<'a> for IndexBuilder<'a> {
+    fn visit_assign(&mut self, input: &AssignStatement<'a>) -> VisitResult {
+        self.add_variable(input.target_variable.get());
+        VisitResult::VisitChildren
+    }
+
+    fn visit_definition(&mut self, input: &DefinitionStatement<'a>) -> VisitResult {
+        for variable in input.variables.iter() {
+            self.add_variable(variable);
+        }
+        VisitResult::VisitChildren
+    }
+
+    fn visit_iteration(&mut self, input: &IterationStatement<'a>) -> VisitResult {
+        self.add_variable(input.variable);
+        VisitResult::VisitChildren
+    }
+}
+
+impl<'a> ProgramVisitor<'a> for IndexBuilder<'a> {
+    fn visit_function(&mut self, input: &'a Function<'a>) -> VisitResult {
+        for argument in input.arguments.values() {
+            self.add_variable(argument.get());
+        }
+        if let Some(location) = input.span.as_ref().and_then(span_to_location) {
+            self.functions.push(FunctionInfo {
+                name: input.name.borrow().name.to_string(),
+                location,
+                circuit: input
+                    .circuit
+                    .get()
+                    .map(|circuit| circuit.name.borrow().name.to_string()),
+            });
+        }
+        VisitResult::VisitChildren
+    }
+
+    fn visit_circuit(&mut self, input: &'a Circuit<'a>) -> VisitResult {
+        let name = input.name.borrow().name.to_string();
+        let members = input
+            .members
+            .borrow()
+            .iter()
+            .map(|(name, member)| match member {
+                CircuitMember::Variable(type_) => Member {
+                    name: name.clone(),
+                    kind: MemberKind::Variable,
+                    detail: type_.to_string(),
+                },
+                CircuitMember::Function(function) => Member {
+                    name: name.clone(),
+                    kind: if function.qualifier == FunctionQualifier::Static {
+                        MemberKind::Static
+                    } else {
+                        MemberKind::Method
+                    },
+                    detail: signature(function),
+                },
+            })
+            .collect();
+
+        self.circuits.insert(
+            name.clone(),
+            CircuitInfo {
+                location: span_to_location(&input.name.borrow().span),
+                name,
+                members,
+            },
+        );
+        VisitResult::VisitChildren
+    }
+
+    fn visit_global_const(&mut self, input: &'a DefinitionStatement<'a>) -> VisitResult {
+        for variable in input.variables.iter() {
+            self.add_variable(variable);
+        }
+        VisitResult::VisitChildren
+    }
+}
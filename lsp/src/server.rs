@@ -0,0 +1,309 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{byte_offset, Analysis, MemberKind, SymbolIndex};
+
+use lsp_server::{Connection, ErrorCode, Message, Notification, Request, RequestId, Response};
+use lsp_types::{
+    notification::{DidChangeTextDocument, DidCloseTextDocument, DidOpenTextDocument, PublishDiagnostics},
+    request::{Completion, GotoDefinition, HoverRequest, References},
+    CompletionItem, CompletionItemKind, CompletionOptions, CompletionParams, GotoDefinitionParams,
+    GotoDefinitionResponse, Hover, HoverContents, HoverParams, HoverProviderCapability, InitializeParams,
+    MarkupContent, MarkupKind, OneOf, Position, PublishDiagnosticsParams, ReferenceParams, ServerCapabilities,
+    TextDocumentSyncCapability, TextDocumentSyncKind, Url,
+};
+use std::{collections::HashMap, error::Error};
+
+/// The error type of the language server loop.
+pub type ServerError = Box<dyn Error + Send + Sync>;
+
+/// Returns the capabilities advertised to the client.
+pub fn capabilities() -> ServerCapabilities {
+    ServerCapabilities {
+        text_document_sync: Some(TextDocumentSyncCapability::Kind(TextDocumentSyncKind::FULL)),
+        hover_provider: Some(HoverProviderCapability::Simple(true)),
+        definition_provider: Some(OneOf::Left(true)),
+        references_provider: Some(OneOf::Left(true)),
+        completion_provider: Some(CompletionOptions {
+            trigger_characters: Some(vec![".".to_string(), ":".to_string()]),
+            ..Default::default()
+        }),
+        ..Default::default()
+    }
+}
+
+/// An open buffer and the last index that was built for it.
+#[derive(Default)]
+struct Document {
+    text: String,
+    /// The last successful index, kept while the buffer does not compile
+    /// so that hover and completion keep working during edits.
+    index: Option<SymbolIndex>,
+}
+
+/// The state of the language server between messages.
+pub struct Server<'c> {
+    connection: &'c Connection,
+    documents: HashMap<Url, Document>,
+}
+
+impl<'c> Server<'c> {
+    ///
+    /// Performs the initialize handshake on the connection and returns the client parameters.
+    ///
+    pub fn initialize(connection: &'c Connection) -> Result<(Self, InitializeParams), ServerError> {
+        let params = connection.initialize(serde_json::to_value(capabilities())?)?;
+        let params = serde_json::from_value(params)?;
+
+        Ok((
+            Self {
+                connection,
+                documents: HashMap::new(),
+            },
+            params,
+        ))
+    }
+
+    ///
+    /// Handles messages until the client requests a shutdown.
+    ///
+    pub fn run(mut self) -> Result<(), ServerError> {
+        for message in &self.connection.receiver {
+            match message {
+                Message::Request(request) => {
+                    if self.connection.handle_shutdown(&request)? {
+                        return Ok(());
+                    }
+                    self.handle_request(request)?;
+                }
+                Message::Notification(notification) => self.handle_notification(notification)?,
+                Message::Response(_) => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    fn handle_request(&mut self, request: Request) -> Result<(), ServerError> {
+        let response = match request.method.as_str() {
+            <HoverRequest as lsp_types::request::Request>::METHOD => {
+                self.respond(request, |server, params: HoverParams| server.hover(params))
+            }
+            <GotoDefinition as lsp_types::request::Request>::METHOD => self
+                .respond(request, |server, params: GotoDefinitionParams| {
+                    server.definition(params)
+                }),
+            <References as lsp_types::request::Request>::METHOD => {
+                self.respond(request, |server, params: ReferenceParams| server.references(params))
+            }
+            <Completion as lsp_types::request::Request>::METHOD => {
+                self.respond(request, |server, params: CompletionParams| server.completion(params))
+            }
+            _ => Response::new_err(
+                request.id,
+                ErrorCode::MethodNotFound as i32,
+                format!("unsupported request `{}`", request.method),
+            ),
+        };
+
+        self.connection.sender.send(Message::Response(response))?;
+        Ok(())
+    }
+
+    /// Deserializes the request parameters and serializes the handler result into a response.
+    fn respond<P, R, F>(&self, request: Request, handler: F) -> Response
+    where
+        P: serde::de::DeserializeOwned,
+        R: serde::Serialize,
+        F: FnOnce(&Self, P) -> R,
+    {
+        let Request { id, params, .. } = request;
+        match serde_json::from_value::<P>(params) {
+            Ok(params) => Response::new_ok(id, handler(self, params)),
+            Err(error) => invalid_params(id, error.to_string()),
+        }
+    }
+
+    fn handle_notification(&mut self, notification: Notification) -> Result<(), ServerError> {
+        match notification.method.as_str() {
+            <DidOpenTextDocument as lsp_types::notification::Notification>::METHOD => {
+                let params: lsp_types::DidOpenTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.update(params.text_document.uri, params.text_document.text)?;
+            }
+            <DidChangeTextDocument as lsp_types::notification::Notification>::METHOD => {
+                let params: lsp_types::DidChangeTextDocumentParams = serde_json::from_value(notification.params)?;
+                // The server asks for full synchronization, so the last change holds the whole buffer.
+                if let Some(change) = params.content_changes.into_iter().last() {
+                    self.update(params.text_document.uri, change.text)?;
+                }
+            }
+            <DidCloseTextDocument as lsp_types::notification::Notification>::METHOD => {
+                let params: lsp_types::DidCloseTextDocumentParams = serde_json::from_value(notification.params)?;
+                self.documents.remove(&params.text_document.uri);
+                self.publish(params.text_document.uri, Vec::new())?;
+            }
+            _ => {}
+        }
+
+        Ok(())
+    }
+
+    /// Re-analyzes a buffer and publishes its diagnostics.
+    fn update(&mut self, uri: Url, text: String) -> Result<(), ServerError> {
+        let analysis = match uri.to_file_path() {
+            Ok(path) => Analysis::new(&path, &text),
+            Err(()) => Analysis::default(),
+        };
+
+        let document = self.documents.entry(uri.clone()).or_default();
+        document.text = text;
+        if analysis.index.is_some() {
+            document.index = analysis.index;
+        }
+
+        self.publish(uri, analysis.diagnostics)
+    }
+
+    fn publish(&self, uri: Url, diagnostics: Vec<lsp_types::Diagnostic>) -> Result<(), ServerError> {
+        let params = PublishDiagnosticsParams {
+            uri,
+            diagnostics,
+            version: None,
+        };
+        let method = <PublishDiagnostics as lsp_types::notification::Notification>::METHOD;
+        self.connection
+            .sender
+            .send(Message::Notification(Notification::new(method.to_string(), params)))?;
+        Ok(())
+    }
+
+    fn index(&self, uri: &Url) -> Option<&SymbolIndex> {
+        self.documents.get(uri).and_then(|document| document.index.as_ref())
+    }
+
+    fn hover(&self, params: HoverParams) -> Option<Hover> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+        let index = self.index(uri)?;
+
+        let value = match index.symbol_at(uri, position) {
+            Some(symbol) if symbol.mutable => format!("let {}: {}", symbol.name, symbol.type_),
+            Some(symbol) => format!("const {}: {}", symbol.name, symbol.type_),
+            None => index.type_at(uri, position)?.to_string(),
+        };
+
+        Some(Hover {
+            contents: HoverContents::Markup(MarkupContent {
+                kind: MarkupKind::Markdown,
+                value: format!("```leo\n{}\n```", value),
+            }),
+            range: None,
+        })
+    }
+
+    fn definition(&self, params: GotoDefinitionParams) -> Option<GotoDefinitionResponse> {
+        let uri = &params.text_document_position_params.text_document.uri;
+        let position = params.text_document_position_params.position;
+        let index = self.index(uri)?;
+
+        let location = match index.symbol_at(uri, position) {
+            Some(symbol) => symbol.declaration.clone(),
+            None => index.link_at(uri, position)?.clone(),
+        };
+
+        Some(GotoDefinitionResponse::Scalar(location))
+    }
+
+    fn references(&self, params: ReferenceParams) -> Option<Vec<lsp_types::Location>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let symbol = self.index(uri)?.symbol_at(uri, position)?;
+
+        let mut locations = Vec::new();
+        if params.context.include_declaration {
+            locations.push(symbol.declaration.clone());
+        }
+        locations.extend(symbol.references.iter().cloned());
+        locations.extend(symbol.assignments.iter().cloned());
+
+        Some(locations)
+    }
+
+    fn completion(&self, params: CompletionParams) -> Option<Vec<CompletionItem>> {
+        let uri = &params.text_document_position.text_document.uri;
+        let position = params.text_document_position.position;
+        let document = self.documents.get(uri)?;
+        let index = document.index.as_ref()?;
+
+        let line = document.text.lines().nth(position.line as usize).unwrap_or_default();
+        let (receiver, is_static) = member_receiver(line, position)?;
+
+        let circuit = if is_static {
+            index.circuits.get(receiver)?
+        } else if receiver == "self" {
+            index.enclosing_circuit(uri, position)?
+        } else {
+            let symbol = index.visible_symbol(receiver, uri, position)?;
+            index.circuits.get(symbol.circuit.as_ref()?)?
+        };
+
+        Some(
+            circuit
+                .members
+                .iter()
+                .filter(|member| (member.kind == MemberKind::Static) == is_static)
+                .map(|member| CompletionItem {
+                    label: member.name.clone(),
+                    kind: Some(match member.kind {
+                        MemberKind::Variable => CompletionItemKind::FIELD,
+                        MemberKind::Method => CompletionItemKind::METHOD,
+                        MemberKind::Static => CompletionItemKind::FUNCTION,
+                    }),
+                    detail: Some(member.detail.clone()),
+                    ..Default::default()
+                })
+                .collect(),
+        )
+    }
+}
+
+///
+/// Returns the identifier before the `.` or `::` preceding the position,
+/// and whether it was a static `::` access.
+///
+fn member_receiver(line: &str, position: Position) -> Option<(&str, bool)> {
+    let end = byte_offset(line, position.character);
+    let is_identifier = |c: char| c.is_ascii_alphanumeric() || c == '_';
+
+    // Skip the partially typed member name.
+    let prefix = line[..end].trim_end_matches(is_identifier);
+    let (prefix, is_static) = if let Some(prefix) = prefix.strip_suffix("::") {
+        (prefix, true)
+    } else {
+        (prefix.strip_suffix('.')?, false)
+    };
+
+    let receiver = &prefix[prefix.trim_end_matches(is_identifier).len()..];
+    if receiver.is_empty() {
+        None
+    } else {
+        Some((receiver, is_static))
+    }
+}
+
+fn invalid_params(id: RequestId, message: String) -> Response {
+    Response::new_err(id, ErrorCode::InvalidParams as i32, message)
+}
@@ -0,0 +1,281 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use leo_lsp::Server;
+
+use lsp_server::{Connection, Message, Notification, Request, RequestId};
+use lsp_types::{
+    CompletionItem, GotoDefinitionResponse, Hover, HoverContents, Location, Position, PublishDiagnosticsParams, Url,
+};
+use serde_json::{json, Value};
+use std::thread;
+
+const PROGRAM: &str = "circuit Point {
+    x: u32,
+    y: u32,
+
+    function new(x: u32, y: u32) -> Self {
+        return Self { x, y };
+    }
+
+    function sum(self) -> u32 {
+        return self.x + self.y;
+    }
+}
+
+function main(a: u32) -> u32 {
+    let p = Point::new(a, 2u32);
+    let total = p.sum();
+    total = total + a;
+    return total;
+}
+";
+
+/// A client talking to a server running on another thread.
+struct Client {
+    connection: Connection,
+    server: Option<thread::JoinHandle<()>>,
+    next_id: i32,
+}
+
+impl Client {
+    fn new() -> Self {
+        let (connection, server_connection) = Connection::memory();
+        let server = thread::spawn(move || {
+            let (server, _) = Server::initialize(&server_connection).unwrap();
+            server.run().unwrap();
+        });
+
+        let mut client = Self {
+            connection,
+            server: Some(server),
+            next_id: 0,
+        };
+        client.request("initialize", json!({ "capabilities": {} }));
+        client.notify("initialized", json!({}));
+        client
+    }
+
+    fn notify(&self, method: &str, params: Value) {
+        let notification = Notification::new(method.to_string(), params);
+        self.connection
+            .sender
+            .send(Message::Notification(notification))
+            .unwrap();
+    }
+
+    fn request(&mut self, method: &str, params: Value) -> Value {
+        self.next_id += 1;
+        let id = RequestId::from(self.next_id);
+        let request = Request::new(id.clone(), method.to_string(), params);
+        self.connection.sender.send(Message::Request(request)).unwrap();
+
+        loop {
+            match self.connection.receiver.recv().unwrap() {
+                Message::Response(response) if response.id == id => {
+                    assert!(response.error.is_none(), "{:?}", response.error);
+                    return response.result.unwrap_or(Value::Null);
+                }
+                _ => continue,
+            }
+        }
+    }
+
+    fn diagnostics(&self) -> PublishDiagnosticsParams {
+        loop {
+            if let Message::Notification(notification) = self.connection.receiver.recv().unwrap() {
+                if notification.method == "textDocument/publishDiagnostics" {
+                    return serde_json::from_value(notification.params).unwrap();
+                }
+            }
+        }
+    }
+
+    fn open(&self, uri: &Url, text: &str) -> PublishDiagnosticsParams {
+        self.notify(
+            "textDocument/didOpen",
+            json!({
+                "textDocument": { "uri": uri, "languageId": "leo", "version": 1, "text": text }
+            }),
+        );
+        self.diagnostics()
+    }
+
+    fn change(&self, uri: &Url, text: &str) -> PublishDiagnosticsParams {
+        self.notify(
+            "textDocument/didChange",
+            json!({
+                "textDocument": { "uri": uri, "version": 2 },
+                "contentChanges": [{ "text": text }]
+            }),
+        );
+        self.diagnostics()
+    }
+
+    fn position_request(&mut self, method: &str, uri: &Url, position: Position) -> Value {
+        self.request(
+            method,
+            json!({
+                "textDocument": { "uri": uri },
+                "position": position
+            }),
+        )
+    }
+
+    fn references(&mut self, uri: &Url, position: Position) -> Vec<Location> {
+        let references = self.request(
+            "textDocument/references",
+            json!({
+                "textDocument": { "uri": uri },
+                "position": position,
+                "context": { "includeDeclaration": true }
+            }),
+        );
+        serde_json::from_value(references).unwrap()
+    }
+}
+
+impl Drop for Client {
+    fn drop(&mut self) {
+        self.request("shutdown", Value::Null);
+        self.notify("exit", Value::Null);
+        if let Some(server) = self.server.take() {
+            server.join().unwrap();
+        }
+    }
+}
+
+fn document_uri() -> Url {
+    Url::from_file_path(std::env::temp_dir().join("leo-lsp-test").join("src").join("main.leo")).unwrap()
+}
+
+/// Returns the position of the `nth` occurrence of `needle` in the text, offset by `offset` characters.
+fn position_of(text: &str, needle: &str, nth: usize, offset: u32) -> Position {
+    let index = text.match_indices(needle).nth(nth).unwrap().0;
+    let line = text[..index].matches('\n').count();
+    let line_start = text[..index].rfind('\n').map(|i| i + 1).unwrap_or(0);
+    let column = text[line_start..index].encode_utf16().count();
+    Position::new(line as u32, column as u32 + offset)
+}
+
+fn hover_text(value: Value) -> String {
+    let hover: Hover = serde_json::from_value(value).unwrap();
+    match hover.contents {
+        HoverContents::Markup(markup) => markup.value,
+        _ => panic!("unexpected hover contents"),
+    }
+}
+
+#[test]
+fn test_diagnostics() {
+    let mut client = Client::new();
+    let uri = document_uri();
+
+    let diagnostics = client.open(&uri, PROGRAM);
+    assert!(diagnostics.diagnostics.is_empty(), "{:?}", diagnostics.diagnostics);
+
+    let broken = PROGRAM.replace("return total;", "return totl;");
+    let diagnostics = client.change(&uri, &broken);
+    assert_eq!(diagnostics.diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics.diagnostics[0].range.start,
+        position_of(&broken, "totl", 0, 0)
+    );
+
+    // Columns count UTF-16 code units, so text outside the basic plane takes two.
+    let broken = PROGRAM.replace("return total;", "/* \u{1F600} */ return totl;");
+    let diagnostics = client.change(&uri, &broken);
+    assert_eq!(diagnostics.diagnostics.len(), 1);
+    assert_eq!(
+        diagnostics.diagnostics[0].range.start,
+        position_of(&broken, "totl", 0, 0)
+    );
+    assert_eq!(diagnostics.diagnostics[0].range.start.character, 20);
+
+    // The last good index is kept while the buffer does not compile.
+    let hover = client.position_request("textDocument/hover", &uri, position_of(PROGRAM, "p.sum", 0, 0));
+    assert!(hover_text(hover).contains("Point"));
+}
+
+#[test]
+fn test_hover() {
+    let mut client = Client::new();
+    let uri = document_uri();
+    client.open(&uri, PROGRAM);
+
+    let hover = client.position_request("textDocument/hover", &uri, position_of(PROGRAM, "total", 0, 0));
+    assert_eq!(hover_text(hover), "```leo\nlet total: u32\n```");
+
+    let hover = client.position_request("textDocument/hover", &uri, position_of(PROGRAM, "p.sum()", 0, 2));
+    assert_eq!(hover_text(hover), "```leo\nu32\n```");
+}
+
+#[test]
+fn test_definition_and_references() {
+    let mut client = Client::new();
+    let uri = document_uri();
+    client.open(&uri, PROGRAM);
+
+    let definition = client.position_request("textDocument/definition", &uri, position_of(PROGRAM, "total", 2, 0));
+    let definition: GotoDefinitionResponse = serde_json::from_value(definition).unwrap();
+    match definition {
+        GotoDefinitionResponse::Scalar(location) => {
+            assert_eq!(location.uri, uri);
+            assert_eq!(location.range.start, position_of(PROGRAM, "total", 0, 0));
+        }
+        _ => panic!("unexpected definition response"),
+    }
+
+    let definition = client.position_request("textDocument/definition", &uri, position_of(PROGRAM, "sum()", 0, 0));
+    let definition: GotoDefinitionResponse = serde_json::from_value(definition).unwrap();
+    match definition {
+        GotoDefinitionResponse::Scalar(location) => {
+            assert_eq!(location.range.start, position_of(PROGRAM, "sum(self)", 0, 0));
+        }
+        _ => panic!("unexpected definition response"),
+    }
+
+    let references = client.references(&uri, position_of(PROGRAM, "total", 0, 0));
+    // The declaration, two reads, and one assignment.
+    assert_eq!(references.len(), 4);
+}
+
+#[test]
+fn test_completion() {
+    let mut client = Client::new();
+    let uri = document_uri();
+    client.open(&uri, PROGRAM);
+
+    let labels = |value: Value| {
+        let items: Vec<CompletionItem> = serde_json::from_value(value).unwrap();
+        let mut labels = items.into_iter().map(|item| item.label).collect::<Vec<_>>();
+        labels.sort();
+        labels
+    };
+
+    let completion = client.position_request("textDocument/completion", &uri, position_of(PROGRAM, "p.sum", 0, 2));
+    assert_eq!(labels(completion), vec!["sum", "x", "y"]);
+
+    let completion = client.position_request("textDocument/completion", &uri, position_of(PROGRAM, "self.x", 0, 5));
+    assert_eq!(labels(completion), vec!["sum", "x", "y"]);
+
+    let completion = client.position_request(
+        "textDocument/completion",
+        &uri,
+        position_of(PROGRAM, "Point::new", 0, 7),
+    );
+    assert_eq!(labels(completion), vec!["new"]);
+}
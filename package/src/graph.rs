@@ -0,0 +1,134 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Queries over the dependency graph recorded in a lock file.
+
+use crate::root::lock_file::{LockFile, Package};
+
+use indexmap::IndexMap;
+
+/// A package and the name it is imported under by its dependent.
+#[derive(Clone, Copy, Debug)]
+pub struct Edge<'a> {
+    pub import_name: &'a str,
+    pub package: &'a Package,
+}
+
+/// The dependency graph of a lock file, with packages looked up by their import path.
+pub struct DependencyGraph<'a> {
+    lock_file: &'a LockFile,
+    packages: IndexMap<String, &'a Package>,
+}
+
+impl<'a> DependencyGraph<'a> {
+    pub fn new(lock_file: &'a LockFile) -> Self {
+        Self {
+            lock_file,
+            packages: lock_file
+                .package
+                .iter()
+                .map(|package| (package.import_path(), package))
+                .collect(),
+        }
+    }
+
+    ///
+    /// Returns the direct dependencies of the package owning the lock file.
+    ///
+    /// Lock files written before these were recorded fall back to the packages
+    /// that no other package depends on.
+    ///
+    pub fn roots(&self) -> Vec<Edge<'a>> {
+        if !self.lock_file.dependencies.is_empty() {
+            return self.edges(&self.lock_file.dependencies);
+        }
+
+        self.lock_file
+            .package
+            .iter()
+            .filter(|package| {
+                let import_path = package.import_path();
+                !self
+                    .lock_file
+                    .package
+                    .iter()
+                    .any(|dependent| dependent.dependencies.values().any(|path| path == &import_path))
+            })
+            .map(|package| Edge {
+                import_name: package.import_name.as_deref().unwrap_or(&package.name),
+                package,
+            })
+            .collect()
+    }
+
+    /// Returns the direct dependencies of a package.
+    pub fn dependencies(&self, package: &'a Package) -> Vec<Edge<'a>> {
+        self.edges(&package.dependencies)
+    }
+
+    /// Returns the packages that are present in more than one version or from more than one source, by name.
+    pub fn duplicates(&self) -> IndexMap<&'a str, Vec<&'a Package>> {
+        let mut by_name = IndexMap::<&str, Vec<&Package>>::new();
+        for package in self.lock_file.package.iter() {
+            by_name.entry(&package.name).or_default().push(package);
+        }
+
+        by_name.retain(|_, packages| packages.len() > 1);
+        by_name
+    }
+
+    ///
+    /// Returns every chain of imports leading from the roots to a package with the given name.
+    ///
+    /// A package matches if either its name or the name it is imported under is `name`.
+    ///
+    pub fn why(&self, name: &str) -> Vec<Vec<Edge<'a>>> {
+        let mut chains = Vec::new();
+        for root in self.roots() {
+            self.collect_chains(name, &mut vec![root], &mut chains);
+        }
+        chains
+    }
+
+    fn collect_chains(&self, name: &str, chain: &mut Vec<Edge<'a>>, chains: &mut Vec<Vec<Edge<'a>>>) {
+        let edge = chain[chain.len() - 1];
+        if edge.import_name == name || edge.package.name == name {
+            chains.push(chain.clone());
+        }
+
+        for dependency in self.dependencies(edge.package) {
+            // The resolver rejects cycles, but a lock file may have been edited by hand.
+            if chain.iter().any(|edge| std::ptr::eq(edge.package, dependency.package)) {
+                continue;
+            }
+
+            chain.push(dependency);
+            self.collect_chains(name, chain, chains);
+            chain.pop();
+        }
+    }
+
+    fn edges(&self, dependencies: &'a IndexMap<String, String>) -> Vec<Edge<'a>> {
+        dependencies
+            .iter()
+            .filter_map(|(import_name, import_path)| {
+                self.packages
+                    .get(import_path)
+                    .map(|&package| Edge { import_name, package })
+            })
+            .collect()
+    }
+}
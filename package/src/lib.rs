@@ -16,10 +16,12 @@
 
 #![doc = include_str!("../README.md")]
 
+pub mod graph;
 pub mod imports;
 pub mod inputs;
 pub mod outputs;
 pub mod package;
+pub mod resolver;
 pub mod root;
 pub mod source;
 
@@ -35,6 +37,11 @@ impl LeoPackage {
         package::Package::initialize(package_name, path, author)
     }
 
+    /// Initializes a Leo library package at the given path.
+    pub fn initialize_library(package_name: &str, path: &Path, author: Option<String>) -> Result<()> {
+        package::Package::initialize_library(package_name, path, author)
+    }
+
     /// Returns `true` if the given Leo package name is valid.
     pub fn is_package_name_valid(package_name: &str) -> bool {
         package::Package::is_package_name_valid(package_name)
@@ -25,21 +25,37 @@ use std::{
     fs::{
         File, {self},
     },
-    io::Write,
+    io::{BufReader, BufWriter, Write},
     path::Path,
 };
 
 pub static CIRCUIT_FILE_EXTENSION: &str = ".json";
+pub static BINARY_CIRCUIT_FILE_EXTENSION: &str = ".circuit";
+
+/// The encoding of a serialized circuit file.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum CircuitFormat {
+    #[default]
+    Json,
+    Binary,
+}
 
 #[derive(Deserialize)]
 pub struct CircuitFile {
     pub package_name: String,
+    #[serde(skip)]
+    pub format: CircuitFormat,
 }
 
 impl CircuitFile {
     pub fn new(package_name: &str) -> Self {
+        Self::with_format(package_name, CircuitFormat::Json)
+    }
+
+    pub fn with_format(package_name: &str, format: CircuitFormat) -> Self {
         Self {
             package_name: package_name.to_string(),
+            format,
         }
     }
 
@@ -67,6 +83,22 @@ impl CircuitFile {
         Ok(())
     }
 
+    /// Opens the serialized circuit at the given file path for streaming reads.
+    pub fn open(&self, path: &Path) -> Result<BufReader<File>> {
+        let path = self.setup_file_path(path);
+
+        let file = File::open(&path).map_err(|_| PackageError::failed_to_read_circuit_file(path.into_owned()))?;
+        Ok(BufReader::new(file))
+    }
+
+    /// Creates the serialized circuit file for streaming writes.
+    pub fn create(&self, path: &Path) -> Result<BufWriter<File>> {
+        let path = self.setup_file_path(path);
+
+        let file = File::create(&path).map_err(PackageError::io_error_circuit_file)?;
+        Ok(BufWriter::new(file))
+    }
+
     /// Removes the serialized circuit at the given path if it exists. Returns `true` on success,
     /// `false` if the file doesn't exist, and `Error` if the file system fails during operation.
     pub fn remove(&self, path: &Path) -> Result<bool> {
@@ -85,8 +117,11 @@ impl CircuitFile {
             if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
                 path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
             }
-            path.to_mut()
-                .push(format!("{}{}", self.package_name, CIRCUIT_FILE_EXTENSION));
+            let extension = match self.format {
+                CircuitFormat::Json => CIRCUIT_FILE_EXTENSION,
+                CircuitFormat::Binary => BINARY_CIRCUIT_FILE_EXTENSION,
+            };
+            path.to_mut().push(format!("{}{}", self.package_name, extension));
         }
         path
     }
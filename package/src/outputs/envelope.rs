@@ -0,0 +1,135 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The envelope of the key and proof files, naming the circuit they were produced for.
+
+use crate::root::{Curve, ProvingSystem, Target};
+use leo_errors::{PackageError, Result};
+
+use serde::{Deserialize, Serialize};
+use std::{convert::TryInto, path::Path};
+
+/// The bytes that key and proof files in an envelope start with.
+pub static ENVELOPE_MAGIC: &[u8; 4] = b"LEO\0";
+
+/// The version of the envelope written by this version of Leo.
+pub const ENVELOPE_VERSION: u32 = 1;
+
+///
+/// The header of a key or proof file.
+///
+/// A file is the magic bytes, the length of the header as a little-endian `u32`, the header
+/// as JSON, and then the key or proof as snarkVM writes it.
+///
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Envelope {
+    pub version: u32,
+    pub package_name: String,
+    pub package_version: String,
+    pub curve: Curve,
+    pub proving_system: ProvingSystem,
+    /// The hash of the `SummarizedCircuit`, as reported by `leo build`.
+    pub circuit_hash: String,
+    pub num_public_inputs: usize,
+}
+
+impl Envelope {
+    pub fn new(
+        package_name: &str,
+        package_version: &str,
+        target: Target,
+        circuit_hash: &str,
+        num_public_inputs: usize,
+    ) -> Self {
+        Self {
+            version: ENVELOPE_VERSION,
+            package_name: package_name.to_string(),
+            package_version: package_version.to_string(),
+            curve: target.curve,
+            proving_system: target.proving_system,
+            circuit_hash: circuit_hash.to_string(),
+            num_public_inputs,
+        }
+    }
+
+    /// Returns the file contents of the given key or proof in this envelope.
+    pub fn seal(&self, contents: &[u8]) -> Result<Vec<u8>> {
+        let header = serde_json::to_vec(self).map_err(PackageError::io_error_envelope)?;
+
+        let mut bytes = Vec::with_capacity(ENVELOPE_MAGIC.len() + 4 + header.len() + contents.len());
+        bytes.extend_from_slice(ENVELOPE_MAGIC);
+        bytes.extend_from_slice(&(header.len() as u32).to_le_bytes());
+        bytes.extend_from_slice(&header);
+        bytes.extend_from_slice(contents);
+        Ok(bytes)
+    }
+
+    /// Splits the contents of the file at the given path into its envelope and the key or proof.
+    pub fn open<'a>(bytes: &'a [u8], path: &Path) -> Result<(Self, &'a [u8])> {
+        let bytes = match bytes.strip_prefix(&ENVELOPE_MAGIC[..]) {
+            Some(bytes) if bytes.len() >= 4 => bytes,
+            _ => return Err(PackageError::missing_envelope(path).into()),
+        };
+        let (length, bytes) = bytes.split_at(4);
+        let length = u32::from_le_bytes(length.try_into().unwrap()) as usize;
+        if bytes.len() < length {
+            return Err(PackageError::missing_envelope(path).into());
+        }
+        let (header, contents) = bytes.split_at(length);
+
+        // Check the version alone first, so newer headers are reported as such rather than as malformed
+        #[derive(Deserialize)]
+        struct Version {
+            version: u32,
+        }
+        let version = serde_json::from_slice::<Version>(header)
+            .map_err(|error| PackageError::failed_to_parse_envelope(path, error))?
+            .version;
+        if version != ENVELOPE_VERSION {
+            return Err(PackageError::unsupported_envelope_version(path, version, ENVELOPE_VERSION).into());
+        }
+
+        let envelope =
+            serde_json::from_slice(header).map_err(|error| PackageError::failed_to_parse_envelope(path, error))?;
+        Ok((envelope, contents))
+    }
+
+    /// Checks that the file at the given path, in this envelope, was produced for the expected circuit.
+    pub fn check(&self, expected: &Envelope, path: &Path) -> Result<()> {
+        let fields = [
+            ("package name", &self.package_name, &expected.package_name),
+            ("package version", &self.package_version, &expected.package_version),
+            ("curve", &self.curve.to_string(), &expected.curve.to_string()),
+            (
+                "proving system",
+                &self.proving_system.to_string(),
+                &expected.proving_system.to_string(),
+            ),
+            ("circuit hash", &self.circuit_hash, &expected.circuit_hash),
+            (
+                "public input count",
+                &self.num_public_inputs.to_string(),
+                &expected.num_public_inputs.to_string(),
+            ),
+        ];
+        for (field, found, expected) in fields {
+            if found != expected {
+                return Err(PackageError::envelope_mismatch(path, field, expected, found).into());
+            }
+        }
+        Ok(())
+    }
+}
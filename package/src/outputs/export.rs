@@ -0,0 +1,111 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The circuit files exported for external tooling.
+
+use crate::outputs::OUTPUTS_DIRECTORY_NAME;
+use leo_errors::{PackageError, Result};
+
+use serde::Deserialize;
+use std::{
+    borrow::Cow,
+    fs::{
+        File, {self},
+    },
+    io::BufWriter,
+    path::Path,
+};
+
+pub static R1CS_FILE_EXTENSION: &str = ".r1cs";
+pub static WTNS_FILE_EXTENSION: &str = ".wtns";
+pub static ZKIF_FILE_EXTENSION: &str = ".zkif";
+
+/// An interchange format the circuit can be exported to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// The circom constraints.
+    #[default]
+    R1cs,
+    /// The circom witness.
+    Wtns,
+    /// The zkInterface messages.
+    Zkif,
+}
+
+impl ExportFormat {
+    pub const ALL: [ExportFormat; 3] = [ExportFormat::R1cs, ExportFormat::Wtns, ExportFormat::Zkif];
+
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::R1cs => R1CS_FILE_EXTENSION,
+            ExportFormat::Wtns => WTNS_FILE_EXTENSION,
+            ExportFormat::Zkif => ZKIF_FILE_EXTENSION,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+pub struct ExportFile {
+    pub package_name: String,
+    #[serde(skip)]
+    pub format: ExportFormat,
+}
+
+impl ExportFile {
+    pub fn new(package_name: &str, format: ExportFormat) -> Self {
+        Self {
+            package_name: package_name.to_string(),
+            format,
+        }
+    }
+
+    pub fn exists_at(&self, path: &Path) -> bool {
+        let path = self.setup_file_path(path);
+        path.exists()
+    }
+
+    /// Creates the exported circuit file for streaming writes.
+    pub fn create(&self, path: &Path) -> Result<BufWriter<File>> {
+        let path = self.setup_file_path(path);
+
+        let file = File::create(&path).map_err(PackageError::io_error_export_file)?;
+        Ok(BufWriter::new(file))
+    }
+
+    /// Removes the exported circuit at the given path if it exists. Returns `true` on success,
+    /// `false` if the file doesn't exist, and `Error` if the file system fails during operation.
+    pub fn remove(&self, path: &Path) -> Result<bool> {
+        let path = self.setup_file_path(path);
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        fs::remove_file(&path).map_err(|_| PackageError::failed_to_remove_export_file(path))?;
+        Ok(true)
+    }
+
+    fn setup_file_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        let mut path = Cow::from(path);
+        if path.is_dir() {
+            if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
+                path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
+            }
+            path.to_mut()
+                .push(format!("{}{}", self.package_name, self.format.extension()));
+        }
+        path
+    }
+}
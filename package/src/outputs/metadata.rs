@@ -0,0 +1,147 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The metadata file, recording how the artifacts in the outputs directory were produced.
+
+use crate::outputs::OUTPUTS_DIRECTORY_NAME;
+use leo_errors::{PackageError, Result};
+
+use serde::{Deserialize, Serialize};
+use std::{
+    borrow::Cow,
+    fs::{
+        File, {self},
+    },
+    io::Write,
+    path::Path,
+};
+
+pub static METADATA_FILE_EXTENSION: &str = ".meta";
+
+/// How an artifact was produced.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Provenance {
+    /// Whether the artifact was produced from a fixed seed. Such artifacts are reproducible,
+    /// and insecure, as anyone who knows the seed can forge proofs.
+    pub deterministic: bool,
+    /// The seed the artifact was produced from, if it is deterministic.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Whether the artifact was imported rather than produced by Leo, such as keys from a
+    /// multi-party ceremony.
+    #[serde(default)]
+    pub imported: bool,
+}
+
+impl Provenance {
+    /// Returns the provenance of an artifact produced from the given seed, or from fresh randomness.
+    pub fn from_seed(seed: Option<u64>) -> Self {
+        Self {
+            deterministic: seed.is_some(),
+            seed,
+            imported: false,
+        }
+    }
+
+    /// Returns the provenance of an imported artifact.
+    pub fn imported() -> Self {
+        Self {
+            deterministic: false,
+            seed: None,
+            imported: true,
+        }
+    }
+}
+
+/// The provenance of the keys, universal SRS and proof of a package.
+///
+/// Artifacts without metadata, such as those written by older versions of Leo, were produced
+/// from fresh randomness.
+#[derive(Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Metadata {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub keys: Option<Provenance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub universal_srs: Option<Provenance>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub proof: Option<Provenance>,
+}
+
+#[derive(Deserialize)]
+pub struct MetadataFile {
+    pub package_name: String,
+}
+
+impl MetadataFile {
+    pub fn new(package_name: &str) -> Self {
+        Self {
+            package_name: package_name.to_string(),
+        }
+    }
+
+    pub fn exists_at(&self, path: &Path) -> bool {
+        let path = self.setup_file_path(path);
+        path.exists()
+    }
+
+    /// Reads the metadata from the given file path, or returns empty metadata if there is none.
+    pub fn read_from(&self, path: &Path) -> Result<Metadata> {
+        let path = self.setup_file_path(path);
+        if !path.exists() {
+            return Ok(Metadata::default());
+        }
+
+        let string = fs::read_to_string(&path).map_err(|_| PackageError::failed_to_read_metadata_file(path.clone()))?;
+        let metadata =
+            serde_json::from_str(&string).map_err(|error| PackageError::failed_to_parse_metadata_file(path, error))?;
+        Ok(metadata)
+    }
+
+    /// Writes the given metadata to a file.
+    pub fn write_to(&self, path: &Path, metadata: &Metadata) -> Result<()> {
+        let path = self.setup_file_path(path);
+        let string = serde_json::to_string_pretty(metadata).map_err(PackageError::io_error_metadata_file)?;
+        let mut file = File::create(&path).map_err(PackageError::io_error_metadata_file)?;
+
+        file.write_all(string.as_bytes())
+            .map_err(PackageError::io_error_metadata_file)?;
+        Ok(())
+    }
+
+    /// Removes the metadata at the given path if it exists. Returns `true` on success,
+    /// `false` if the file doesn't exist, and `Error` if the file system fails during operation.
+    pub fn remove(&self, path: &Path) -> Result<bool> {
+        let path = self.setup_file_path(path);
+        if !path.exists() {
+            return Ok(false);
+        }
+
+        fs::remove_file(&path).map_err(|_| PackageError::failed_to_remove_metadata_file(path))?;
+        Ok(true)
+    }
+
+    fn setup_file_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        let mut path = Cow::from(path);
+        if path.is_dir() {
+            if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
+                path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
+            }
+            path.to_mut()
+                .push(format!("{}{}", self.package_name, METADATA_FILE_EXTENSION));
+        }
+        path
+    }
+}
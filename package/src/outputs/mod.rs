@@ -26,11 +26,29 @@ pub use self::checksum::*;
 pub mod directory;
 pub use directory::*;
 
+pub mod envelope;
+pub use self::envelope::*;
+
+pub mod export;
+pub use self::export::*;
+
+pub mod metadata;
+pub use self::metadata::*;
+
+pub mod profile;
+pub use self::profile::*;
+
 pub mod proof;
 pub use self::proof::*;
 
 pub mod proving_key;
 pub use self::proving_key::*;
 
+pub mod universal_srs;
+pub use self::universal_srs::*;
+
 pub mod verification_key;
 pub use self::verification_key::*;
+
+pub mod witness;
+pub use self::witness::*;
@@ -16,7 +16,7 @@
 
 //! The proof file.
 
-use crate::outputs::OUTPUTS_DIRECTORY_NAME;
+use crate::outputs::{Envelope, OUTPUTS_DIRECTORY_NAME};
 use leo_errors::{PackageError, Result};
 
 use serde::Deserialize;
@@ -43,26 +43,31 @@ impl ProofFile {
         }
     }
 
+    pub fn full_path<'a>(&self, path: &'a Path) -> Cow<'a, Path> {
+        self.setup_file_path(path)
+    }
+
     pub fn exists_at(&self, path: &Path) -> bool {
         let path = self.setup_file_path(path);
         path.exists()
     }
 
-    /// Reads the proof from the given file path if it exists.
-    pub fn read_from(&self, path: &Path) -> Result<String> {
+    /// Reads the proof and its envelope from the given file path if it exists.
+    pub fn read_from(&self, path: &Path) -> Result<(Envelope, Vec<u8>)> {
         let path = self.setup_file_path(path);
 
-        let string =
-            fs::read_to_string(&path).map_err(|_| PackageError::failed_to_read_proof_file(path.into_owned()))?;
-        Ok(string)
+        let bytes = fs::read(&path).map_err(|_| PackageError::failed_to_read_proof_file(path.clone()))?;
+        let (envelope, proof) = Envelope::open(&bytes, &path)?;
+        Ok((envelope, proof.to_vec()))
     }
 
-    /// Writes the given proof to a file.
-    pub fn write_to(&self, path: &Path, proof: &[u8]) -> Result<()> {
+    /// Writes the given proof to a file, in the given envelope.
+    pub fn write_to(&self, path: &Path, envelope: &Envelope, proof: &[u8]) -> Result<()> {
         let path = self.setup_file_path(path);
         let mut file = File::create(&path).map_err(PackageError::io_error_proof_file)?;
 
-        file.write_all(proof).map_err(PackageError::io_error_proof_file)?;
+        file.write_all(&envelope.seal(proof)?)
+            .map_err(PackageError::io_error_proof_file)?;
         tracing::info!("Saving proof... ({:?})", path);
 
         Ok(())
@@ -16,7 +16,10 @@
 
 //! The proving key file.
 
-use crate::outputs::OUTPUTS_DIRECTORY_NAME;
+use crate::{
+    outputs::{Envelope, OUTPUTS_DIRECTORY_NAME},
+    root::ProvingSystem,
+};
 use leo_errors::{PackageError, Result};
 
 use serde::Deserialize;
@@ -30,16 +33,24 @@ use std::{
 };
 
 pub static PROVING_KEY_FILE_EXTENSION: &str = ".lpk";
+pub static MARLIN_PROVING_KEY_FILE_EXTENSION: &str = ".mpk";
 
 #[derive(Deserialize)]
 pub struct ProvingKeyFile {
     pub package_name: String,
+    #[serde(skip)]
+    pub proving_system: ProvingSystem,
 }
 
 impl ProvingKeyFile {
     pub fn new(package_name: &str) -> Self {
+        Self::with_proving_system(package_name, ProvingSystem::Groth16)
+    }
+
+    pub fn with_proving_system(package_name: &str, proving_system: ProvingSystem) -> Self {
         Self {
             package_name: package_name.to_string(),
+            proving_system,
         }
     }
 
@@ -52,20 +63,21 @@ impl ProvingKeyFile {
         path.exists()
     }
 
-    /// Reads the proving key from the given file path if it exists.
-    pub fn read_from(&self, path: &Path) -> Result<Vec<u8>> {
+    /// Reads the proving key and its envelope from the given file path if it exists.
+    pub fn read_from(&self, path: &Path) -> Result<(Envelope, Vec<u8>)> {
         let path = self.setup_file_path(path);
 
-        let bytes = fs::read(&path).map_err(|_| PackageError::failed_to_read_proving_key_file(path))?;
-        Ok(bytes)
+        let bytes = fs::read(&path).map_err(|_| PackageError::failed_to_read_proving_key_file(path.clone()))?;
+        let (envelope, proving_key) = Envelope::open(&bytes, &path)?;
+        Ok((envelope, proving_key.to_vec()))
     }
 
-    /// Writes the given proving key to a file.
-    pub fn write_to<'a>(&self, path: &'a Path, proving_key: &[u8]) -> Result<Cow<'a, Path>> {
+    /// Writes the given proving key to a file, in the given envelope.
+    pub fn write_to<'a>(&self, path: &'a Path, envelope: &Envelope, proving_key: &[u8]) -> Result<Cow<'a, Path>> {
         let path = self.setup_file_path(path);
         let mut file = File::create(&path).map_err(PackageError::io_error_proving_key_file)?;
 
-        file.write_all(proving_key)
+        file.write_all(&envelope.seal(proving_key)?)
             .map_err(PackageError::io_error_proving_key_file)?;
         Ok(path)
     }
@@ -88,8 +100,11 @@ impl ProvingKeyFile {
             if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
                 path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
             }
-            path.to_mut()
-                .push(format!("{}{}", self.package_name, PROVING_KEY_FILE_EXTENSION));
+            let extension = match self.proving_system {
+                ProvingSystem::Groth16 => PROVING_KEY_FILE_EXTENSION,
+                ProvingSystem::Marlin => MARLIN_PROVING_KEY_FILE_EXTENSION,
+            };
+            path.to_mut().push(format!("{}{}", self.package_name, extension));
         }
         path
     }
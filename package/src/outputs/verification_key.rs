@@ -16,7 +16,10 @@
 
 //! The verification key file.
 
-use crate::outputs::OUTPUTS_DIRECTORY_NAME;
+use crate::{
+    outputs::{Envelope, OUTPUTS_DIRECTORY_NAME},
+    root::ProvingSystem,
+};
 use leo_errors::{PackageError, Result};
 
 use serde::Deserialize;
@@ -30,16 +33,24 @@ use std::{
 };
 
 pub static VERIFICATION_KEY_FILE_EXTENSION: &str = ".lvk";
+pub static MARLIN_VERIFICATION_KEY_FILE_EXTENSION: &str = ".mvk";
 
 #[derive(Deserialize)]
 pub struct VerificationKeyFile {
     pub package_name: String,
+    #[serde(skip)]
+    pub proving_system: ProvingSystem,
 }
 
 impl VerificationKeyFile {
     pub fn new(package_name: &str) -> Self {
+        Self::with_proving_system(package_name, ProvingSystem::Groth16)
+    }
+
+    pub fn with_proving_system(package_name: &str, proving_system: ProvingSystem) -> Self {
         Self {
             package_name: package_name.to_string(),
+            proving_system,
         }
     }
 
@@ -52,21 +63,21 @@ impl VerificationKeyFile {
         path.exists()
     }
 
-    /// Reads the verification key from the given file path if it exists.
-    pub fn read_from(&self, path: &Path) -> Result<Vec<u8>> {
+    /// Reads the verification key and its envelope from the given file path if it exists.
+    pub fn read_from(&self, path: &Path) -> Result<(Envelope, Vec<u8>)> {
         let path = self.setup_file_path(path);
 
-        let bytes =
-            fs::read(&path).map_err(|_| PackageError::failed_to_read_verification_key_file(path.into_owned()))?;
-        Ok(bytes)
+        let bytes = fs::read(&path).map_err(|_| PackageError::failed_to_read_verification_key_file(path.clone()))?;
+        let (envelope, verification_key) = Envelope::open(&bytes, &path)?;
+        Ok((envelope, verification_key.to_vec()))
     }
 
-    /// Writes the given verification key to a file.
-    pub fn write_to<'a>(&self, path: &'a Path, verification_key: &[u8]) -> Result<Cow<'a, Path>> {
+    /// Writes the given verification key to a file, in the given envelope.
+    pub fn write_to<'a>(&self, path: &'a Path, envelope: &Envelope, verification_key: &[u8]) -> Result<Cow<'a, Path>> {
         let path = self.setup_file_path(path);
         let mut file = File::create(&path).map_err(PackageError::io_error_verification_key_file)?;
 
-        file.write_all(verification_key)
+        file.write_all(&envelope.seal(verification_key)?)
             .map_err(PackageError::io_error_verification_key_file)?;
         Ok(path)
     }
@@ -89,8 +100,11 @@ impl VerificationKeyFile {
             if !path.ends_with(OUTPUTS_DIRECTORY_NAME) {
                 path.to_mut().push(OUTPUTS_DIRECTORY_NAME);
             }
-            path.to_mut()
-                .push(format!("{}{}", self.package_name, VERIFICATION_KEY_FILE_EXTENSION));
+            let extension = match self.proving_system {
+                ProvingSystem::Groth16 => VERIFICATION_KEY_FILE_EXTENSION,
+                ProvingSystem::Marlin => MARLIN_VERIFICATION_KEY_FILE_EXTENSION,
+            };
+            path.to_mut().push(format!("{}{}", self.package_name, extension));
         }
         path
     }
@@ -18,7 +18,7 @@ use crate::{
     imports::ImportsDirectory,
     inputs::{InputFile, InputsDirectory, StateFile},
     root::{Gitignore, Manifest, README},
-    source::{MainFile, SourceDirectory},
+    source::{LibFile, MainFile, SourceDirectory},
 };
 
 use leo_errors::{PackageError, Result};
@@ -32,6 +32,10 @@ pub struct Package {
     pub version: String,
     pub description: Option<String>,
     pub license: Option<String>,
+    /// Globs selecting the files to publish, instead of the sources, inputs and readme.
+    pub include: Option<Vec<String>>,
+    /// Globs of files that are never published.
+    pub exclude: Option<Vec<String>>,
 }
 
 impl Package {
@@ -46,6 +50,8 @@ impl Package {
             version: "0.1.0".to_owned(),
             description: None,
             license: None,
+            include: None,
+            exclude: None,
         })
     }
 
@@ -143,6 +149,12 @@ impl Package {
             result = false;
         }
 
+        // Check if the lib file already exists.
+        if LibFile::exists_at(path) {
+            existing_files.push(LibFile::filename());
+            result = false;
+        }
+
         if !existing_files.is_empty() {
             tracing::error!("File(s) {:?} already exist", existing_files);
         }
@@ -182,6 +194,26 @@ impl Package {
         true
     }
 
+    /// Returns `true` if a library package is initialized at the given path
+    pub fn is_library_initialized(package_name: &str, path: &Path) -> bool {
+        // Check that the package name is valid.
+        if !Self::is_package_name_valid(package_name) {
+            return false;
+        }
+
+        // Check if the manifest file exists.
+        if !Manifest::exists_at(path) {
+            return false;
+        }
+
+        // Check if the lib file exists.
+        if !LibFile::exists_at(path) {
+            return false;
+        }
+
+        true
+    }
+
     /// Creates a package at the given path
     pub fn initialize(package_name: &str, path: &Path, author: Option<String>) -> Result<()> {
         // First, verify that this directory is not already initialized as a Leo package.
@@ -232,6 +264,47 @@ impl Package {
         Ok(())
     }
 
+    /// Creates a library package at the given path, with a lib file and no inputs
+    pub fn initialize_library(package_name: &str, path: &Path, author: Option<String>) -> Result<()> {
+        // First, verify that this directory is not already initialized as a Leo package.
+        {
+            if !Self::can_initialize(package_name, path) {
+                return Err(PackageError::failed_to_initialize_package(package_name, path.as_os_str()).into());
+            }
+        }
+        // Next, initialize this directory as a Leo library package.
+        {
+            // Create the manifest file.
+            Manifest::new(package_name, author)?.write_to(path)?;
+
+            // Verify that the .gitignore file does not exist.
+            if !Gitignore::exists_at(path) {
+                // Create the .gitignore file.
+                Gitignore::new().write_to(path)?;
+            }
+
+            // Verify that the README.md file does not exist.
+            if !README::exists_at(path) {
+                // Create the README.md file.
+                README::new(package_name).write_to(path)?;
+            }
+
+            // Create the source directory.
+            SourceDirectory::create(path)?;
+
+            // Create the lib file in the source directory.
+            LibFile::new(package_name).write_to(path)?;
+        }
+        // Next, verify that a valid Leo library package has been initialized in this directory
+        {
+            if !Self::is_library_initialized(package_name, path) {
+                return Err(PackageError::failed_to_initialize_package(package_name, path.as_os_str()).into());
+            }
+        }
+
+        Ok(())
+    }
+
     /// Removes the package at the given path
     pub fn remove_imported_package(package_name: &str, path: &Path) -> Result<()> {
         ImportsDirectory::remove_import(path, package_name)
@@ -0,0 +1,347 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! Resolves the dependencies of a package to a single version of each registry package.
+
+use crate::root::{
+    lock_file::{package_checksum, LockFile, Package, Source},
+    Dependency, Manifest, AUTHOR_PLACEHOLDER,
+};
+use leo_errors::{PackageError, Result};
+
+use indexmap::IndexMap;
+use semver::{Op, Version, VersionReq};
+use std::{
+    convert::TryFrom,
+    path::{Path, PathBuf},
+};
+
+/// The number of times the dependency graph is walked before a version conflict is reported.
+const MAX_PASSES: usize = 32;
+
+/// A dependency pinned to exactly one package.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Pinned {
+    /// A published version of a registry package.
+    Registry {
+        author: String,
+        package: String,
+        version: Version,
+    },
+    /// A package in a local directory.
+    Path { path: PathBuf },
+    /// A package in a git repository, at the requested revision or the default branch.
+    Git { git: String, rev: Option<String> },
+}
+
+/// A package made available on disk by a [`PackageIndex`].
+#[derive(Clone, Debug)]
+pub struct Fetched {
+    /// The directory holding the manifest and the sources of the package.
+    pub path: PathBuf,
+    /// The origin recorded in the lock file, `None` for registry packages.
+    pub source: Option<Source>,
+}
+
+/// The packages available to the resolver.
+pub trait PackageIndex {
+    /// Returns the published versions of a registry package.
+    fn versions(&mut self, author: &str, package: &str) -> Result<Vec<Version>>;
+
+    /// Makes a package available on disk, downloading or cloning it if needed.
+    fn fetch(&mut self, import_name: &str, package: &Pinned) -> Result<Fetched>;
+}
+
+/// A version requirement and the chain of imports that introduced it.
+#[derive(Clone, Debug)]
+struct Requirement {
+    requirement: VersionReq,
+    text: String,
+    path: Vec<String>,
+}
+
+/// The packages and requirements found during a walk of the dependency graph.
+#[derive(Default)]
+struct Graph {
+    requirements: IndexMap<(String, String), Vec<Requirement>>,
+    packages: IndexMap<Pinned, Package>,
+}
+
+///
+/// Picks one version of every registry package that satisfies all of its dependents.
+///
+/// Path and git dependencies are used as they are, but their own dependencies take part in
+/// the resolution. Versions recorded in a previous lock file are kept while they still satisfy
+/// the requirements, otherwise the highest matching version is selected. Git dependencies
+/// without a revision stay at the commit recorded in a previous lock file.
+///
+pub struct Resolver<'a, I: PackageIndex> {
+    index: &'a mut I,
+    locked: IndexMap<(String, String), Version>,
+    /// The locked commits of git dependencies, by import name and repository.
+    locked_revs: IndexMap<(String, String), String>,
+    selected: IndexMap<(String, String), Version>,
+}
+
+impl<'a, I: PackageIndex> Resolver<'a, I> {
+    pub fn new(index: &'a mut I) -> Self {
+        Self {
+            index,
+            locked: IndexMap::new(),
+            locked_revs: IndexMap::new(),
+            selected: IndexMap::new(),
+        }
+    }
+
+    /// Prefers the registry versions and git commits recorded in the given lock file.
+    pub fn with_lock_file(mut self, lock_file: &LockFile) -> Self {
+        for package in lock_file.package.iter() {
+            match &package.source {
+                None => {
+                    if let Ok(version) = Version::parse(&package.version) {
+                        self.locked
+                            .insert((package.author.clone(), package.name.clone()), version);
+                    }
+                }
+                Some(Source::Git { git, rev }) => {
+                    if let Some(import_name) = &package.import_name {
+                        self.locked_revs.insert((import_name.clone(), git.clone()), rev.clone());
+                    }
+                }
+                Some(Source::Path { .. }) => {}
+            }
+        }
+        self
+    }
+
+    ///
+    /// Resolves the dependencies of the package `name` in the directory `path` into a lock file.
+    ///
+    pub fn resolve(mut self, name: &str, path: &Path, dependencies: &IndexMap<String, Dependency>) -> Result<LockFile> {
+        let mut changed = None;
+
+        for _ in 0..MAX_PASSES {
+            let mut graph = Graph::default();
+            let imports = self.walk(&mut graph, &[name.to_string()], &[], path, dependencies)?;
+
+            // A selection made early in the walk may not satisfy requirements found later on.
+            changed = None;
+            for (key, requirements) in graph.requirements.iter() {
+                if !matches_all(&self.selected[key], requirements) {
+                    let version = self.select(key, requirements)?;
+                    self.selected.insert(key.clone(), version);
+                    changed = Some((key.clone(), requirements.clone()));
+                }
+            }
+
+            if changed.is_none() {
+                return Ok(LockFile {
+                    dependencies: imports,
+                    package: graph.packages.into_iter().map(|(_, package)| package).collect(),
+                });
+            }
+        }
+
+        // The selections did not settle, report the last package that kept changing.
+        let ((author, package), requirements) = changed.unwrap();
+        Err(conflict(&author, &package, &requirements))
+    }
+
+    /// Adds the dependencies of the package at `directory` to the graph.
+    /// Returns the import paths of the dependencies by import name.
+    fn walk(
+        &mut self,
+        graph: &mut Graph,
+        path: &[String],
+        stack: &[Pinned],
+        directory: &Path,
+        dependencies: &IndexMap<String, Dependency>,
+    ) -> Result<IndexMap<String, String>> {
+        let mut imports = IndexMap::new();
+
+        for (import_name, dependency) in dependencies.iter() {
+            let mut import_path = path.to_vec();
+            import_path.push(import_name.clone());
+
+            let pinned = match dependency {
+                Dependency::Registry {
+                    author,
+                    version,
+                    package,
+                } => {
+                    let requirement = parse_requirement(package, version)?;
+                    let key = (author.clone(), package.clone());
+                    let requirements = graph.requirements.entry(key.clone()).or_default();
+                    requirements.push(Requirement {
+                        requirement,
+                        text: version.clone(),
+                        path: import_path.clone(),
+                    });
+
+                    let version = match self.selected.get(&key) {
+                        Some(version) => version.clone(),
+                        None => {
+                            let version = self.select(&key, requirements)?;
+                            self.selected.insert(key, version.clone());
+                            version
+                        }
+                    };
+
+                    Pinned::Registry {
+                        author: author.clone(),
+                        package: package.clone(),
+                        version,
+                    }
+                }
+                Dependency::Path { path } => {
+                    let path = directory.join(path);
+                    Pinned::Path {
+                        path: path.canonicalize().unwrap_or(path),
+                    }
+                }
+                Dependency::Git { git, rev } => Pinned::Git {
+                    git: git.clone(),
+                    rev: rev
+                        .clone()
+                        .or_else(|| self.locked_revs.get(&(import_name.clone(), git.clone())).cloned()),
+                },
+            };
+
+            if stack.contains(&pinned) {
+                // Pretty format for the message - show dependency structure.
+                let message = import_path
+                    .iter()
+                    .enumerate()
+                    .map(|(i, val)| format!("{}└─{}", " ".repeat(i * 2), val))
+                    .collect::<Vec<_>>()
+                    .join("\n");
+
+                return Err(PackageError::recursive_dependency_found(format!("{} (FAILURE)", message)).into());
+            }
+
+            // Packages shared by several dependents are only added once.
+            if let Some(package) = graph.packages.get(&pinned) {
+                imports.insert(import_name.clone(), package.import_path());
+                continue;
+            }
+
+            let fetched = self.index.fetch(import_name, &pinned)?;
+            let manifest = Manifest::try_from(fetched.path.as_path())?;
+
+            let mut package = match &pinned {
+                Pinned::Registry {
+                    author,
+                    package,
+                    version,
+                } => Package::new(package.clone(), version.to_string(), author.clone(), None),
+                _ => Package::new(
+                    manifest.get_package_name(),
+                    manifest.get_package_version(),
+                    manifest
+                        .get_package_remote()
+                        .map_or(AUTHOR_PLACEHOLDER.to_string(), |remote| remote.author),
+                    fetched.source.clone(),
+                ),
+            };
+            package.import_name = Some(import_name.clone());
+            package.checksum = Some(package_checksum(&fetched.path)?);
+            imports.insert(import_name.clone(), package.import_path());
+
+            let (index, _) = graph.packages.insert_full(pinned.clone(), package);
+
+            let nested = manifest.get_package_dependencies().unwrap_or_default();
+            if !nested.is_empty() {
+                let mut stack = stack.to_vec();
+                stack.push(pinned);
+
+                let nested = self.walk(graph, &import_path, &stack, &fetched.path, &nested)?;
+                graph.packages[index].dependencies = nested;
+            }
+        }
+
+        Ok(imports)
+    }
+
+    ///
+    /// Returns the preferred version of a registry package that satisfies all requirements.
+    ///
+    /// A locked version, or else a version named by an exact requirement, is known to exist
+    /// and is selected without listing the versions of the package.
+    ///
+    fn select(&mut self, key: &(String, String), requirements: &[Requirement]) -> Result<Version> {
+        let (author, package) = key;
+        let known = self.locked.get(key).cloned().into_iter().chain(
+            requirements
+                .iter()
+                .filter_map(|requirement| exact_version(&requirement.requirement)),
+        );
+        if let Some(version) = known.into_iter().find(|version| matches_all(version, requirements)) {
+            return Ok(version);
+        }
+
+        self.index
+            .versions(author, package)?
+            .into_iter()
+            .filter(|version| matches_all(version, requirements))
+            .max()
+            .ok_or_else(|| conflict(author, package, requirements))
+    }
+}
+
+///
+/// Parses the version requirement of a registry dependency.
+///
+/// A bare version such as `1.0.0` was an exact version before requirements were supported,
+/// so it keeps meaning `=1.0.0` rather than `^1.0.0`.
+///
+fn parse_requirement(package: &str, text: &str) -> Result<VersionReq> {
+    let requirement = match Version::parse(text.trim()) {
+        Ok(version) => VersionReq::parse(&format!("={}", version)),
+        Err(_) => VersionReq::parse(text),
+    };
+
+    Ok(requirement.map_err(|error| PackageError::invalid_version_requirement(package, text, error))?)
+}
+
+/// Returns the version named by a requirement that matches a single version.
+fn exact_version(requirement: &VersionReq) -> Option<Version> {
+    match requirement.comparators.as_slice() {
+        [comparator] if comparator.op == Op::Exact => Some(Version {
+            major: comparator.major,
+            minor: comparator.minor?,
+            patch: comparator.patch?,
+            pre: comparator.pre.clone(),
+            build: Default::default(),
+        }),
+        _ => None,
+    }
+}
+
+fn matches_all(version: &Version, requirements: &[Requirement]) -> bool {
+    requirements
+        .iter()
+        .all(|requirement| requirement.requirement.matches(version))
+}
+
+fn conflict(author: &str, package: &str, requirements: &[Requirement]) -> leo_errors::LeoError {
+    let requirements = requirements
+        .iter()
+        .map(|requirement| format!("  {} requires `{}`", requirement.path.join(" -> "), requirement.text))
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    PackageError::conflicting_dependency_versions(format!("{}/{}", author, package), requirements).into()
+}
@@ -14,26 +14,39 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::root::Dependency;
+use crate::{
+    imports::IMPORTS_DIRECTORY_NAME,
+    root::{Manifest, MANIFEST_FILENAME},
+    source::SOURCE_DIRECTORY_NAME,
+};
 use leo_errors::{PackageError, Result};
 
 use indexmap::IndexMap;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::{
     borrow::Cow,
+    collections::{HashSet, VecDeque},
     convert::TryFrom,
     fmt::{self, Display},
-    fs::File,
+    fs::{self, File},
     io::{Read, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
+use walkdir::WalkDir;
 
 pub const LOCKFILE_FILENAME: &str = "Leo.lock";
 
+/// The prefix of the local package paths stored relative to the lock file.
+const CURRENT_DIRECTORY: &str = ".";
+
 /// Lock-file struct, contains all information about imported dependencies
 /// and their relationships.
 #[derive(Default, Debug, Clone, Serialize, Deserialize)]
 pub struct LockFile {
+    /// The direct dependencies of the package owning the lock file, by import name.
+    #[serde(skip_serializing_if = "IndexMap::is_empty", default)]
+    pub dependencies: IndexMap<String, String>,
     pub package: Vec<Package>,
 }
 
@@ -44,13 +57,33 @@ pub struct Package {
     pub version: String,
     pub author: String,
     pub import_name: Option<String>,
+    /// SHA256 checksum of the package manifest and sources, see `package_checksum`.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub checksum: Option<String>,
+    /// Where the package was fetched from, the Aleo Package Manager if not set.
+    #[serde(skip_serializing_if = "Option::is_none", default)]
+    pub source: Option<Source>,
     #[serde(skip_serializing_if = "IndexMap::is_empty", default)]
     pub dependencies: IndexMap<String, String>,
 }
 
+/// The origin of a package that was not fetched from the Aleo Package Manager.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum Source {
+    /// A package in a local directory. Directories inside the directory of the lock file
+    /// are stored relative to it, so the lock file of a workspace can be committed.
+    Path { path: PathBuf },
+    /// A package cloned from a git repository, pinned to the commit that was checked out.
+    Git { git: String, rev: String },
+}
+
 impl LockFile {
     pub fn new() -> Self {
-        LockFile { package: vec![] }
+        LockFile {
+            dependencies: IndexMap::new(),
+            package: vec![],
+        }
     }
 
     /// Check if LockFile exists in a directory.
@@ -76,25 +109,121 @@ impl LockFile {
     /// Form a IndexMap of kind:
     /// ``` imported_name => package_name ```
     /// for all imported packages.
-    pub fn to_import_map(&self) -> IndexMap<String, String> {
+    ///
+    /// Packages from a local path map to their absolute directory instead.
+    /// Names under which a package is imported by other dependencies are included too.
+    ///
+    /// Returns an error if an import name refers to two different packages.
+    pub fn to_import_map(&self) -> Result<IndexMap<String, String>> {
         let mut result = IndexMap::new();
         for package in self.package.iter() {
-            match &package.import_name {
-                Some(name) => result.insert(name.clone(), package.to_string()),
-                None => result.insert(package.name.clone(), package.to_string()),
+            let name = package.import_name.as_ref().unwrap_or(&package.name);
+            insert_import(&mut result, name, &package.import_path())?;
+        }
+
+        for package in self.package.iter() {
+            for (import_name, import_path) in package.dependencies.iter() {
+                insert_import(&mut result, import_name, import_path)?;
+            }
+        }
+
+        Ok(result)
+    }
+
+    ///
+    /// Form the import map of a member of the workspace at `root`.
+    ///
+    /// Only the dependencies of the member and their own dependencies are included, so members
+    /// can use the same import name for different packages.
+    /// Packages stored in the `imports/` directory of the workspace map to their absolute path.
+    ///
+    pub fn to_workspace_import_map(&self, root: &Path, member: &Path) -> Result<IndexMap<String, String>> {
+        let canonicalize = |path: &Path| path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+        let member = canonicalize(member);
+
+        // The workspace root resolves its dependencies into the top level of the lock file
+        let dependencies = if member == canonicalize(root) {
+            Some(&self.dependencies)
+        } else {
+            self.package
+                .iter()
+                .find(|package| match &package.source {
+                    Some(Source::Path { path }) => canonicalize(path) == member,
+                    _ => false,
+                })
+                .map(|package| &package.dependencies)
+        };
+
+        let records = self
+            .package
+            .iter()
+            .map(|package| (package.import_path(), package))
+            .collect::<IndexMap<_, _>>();
+        let mut pending = dependencies.into_iter().flatten().collect::<VecDeque<_>>();
+        let mut visited = HashSet::new();
+        let mut result = IndexMap::new();
+        while let Some((import_name, import_path)) = pending.pop_front() {
+            insert_import(&mut result, import_name, import_path)?;
+            if visited.insert(import_path) {
+                if let Some(record) = records.get(import_path) {
+                    pending.extend(record.dependencies.iter());
+                }
+            }
+        }
+
+        let imports_directory = root.join(IMPORTS_DIRECTORY_NAME);
+        for import_path in result.values_mut() {
+            if !Path::new(import_path.as_str()).is_absolute() {
+                *import_path = imports_directory.join(&import_path).display().to_string();
+            }
+        }
+
+        Ok(result)
+    }
+
+    ///
+    /// Checks that every fetched dependency of the package at the given path still matches its recorded checksum.
+    ///
+    /// Dependencies from a local path are edited in place and are not verified.
+    /// Any other dependency without a checksum is an error, until `leo fetch` records one.
+    ///
+    pub fn verify(&self, package_path: &Path) -> Result<()> {
+        for package in self.package.iter() {
+            if let Some(Source::Path { .. }) = package.source {
+                continue;
+            }
+
+            let expected = package
+                .checksum
+                .as_ref()
+                .ok_or_else(|| PackageError::dependency_checksum_missing(&package.name))?;
+
+            let directory = package.directory(package_path);
+            let found = if Manifest::exists_at(&directory) {
+                package_checksum(&directory)?
+            } else {
+                "no package".to_string()
             };
+
+            if &found != expected {
+                return Err(PackageError::dependency_checksum_mismatch(&package.name, expected, found).into());
+            }
         }
 
-        result
+        Ok(())
     }
 
     /// Write Leo.lock to the given location.
-    pub fn write_to(self, path: &Path) -> Result<()> {
+    pub fn write_to(mut self, path: &Path) -> Result<()> {
         let mut path = Cow::from(path);
         if path.is_dir() {
             path.to_mut().push(LOCKFILE_FILENAME);
         }
 
+        if let Some(directory) = path.parent() {
+            self.relativize_paths(directory);
+        }
+
         File::create(&path)
             .map_err(|error| PackageError::failed_to_create_lock_file(LOCKFILE_FILENAME, error))?
             .write_all(self.to_string()?.as_bytes())
@@ -102,6 +231,63 @@ impl LockFile {
 
         Ok(())
     }
+
+    /// Stores the local packages inside `directory` as `./`-prefixed paths relative to it.
+    fn relativize_paths(&mut self, directory: &Path) {
+        let directory = directory.canonicalize().unwrap_or_else(|_| directory.to_path_buf());
+        let relativize = |path: &Path| {
+            path.strip_prefix(&directory)
+                .ok()
+                .map(|relative| Path::new(CURRENT_DIRECTORY).join(relative))
+        };
+
+        let import_paths = self
+            .package
+            .iter_mut()
+            .flat_map(|package| package.dependencies.values_mut())
+            .chain(self.dependencies.values_mut());
+        for import_path in import_paths {
+            if let Some(relative) = relativize(Path::new(import_path.as_str())) {
+                *import_path = relative.display().to_string();
+            }
+        }
+
+        for package in self.package.iter_mut() {
+            if let Some(Source::Path { path }) = &mut package.source {
+                if let Some(relative) = relativize(path) {
+                    *path = relative;
+                }
+            }
+        }
+    }
+
+    /// Restores the absolute paths of the local packages stored relative to `directory`.
+    fn absolutize_paths(&mut self, directory: &Path) {
+        let absolutize = |path: &Path| {
+            path.strip_prefix(CURRENT_DIRECTORY)
+                .ok()
+                .map(|relative| directory.join(relative))
+        };
+
+        let import_paths = self
+            .package
+            .iter_mut()
+            .flat_map(|package| package.dependencies.values_mut())
+            .chain(self.dependencies.values_mut());
+        for import_path in import_paths {
+            if let Some(absolute) = absolutize(Path::new(import_path.as_str())) {
+                *import_path = absolute.display().to_string();
+            }
+        }
+
+        for package in self.package.iter_mut() {
+            if let Some(Source::Path { path }) = &mut package.source {
+                if let Some(absolute) = absolutize(path) {
+                    *path = absolute;
+                }
+            }
+        }
+    }
 }
 
 impl TryFrom<&Path> for LockFile {
@@ -124,16 +310,46 @@ impl TryFrom<&Path> for LockFile {
         file.read_to_string(&mut buffer)
             .map_err(|error| PackageError::failed_to_read_lock_file(LOCKFILE_FILENAME, error))?;
 
-        toml::from_str(&buffer).map_err(|error| PackageError::failed_to_parse_lock_file(LOCKFILE_FILENAME, error))
+        let mut lock_file: LockFile = toml::from_str(&buffer)
+            .map_err(|error| PackageError::failed_to_parse_lock_file(LOCKFILE_FILENAME, error))?;
+
+        if let Some(directory) = path.parent() {
+            lock_file.absolutize_paths(directory);
+        }
+
+        Ok(lock_file)
     }
 }
 
 impl Package {
-    /// Fill dependencies from Leo Manifest data.
-    pub fn add_dependencies(&mut self, dependencies: &IndexMap<String, Dependency>) {
-        for (import_name, dependency) in dependencies.iter() {
-            self.dependencies
-                .insert(import_name.clone(), Package::from(dependency).to_string());
+    /// Returns a record for a package without dependencies of its own.
+    pub fn new(name: String, version: String, author: String, source: Option<Source>) -> Self {
+        Self {
+            name,
+            version,
+            author,
+            import_name: None,
+            checksum: None,
+            source,
+            dependencies: Default::default(),
+        }
+    }
+
+    /// Returns the directory holding the package, for a dependency of the package at the given path.
+    pub fn directory(&self, package_path: &Path) -> PathBuf {
+        match &self.source {
+            Some(Source::Path { path }) => path.clone(),
+            _ => package_path.join(IMPORTS_DIRECTORY_NAME).join(self.import_path()),
+        }
+    }
+
+    /// Returns where the import resolver finds the package: the name of its
+    /// directory inside `imports/`, or its absolute path for a local package.
+    pub fn import_path(&self) -> String {
+        match &self.source {
+            None => self.to_string(),
+            Some(Source::Path { path }) => path.display().to_string(),
+            Some(Source::Git { rev, .. }) => format!("{}@{}", self.name, &rev[..rev.len().min(12)]),
         }
     }
 }
@@ -146,14 +362,46 @@ impl Display for Package {
     }
 }
 
-impl From<&Dependency> for Package {
-    fn from(dependency: &Dependency) -> Package {
-        Package {
-            name: dependency.package.clone(),
-            author: dependency.author.clone(),
-            version: dependency.version.clone(),
-            dependencies: Default::default(),
-            import_name: None,
+/// Adds an import to the given import map, unless its name already refers to another package.
+fn insert_import(import_map: &mut IndexMap<String, String>, import_name: &str, import_path: &str) -> Result<()> {
+    match import_map.get(import_name) {
+        Some(existing) if existing != import_path => {
+            Err(PackageError::import_name_collision(import_name, existing, import_path).into())
+        }
+        Some(_) => Ok(()),
+        None => {
+            import_map.insert(import_name.to_string(), import_path.to_string());
+            Ok(())
+        }
+    }
+}
+
+///
+/// Returns a SHA256 checksum of the manifest and the source files of the package at the given path.
+///
+/// Files are hashed in path order along with their path relative to the package,
+/// so the checksum does not depend on where the package is stored.
+///
+pub fn package_checksum(path: &Path) -> Result<String> {
+    let hash_error = |error| PackageError::failed_to_hash_dependency(path, error);
+
+    let mut files = vec![path.join(MANIFEST_FILENAME)];
+    for entry in WalkDir::new(path.join(SOURCE_DIRECTORY_NAME)).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+        let entry = entry.map_err(hash_error)?;
+        if entry.file_type().is_file() {
+            files.push(entry.into_path());
         }
     }
+
+    let mut hasher = Sha256::new();
+    for file in files {
+        let relative = file.strip_prefix(path).unwrap_or(&file);
+        let content = fs::read(&file).map_err(|error| PackageError::failed_to_hash_dependency(&file, error))?;
+
+        hasher.update(relative.to_string_lossy().replace('\\', "/").as_bytes());
+        hasher.update((content.len() as u64).to_le_bytes());
+        hasher.update(content);
+    }
+
+    Ok(format!("{:x}", hasher.finalize()))
 }
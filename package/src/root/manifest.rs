@@ -14,7 +14,10 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::package::Package;
+use crate::{
+    package::Package,
+    root::{Target, Workspace},
+};
 use leo_errors::{PackageError, Result};
 
 use indexmap::IndexMap;
@@ -25,7 +28,7 @@ use std::{
     convert::TryFrom,
     fs::File,
     io::{Read, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
 
 pub const MANIFEST_FILENAME: &str = "Leo.toml";
@@ -36,18 +39,30 @@ pub struct Remote {
     pub author: String,
 }
 
-#[derive(Clone, Debug, Deserialize)]
-pub struct Dependency {
-    pub author: String,
-    pub version: String,
-    pub package: String,
+/// A dependency listed in the `[dependencies]` section of the manifest.
+#[derive(Clone, Debug, Deserialize, PartialEq)]
+#[serde(untagged)]
+pub enum Dependency {
+    /// A package published to the Aleo Package Manager.
+    Registry {
+        author: String,
+        version: String,
+        package: String,
+    },
+    /// A package in a local directory, relative to the manifest that lists it.
+    Path { path: PathBuf },
+    /// A package in a git repository, checked out at `rev` or the default branch.
+    Git { git: String, rev: Option<String> },
 }
 
 #[derive(Deserialize)]
 pub struct Manifest {
     pub project: Package,
     pub remote: Option<Remote>,
+    #[serde(default)]
+    pub target: Target,
     pub dependencies: Option<IndexMap<String, Dependency>>,
+    pub workspace: Option<Workspace>,
 }
 
 impl Manifest {
@@ -55,7 +70,9 @@ impl Manifest {
         Ok(Self {
             project: Package::new(package_name)?,
             remote: author.map(|author| Remote { author }),
+            target: Target::default(),
             dependencies: Some(IndexMap::<String, Dependency>::new()),
+            workspace: None,
         })
     }
 
@@ -90,15 +107,23 @@ impl Manifest {
     /// Get HashMap of kind:
     ///     import name => import directory
     /// Which then used in AST/ASG to resolve import paths.
+    ///
+    /// Path and git dependencies are only located once fetched, see `LockFile::to_import_map`.
     pub fn get_imports_map(&self) -> Option<HashMap<String, String>> {
         self.dependencies.clone().map(|dependencies| {
             dependencies
                 .into_iter()
                 .map(|(name, dependency)| {
-                    (
-                        name,
-                        format!("{}-{}@{}", dependency.author, dependency.package, dependency.version),
-                    )
+                    let directory = match dependency {
+                        Dependency::Registry {
+                            author,
+                            package,
+                            version,
+                        } => format!("{}-{}@{}", author, package, version),
+                        Dependency::Path { path } => path.display().to_string(),
+                        Dependency::Git { git, .. } => git,
+                    };
+                    (name, directory)
                 })
                 .collect()
         })
@@ -108,6 +133,22 @@ impl Manifest {
         self.project.license.clone()
     }
 
+    pub fn get_package_include(&self) -> Option<Vec<String>> {
+        self.project.include.clone()
+    }
+
+    pub fn get_package_exclude(&self) -> Option<Vec<String>> {
+        self.project.exclude.clone()
+    }
+
+    pub fn get_target(&self) -> Target {
+        self.target
+    }
+
+    pub fn get_workspace(&self) -> Option<Workspace> {
+        self.workspace.clone()
+    }
+
     pub fn get_package_remote(&self) -> Option<Remote> {
         self.remote.clone()
     }
@@ -143,15 +184,19 @@ license = "MIT"
 author = "{author}" # Add your Aleo Package Manager username or team name.
 
 [target]
-curve = "bls12_377"
-proving_system = "groth16"
+curve = "{curve}"
+proving_system = "{proving_system}"
 
 [dependencies]
 # Define dependencies here in format:
 # name = {{ package = "package-name", author = "author", version = "version" }}
+# name = {{ path = "../local-package" }}
+# name = {{ git = "https://example.com/package.git", rev = "commit" }}
 "#,
             name = self.project.name,
-            author = author
+            author = author,
+            curve = self.target.curve,
+            proving_system = self.target.proving_system,
         )
     }
 }
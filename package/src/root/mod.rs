@@ -26,5 +26,11 @@ pub use self::manifest::*;
 pub mod readme;
 pub use self::readme::*;
 
+pub mod target;
+pub use self::target::*;
+
+pub mod workspace;
+pub use self::workspace::*;
+
 pub mod zip;
 pub use self::zip::*;
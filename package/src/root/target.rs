@@ -0,0 +1,66 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `[target]` section of a manifest.
+
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+/// The pairing-friendly curve that the circuit of a package is proven over.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Curve {
+    #[default]
+    #[serde(rename = "bls12_377")]
+    Bls12_377,
+    #[serde(rename = "bw6_761")]
+    Bw6_761,
+}
+
+impl fmt::Display for Curve {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Curve::Bls12_377 => write!(f, "bls12_377"),
+            Curve::Bw6_761 => write!(f, "bw6_761"),
+        }
+    }
+}
+
+/// The proof system used to set up, prove and verify the circuit of a package.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum ProvingSystem {
+    #[default]
+    Groth16,
+    Marlin,
+}
+
+impl fmt::Display for ProvingSystem {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ProvingSystem::Groth16 => write!(f, "groth16"),
+            ProvingSystem::Marlin => write!(f, "marlin"),
+        }
+    }
+}
+
+/// The curve and proving system of a package, defaulting to Groth16 over BLS12-377
+/// for manifests written before the section existed.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Eq)]
+#[serde(default, deny_unknown_fields)]
+pub struct Target {
+    pub curve: Curve,
+    pub proving_system: ProvingSystem,
+}
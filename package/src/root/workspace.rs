@@ -0,0 +1,102 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//! The `[workspace]` section of a manifest.
+
+use crate::root::MANIFEST_FILENAME;
+use leo_errors::{PackageError, Result};
+
+use serde::Deserialize;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// A set of packages that are fetched and built together and share one lock file.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq)]
+pub struct Workspace {
+    /// Directories of the member packages relative to the workspace root, globs are allowed.
+    pub members: Vec<String>,
+}
+
+/// The parts of a manifest that are read when looking for a workspace.
+/// Unlike a package manifest, a workspace root does not need a `[project]` section.
+#[derive(Deserialize)]
+struct WorkspaceManifest {
+    workspace: Option<Workspace>,
+}
+
+impl Workspace {
+    /// Returns the `[workspace]` section of the manifest in the given directory, if any.
+    pub fn read_from(path: &Path) -> Result<Option<Self>> {
+        let manifest_path = path.join(MANIFEST_FILENAME);
+        if !manifest_path.exists() {
+            return Ok(None);
+        }
+
+        let content = fs::read_to_string(&manifest_path)
+            .map_err(|e| PackageError::failed_to_read_manifest_file(MANIFEST_FILENAME, e))?;
+        let manifest: WorkspaceManifest =
+            toml::from_str(&content).map_err(|e| PackageError::failed_to_parse_manifest_file(MANIFEST_FILENAME, e))?;
+
+        Ok(manifest.workspace)
+    }
+
+    ///
+    /// Returns the root of the workspace containing the package at `path`.
+    ///
+    /// The package directory itself is the root if its manifest has a `[workspace]` section,
+    /// otherwise the closest parent directory whose workspace lists the package as a member.
+    ///
+    pub fn find_root(path: &Path) -> Result<Option<PathBuf>> {
+        let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+
+        for directory in path.ancestors() {
+            if let Some(workspace) = Self::read_from(directory)? {
+                if directory == path || workspace.member_paths(directory)?.contains(&path) {
+                    return Ok(Some(directory.to_path_buf()));
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Returns the directories of the member packages, sorted and with globs expanded.
+    pub fn member_paths(&self, root: &Path) -> Result<Vec<PathBuf>> {
+        let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+
+        let mut paths = Vec::new();
+        for member in self.members.iter() {
+            let pattern = root.join(member);
+            let matches = glob::glob(&pattern.to_string_lossy())
+                .map_err(|error| PackageError::invalid_file_pattern(member, error))?
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.join(MANIFEST_FILENAME).exists())
+                .collect::<Vec<_>>();
+
+            if matches.is_empty() {
+                return Err(PackageError::workspace_member_not_found(member, &root).into());
+            }
+
+            paths.extend(matches.into_iter().map(|entry| entry.canonicalize().unwrap_or(entry)));
+        }
+
+        paths.sort();
+        paths.dedup();
+        Ok(paths)
+    }
+}
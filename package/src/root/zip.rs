@@ -23,24 +23,31 @@ use crate::{
         CHECKSUM_FILE_EXTENSION, CIRCUIT_FILE_EXTENSION, OUTPUTS_DIRECTORY_NAME, PROOF_FILE_EXTENSION,
         PROVING_KEY_FILE_EXTENSION, VERIFICATION_KEY_FILE_EXTENSION,
     },
-    root::{MANIFEST_FILENAME, README_FILENAME},
+    root::{Manifest, MANIFEST_FILENAME, README_FILENAME},
     source::{SOURCE_DIRECTORY_NAME, SOURCE_FILE_EXTENSION},
 };
 use leo_errors::{PackageError, Result};
 
+use glob::{MatchOptions, Pattern};
 use serde::Deserialize;
 use std::{
     borrow::Cow,
+    collections::HashSet,
+    convert::TryFrom,
     fs::{
         File, {self},
     },
     io::{Read, Write},
-    path::Path,
+    path::{Path, PathBuf},
 };
 use walkdir::WalkDir;
-use zip::write::{FileOptions, ZipWriter};
+use zip::{
+    write::{FileOptions, ZipWriter},
+    DateTime,
+};
 
 pub static ZIP_FILE_EXTENSION: &str = ".zip";
+pub static LEOIGNORE_FILENAME: &str = ".leoignore";
 
 #[derive(Deserialize)]
 pub struct ZipFile {
@@ -70,10 +77,34 @@ impl ZipFile {
     //     Ok(fs::read(&path).map_err(|_| PackageError::FileReadError(path.clone()))?)
     // }
 
+    /// Returns the paths of the files in the package directory that are written to the zip file,
+    /// relative to the package directory and in the order they are written.
+    pub fn included_files(&self, src_dir: &Path) -> Result<Vec<PathBuf>> {
+        let filter = PackageFilter::load(src_dir)?;
+
+        let mut files = WalkDir::new(src_dir)
+            .into_iter()
+            .filter_map(|e| e.ok())
+            .filter(|entry| entry.file_type().is_file())
+            .filter_map(|entry| entry.path().strip_prefix(src_dir).ok().map(Path::to_path_buf))
+            .filter(|name| {
+                let included = filter.is_included(name);
+                tracing::debug!("Checking if {:?} is included - {}", name, included);
+                included
+            })
+            .collect::<Vec<_>>();
+
+        // Sort the entries so the zip file does not depend on the order of the file system.
+        files.sort();
+        Ok(files)
+    }
+
     /// Writes the current package contents to a zip file.
+    ///
+    /// Entries are sorted and carry a fixed timestamp, so the same package contents always
+    /// produce the same zip file.
     pub fn write(&self, src_dir: &Path) -> Result<()> {
-        // Build walkdir iterator from current package
-        let walkdir = WalkDir::new(src_dir);
+        let files = self.included_files(src_dir)?;
 
         // Create zip file
         let path = self.setup_file_path(src_dir);
@@ -82,44 +113,42 @@ impl ZipFile {
         let mut zip = ZipWriter::new(file);
         let options = FileOptions::default()
             .compression_method(zip::CompressionMethod::Stored)
-            .unix_permissions(0o755);
+            .unix_permissions(0o755)
+            .last_modified_time(DateTime::default());
 
-        // Walk through files in directory and write desired ones to the zip file
+        // Write the included files, each preceded by the directories leading to it.
+        let mut directories = HashSet::new();
         let mut buffer = Vec::new();
-        for entry in walkdir.into_iter().filter_map(|e| e.ok()) {
-            let path = entry.path();
-            let name = path.strip_prefix(src_dir).unwrap();
+        for name in files {
+            let mut parents = name
+                .ancestors()
+                .skip(1)
+                .filter(|parent| !parent.as_os_str().is_empty())
+                .collect::<Vec<_>>();
+            parents.reverse();
+
+            for parent in parents {
+                if directories.insert(parent.to_path_buf()) {
+                    tracing::info!("Adding directory {:?}", parent);
+                    #[allow(deprecated)]
+                    zip.add_directory_from_path(parent, options)
+                        .map_err(PackageError::io_error_zip_file)?;
+                }
+            }
 
-            // Add file/directory exclusion
+            let file_path = src_dir.join(&name);
+            tracing::info!("Adding file {:?} as {:?}", file_path, name);
+            #[allow(deprecated)]
+            zip.start_file_from_path(&name, options)
+                .map_err(PackageError::io_error_zip_file)?;
 
-            let included = is_included(name);
-            tracing::debug!("Checking if {:?} is included - {}", name, included);
-            if !included {
-                continue;
-            }
+            let mut f = File::open(&file_path).map_err(PackageError::failed_to_open_zip_file)?;
+            f.read_to_end(&mut buffer)
+                .map_err(PackageError::failed_to_read_zip_file)?;
+            zip.write_all(&*buffer)
+                .map_err(PackageError::failed_to_write_zip_file)?;
 
-            // Write file or directory
-            if path.is_file() {
-                tracing::info!("Adding file {:?} as {:?}", path, name);
-                #[allow(deprecated)]
-                zip.start_file_from_path(name, options)
-                    .map_err(PackageError::io_error_zip_file)?;
-
-                let mut f = File::open(path).map_err(PackageError::failed_to_open_zip_file)?;
-                f.read_to_end(&mut buffer)
-                    .map_err(PackageError::failed_to_read_zip_file)?;
-                zip.write_all(&*buffer)
-                    .map_err(PackageError::failed_to_write_zip_file)?;
-
-                buffer.clear();
-            } else if !name.as_os_str().is_empty() {
-                // Only if not root Avoids path spec / warning
-                // and mapname conversion failed error on unzip
-                tracing::info!("Adding directory {:?} as {:?}", path, name);
-                #[allow(deprecated)]
-                zip.add_directory_from_path(name, options)
-                    .map_err(PackageError::io_error_zip_file)?;
-            }
+            buffer.clear();
         }
 
         zip.finish().map_err(PackageError::io_error_zip_file)?;
@@ -154,11 +183,119 @@ impl ZipFile {
     }
 }
 
-/// Check if the file path should be included in the package zip file.
-fn is_included(path: &Path) -> bool {
+///
+/// The rules selecting the files of a package that are written to its zip file.
+///
+/// By default the manifest, readme, sources and inputs are selected. The `include` globs in the
+/// manifest replace that selection, and files matching the `exclude` globs or a line of the
+/// `.leoignore` file are left out. Imports and build outputs are never published.
+///
+#[derive(Debug, Default)]
+pub struct PackageFilter {
+    include: Vec<Pattern>,
+    exclude: Vec<Pattern>,
+}
+
+impl PackageFilter {
+    /// Reads the rules from the manifest and the `.leoignore` file of the package at `src_dir`.
+    pub fn load(src_dir: &Path) -> Result<Self> {
+        let mut include = Vec::new();
+        let mut exclude = Vec::new();
+
+        if Manifest::exists_at(src_dir) {
+            let manifest = Manifest::try_from(src_dir)?;
+            include = manifest.get_package_include().unwrap_or_default();
+            exclude = manifest.get_package_exclude().unwrap_or_default();
+        }
+
+        let leoignore = src_dir.join(LEOIGNORE_FILENAME);
+        if leoignore.exists() {
+            let content = fs::read_to_string(&leoignore).map_err(PackageError::io_error_leoignore_file)?;
+            exclude.extend(
+                content
+                    .lines()
+                    .map(str::trim)
+                    .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                    .map(str::to_string),
+            );
+        }
+
+        Self::new(&include, &exclude)
+    }
+
+    pub fn new(include: &[String], exclude: &[String]) -> Result<Self> {
+        Ok(Self {
+            include: include
+                .iter()
+                .map(|pattern| parse_pattern(pattern))
+                .collect::<Result<_>>()?,
+            exclude: exclude
+                .iter()
+                .map(|pattern| parse_pattern(pattern))
+                .collect::<Result<_>>()?,
+        })
+    }
+
+    /// Returns `true` if the file at `path`, relative to the package directory, is published.
+    pub fn is_included(&self, path: &Path) -> bool {
+        if is_build_artifact(path) {
+            return false;
+        }
+
+        // The manifest is always needed to use the package.
+        if path == Path::new(MANIFEST_FILENAME) {
+            return true;
+        }
+
+        let selected = match self.include.is_empty() {
+            true => is_package_file(path),
+            false => matches_any(&self.include, path),
+        };
+
+        selected && !matches_any(&self.exclude, path)
+    }
+}
+
+/// Parses a gitignore-like glob. A leading `/` and a trailing `/` are ignored.
+fn parse_pattern(pattern: &str) -> Result<Pattern> {
+    let trimmed = pattern.trim_start_matches('/').trim_end_matches('/');
+    Ok(Pattern::new(trimmed).map_err(|error| PackageError::invalid_file_pattern(pattern, error))?)
+}
+
+///
+/// Returns `true` if the path or one of its parent directories matches one of the patterns.
+///
+/// Patterns without a `/` are matched against file and directory names, other patterns
+/// against paths relative to the package directory.
+///
+fn matches_any(patterns: &[Pattern], path: &Path) -> bool {
+    let options = MatchOptions {
+        case_sensitive: true,
+        require_literal_separator: true,
+        require_literal_leading_dot: false,
+    };
+
+    path.ancestors()
+        .filter(|ancestor| !ancestor.as_os_str().is_empty())
+        .any(|ancestor| {
+            let relative = ancestor.to_string_lossy().replace('\\', "/");
+            let name = ancestor.file_name().unwrap_or_default().to_string_lossy();
+
+            patterns.iter().any(|pattern| {
+                let candidate = match pattern.as_str().contains('/') {
+                    true => relative.as_str(),
+                    false => &name,
+                };
+                pattern.matches_with(candidate, options)
+            })
+        })
+}
+
+/// Check if the file path is an import or a build output, which are never published.
+fn is_build_artifact(path: &Path) -> bool {
     // DO NOT include `imports` and `outputs` directories.
     if path.starts_with(IMPORTS_DIRECTORY_NAME) || path.starts_with(OUTPUTS_DIRECTORY_NAME) {
-        return false;
+        return true;
     }
 
     // excluded extensions: `.in`, `.bytes`, `lpk`, `lvk`, `.proof`, `.sum`, `.zip`, `.bytes`
@@ -171,9 +308,14 @@ fn is_included(path: &Path) -> bool {
             | ext.eq(ZIP_FILE_EXTENSION.trim_start_matches('.'))
             | ext.eq(CIRCUIT_FILE_EXTENSION.trim_start_matches('.'))
     }) {
-        return false;
+        return true;
     }
 
+    false
+}
+
+/// Check if the file path is selected by default: the manifest, readme, sources and inputs.
+fn is_package_file(path: &Path) -> bool {
     // Allow `inputs` folder
     if path.ends_with(INPUTS_DIRECTORY_NAME.trim_end_matches('/')) {
         return true;
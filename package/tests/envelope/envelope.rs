@@ -0,0 +1,76 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+// Tests for the envelope of key and proof files
+
+use crate::test_dir;
+use leo_package::{
+    outputs::{Envelope, ProofFile, ProvingKeyFile, ENVELOPE_VERSION},
+    root::{ProvingSystem, Target},
+};
+
+use std::fs;
+
+fn envelope(package_version: &str, circuit_hash: &str) -> Envelope {
+    Envelope::new("envelope", package_version, Target::default(), circuit_hash, 2)
+}
+
+#[test]
+fn test_envelope_round_trip() {
+    let test_directory = test_dir();
+    let expected = envelope("0.1.0", "abc");
+
+    ProvingKeyFile::new("envelope")
+        .write_to(&test_directory.join("envelope.lpk"), &expected, b"key")
+        .unwrap();
+    let (found, proving_key) = ProvingKeyFile::new("envelope")
+        .read_from(&test_directory.join("envelope.lpk"))
+        .unwrap();
+
+    assert_eq!(found, expected);
+    assert_eq!(found.version, ENVELOPE_VERSION);
+    assert_eq!(proving_key, b"key");
+    assert!(found.check(&expected, &test_directory).is_ok());
+}
+
+#[test]
+fn test_envelope_mismatch() {
+    let test_directory = test_dir();
+    let expected = envelope("0.1.0", "abc");
+
+    assert!(envelope("0.2.0", "abc").check(&expected, &test_directory).is_err());
+    assert!(envelope("0.1.0", "def").check(&expected, &test_directory).is_err());
+
+    let mut marlin = expected.clone();
+    marlin.proving_system = ProvingSystem::Marlin;
+    assert!(marlin.check(&expected, &test_directory).is_err());
+}
+
+#[test]
+fn test_envelope_missing() {
+    let test_directory = test_dir();
+    let proof_path = test_directory.join("envelope.proof");
+
+    // Proofs written before the envelope existed are raw bytes
+    fs::write(&proof_path, b"proof").unwrap();
+    assert!(ProofFile::new("envelope").read_from(&proof_path).is_err());
+
+    // Envelopes from newer versions of Leo are not read
+    let mut newer = envelope("0.1.0", "abc");
+    newer.version = ENVELOPE_VERSION + 1;
+    fs::write(&proof_path, newer.seal(b"proof").unwrap()).unwrap();
+    assert!(ProofFile::new("envelope").read_from(&proof_path).is_err());
+}
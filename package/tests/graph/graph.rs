@@ -0,0 +1,131 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+// Tests for the dependency graph of a lock file
+
+use leo_package::{
+    graph::DependencyGraph,
+    root::lock_file::{LockFile, Package, Source},
+};
+
+/// Returns a record for a package imported under its own name.
+fn package(name: &str, version: &str, source: Option<Source>) -> Package {
+    let mut package = Package::new(name.into(), version.into(), "author".into(), source);
+    package.import_name = Some(name.into());
+    package
+}
+
+/// app -> math -> util v1.0.0, app -> old_util = util from git, app -> net -> math
+fn lock_file() -> LockFile {
+    let mut math = package(
+        "math",
+        "0.1.0",
+        Some(Source::Path {
+            path: "/packages/math".into(),
+        }),
+    );
+    let util = package("util", "1.0.0", None);
+    let git_util = package(
+        "util",
+        "0.9.0",
+        Some(Source::Git {
+            git: "https://example.com/util.git".into(),
+            rev: "0123456789abcdef".into(),
+        }),
+    );
+    let mut net = package("net", "0.2.0", None);
+
+    math.dependencies.insert("util".into(), util.import_path());
+    net.dependencies.insert("math".into(), math.import_path());
+
+    let mut lock_file = LockFile::new();
+    lock_file.dependencies.insert("math".into(), math.import_path());
+    lock_file.dependencies.insert("old_util".into(), git_util.import_path());
+    lock_file.dependencies.insert("net".into(), net.import_path());
+    lock_file
+        .add_package(math)
+        .add_package(util)
+        .add_package(git_util)
+        .add_package(net);
+    lock_file
+}
+
+#[test]
+fn test_graph_roots() {
+    let lock_file = lock_file();
+    let graph = DependencyGraph::new(&lock_file);
+
+    let roots = graph
+        .roots()
+        .into_iter()
+        .map(|edge| (edge.import_name, edge.package.version.as_str()))
+        .collect::<Vec<_>>();
+    assert_eq!(roots, vec![("math", "0.1.0"), ("old_util", "0.9.0"), ("net", "0.2.0")]);
+
+    let math = graph.roots()[0].package;
+    let dependencies = graph.dependencies(math);
+    assert_eq!(dependencies.len(), 1);
+    assert_eq!(dependencies[0].package.version, "1.0.0");
+}
+
+#[test]
+fn test_graph_roots_without_recorded_dependencies() {
+    let mut lock_file = lock_file();
+    lock_file.dependencies.clear();
+    let graph = DependencyGraph::new(&lock_file);
+
+    // Packages no other package depends on are the roots.
+    let roots = graph
+        .roots()
+        .into_iter()
+        .map(|edge| edge.import_name)
+        .collect::<Vec<_>>();
+    assert_eq!(roots, vec!["util", "net"]);
+}
+
+#[test]
+fn test_graph_duplicates() {
+    let lock_file = lock_file();
+    let graph = DependencyGraph::new(&lock_file);
+
+    let duplicates = graph.duplicates();
+    assert_eq!(duplicates.len(), 1);
+    let versions = duplicates["util"]
+        .iter()
+        .map(|package| package.version.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(versions, vec!["1.0.0", "0.9.0"]);
+}
+
+#[test]
+fn test_graph_why() {
+    let lock_file = lock_file();
+    let graph = DependencyGraph::new(&lock_file);
+
+    let chains = graph
+        .why("util")
+        .into_iter()
+        .map(|chain| chain.into_iter().map(|edge| edge.import_name).collect::<Vec<_>>())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        chains,
+        vec![vec!["math", "util"], vec!["old_util"], vec!["net", "math", "util"],]
+    );
+
+    // Packages can be looked up by their import name too.
+    assert_eq!(graph.why("old_util").len(), 1);
+    assert!(graph.why("missing").is_empty());
+}
@@ -16,10 +16,10 @@
 
 use crate::test_dir;
 use leo_package::{
-    inputs::{InputFile, InputsDirectory, StateFile},
+    inputs::{InputFile, InputsDirectory, StateFile, INPUTS_DIRECTORY_NAME},
     package::Package,
     root::Manifest,
-    source::{MainFile, SourceDirectory},
+    source::{LibFile, MainFile, SourceDirectory},
 };
 
 const TEST_PACKAGE_NAME: &str = "test-package";
@@ -52,6 +52,24 @@ fn initialize_valid_package_with_author() {
     assert!(Package::is_initialized(TEST_PACKAGE_NAME, &test_directory));
 }
 
+#[test]
+fn initialize_valid_library() {
+    let test_directory = test_dir();
+
+    // Ensure a package can be initialized at the `test_directory`
+    assert!(Package::can_initialize(TEST_PACKAGE_NAME, &test_directory));
+
+    // Initialize a library package at the `test_directory`
+    assert!(Package::initialize_library(TEST_PACKAGE_NAME, &test_directory, None).is_ok());
+
+    // Ensure a library is initialized at the `test_directory`, without a main file or inputs
+    assert!(Package::is_library_initialized(TEST_PACKAGE_NAME, &test_directory));
+    assert!(LibFile::exists_at(&test_directory));
+    assert!(!MainFile::exists_at(&test_directory));
+    assert!(!test_directory.join(INPUTS_DIRECTORY_NAME).exists());
+    assert!(!Package::is_initialized(TEST_PACKAGE_NAME, &test_directory));
+}
+
 #[test]
 #[ignore]
 fn initialize_fails_with_invalid_package_names() {
@@ -0,0 +1,241 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+// Tests for package lock file
+
+use crate::test_dir;
+use leo_package::root::{
+    lock_file::{package_checksum, LockFile, Package, Source},
+    MANIFEST_FILENAME,
+};
+
+use std::{
+    convert::TryFrom,
+    fs::{self, File},
+    io::Write,
+    path::Path,
+};
+
+/// Create a package with a manifest and a single source file.
+fn create_package(path: &Path, source: &str) {
+    fs::create_dir_all(path.join("src")).unwrap();
+    File::create(path.join(MANIFEST_FILENAME))
+        .unwrap()
+        .write_all(b"[project]\nname = \"shared\"\nversion = \"0.1.0\"\n")
+        .unwrap();
+    File::create(path.join("src/main.leo"))
+        .unwrap()
+        .write_all(source.as_bytes())
+        .unwrap();
+}
+
+#[test]
+fn test_lock_file_sources() {
+    let test_directory = test_dir();
+
+    let mut registry = Package::new("registry".into(), "0.1.0".into(), "author".into(), None);
+    registry.import_name = Some("registry_import".into());
+    let local = Package::new(
+        "local".into(),
+        "0.1.0".into(),
+        "author".into(),
+        Some(Source::Path {
+            path: "/packages/local".into(),
+        }),
+    );
+    let mut remote = Package::new(
+        "remote".into(),
+        "0.1.0".into(),
+        "author".into(),
+        Some(Source::Git {
+            git: "file:///packages/remote".into(),
+            rev: "0123456789abcdef0123456789abcdef01234567".into(),
+        }),
+    );
+    remote.checksum = Some("checksum".into());
+
+    let mut lock_file = LockFile::new();
+    lock_file.add_package(registry).add_package(local).add_package(remote);
+    lock_file.write_to(&test_directory).unwrap();
+
+    let lock_file = LockFile::try_from(test_directory.as_path()).unwrap();
+    assert_eq!(
+        lock_file.package[2].source,
+        Some(Source::Git {
+            git: "file:///packages/remote".into(),
+            rev: "0123456789abcdef0123456789abcdef01234567".into(),
+        })
+    );
+    assert_eq!(lock_file.package[2].checksum.as_deref(), Some("checksum"));
+
+    let import_map = lock_file.to_import_map().unwrap();
+    assert_eq!(import_map["registry_import"], "author-registry@0.1.0");
+    assert_eq!(import_map["local"], "/packages/local");
+    assert_eq!(import_map["remote"], "remote@0123456789ab");
+}
+
+#[test]
+fn test_import_name_collision() {
+    let mut lib = Package::new("lib".into(), "1.0.0".into(), "author".into(), None);
+    lib.import_name = Some("lib".into());
+    let mut other = Package::new("other".into(), "0.1.0".into(), "author".into(), None);
+    other.import_name = Some("other".into());
+
+    // Importing the same package under the same name from two packages is fine.
+    other.dependencies.insert("lib".into(), "author-lib@1.0.0".into());
+    let mut lock_file = LockFile::new();
+    lock_file.add_package(lib.clone()).add_package(other.clone());
+    assert_eq!(lock_file.to_import_map().unwrap()["lib"], "author-lib@1.0.0");
+
+    // Importing another package under that name is not.
+    other.dependencies.insert("lib".into(), "author-lib@2.0.0".into());
+    let mut lock_file = LockFile::new();
+    lock_file.add_package(lib).add_package(other);
+    assert!(lock_file.to_import_map().is_err());
+}
+
+#[test]
+fn test_package_checksum() {
+    let test_directory = test_dir();
+    let source = "function double(x: u32) -> u32 {\n    return x * 2u32;\n}\n";
+
+    create_package(&test_directory.join("a"), source);
+    create_package(&test_directory.join("b"), source);
+    create_package(&test_directory.join("c"), &source.replace("2u32", "3u32"));
+
+    // Only the contents of the package matter, not where it is stored.
+    let checksum = package_checksum(&test_directory.join("a")).unwrap();
+    assert_eq!(checksum, package_checksum(&test_directory.join("b")).unwrap());
+    assert_ne!(checksum, package_checksum(&test_directory.join("c")).unwrap());
+
+    // Build outputs are not part of the package.
+    fs::create_dir_all(test_directory.join("a/outputs")).unwrap();
+    File::create(test_directory.join("a/outputs/shared.sum")).unwrap();
+    assert_eq!(checksum, package_checksum(&test_directory.join("a")).unwrap());
+}
+
+#[test]
+fn test_lock_file_verify() {
+    let test_directory = test_dir();
+    let source = "function double(x: u32) -> u32 {\n    return x * 2u32;\n}\n";
+
+    let mut package = Package::new("shared".into(), "0.1.0".into(), "author".into(), None);
+    let directory = package.directory(&test_directory);
+    create_package(&directory, source);
+    package.checksum = Some(package_checksum(&directory).unwrap());
+
+    let mut lock_file = LockFile::new();
+    lock_file.add_package(package);
+    assert!(lock_file.verify(&test_directory).is_ok());
+
+    // A modified dependency is rejected.
+    fs::write(directory.join("src/main.leo"), source.replace("2u32", "3u32")).unwrap();
+    let error = lock_file.verify(&test_directory).unwrap_err().to_string();
+    assert!(error.contains("do not match the lock file"), "{}", error);
+
+    // So is a missing one.
+    fs::remove_dir_all(&directory).unwrap();
+    assert!(lock_file.verify(&test_directory).is_err());
+
+    // A record without a checksum cannot be verified.
+    create_package(&directory, source);
+    lock_file.package[0].checksum = None;
+    let error = lock_file.verify(&test_directory).unwrap_err().to_string();
+    assert!(error.contains("records no checksum"), "{}", error);
+}
+
+#[test]
+fn test_lock_file_workspace_paths() {
+    let test_directory = test_dir().canonicalize().unwrap();
+    let member = test_directory.join("packages/app");
+    let math = test_directory.join("packages/math");
+    let other = test_directory.join("packages/other");
+
+    let mut app = Package::new(
+        "app".into(),
+        "0.1.0".into(),
+        "author".into(),
+        Some(Source::Path { path: member.clone() }),
+    );
+    app.import_name = Some("app".into());
+    app.dependencies.insert("math".into(), math.display().to_string());
+    app.dependencies
+        .insert("registry".into(), "author-registry@0.1.0".into());
+    let mut local = Package::new(
+        "math".into(),
+        "0.1.0".into(),
+        "author".into(),
+        Some(Source::Path { path: math.clone() }),
+    );
+    local.import_name = Some("math".into());
+    local.dependencies.insert("helper".into(), "author-helper@0.1.0".into());
+    let mut helper = Package::new("helper".into(), "0.1.0".into(), "author".into(), None);
+    helper.import_name = Some("helper".into());
+    let mut registry = Package::new("registry".into(), "0.1.0".into(), "author".into(), None);
+    registry.import_name = Some("registry".into());
+
+    // Another member imports a different package under the same name.
+    let mut other_member = Package::new(
+        "other".into(),
+        "0.1.0".into(),
+        "author".into(),
+        Some(Source::Path { path: other.clone() }),
+    );
+    other_member.import_name = Some("other".into());
+    other_member
+        .dependencies
+        .insert("registry".into(), "author-registry@0.2.0".into());
+    let mut other_registry = Package::new("registry".into(), "0.2.0".into(), "author".into(), None);
+    other_registry.import_name = Some("registry".into());
+
+    let mut lock_file = LockFile::new();
+    lock_file
+        .add_package(app)
+        .add_package(local)
+        .add_package(helper)
+        .add_package(registry)
+        .add_package(other_member)
+        .add_package(other_registry);
+    lock_file.write_to(&test_directory).unwrap();
+
+    // Packages inside the workspace are stored relative to the lock file.
+    let content = fs::read_to_string(test_directory.join("Leo.lock")).unwrap();
+    assert!(content.contains("path = \"./packages/math\""), "{}", content);
+    assert!(!content.contains(&test_directory.display().to_string()), "{}", content);
+
+    let lock_file = LockFile::try_from(test_directory.as_path()).unwrap();
+    assert_eq!(lock_file.package[1].source, Some(Source::Path { path: math.clone() }));
+
+    // Registry packages are read from the `imports/` directory of the workspace.
+    let import_map = lock_file.to_workspace_import_map(&test_directory, &member).unwrap();
+    assert_eq!(import_map["math"], math.display().to_string());
+    assert_eq!(
+        import_map["registry"],
+        test_directory
+            .join("imports/author-registry@0.1.0")
+            .display()
+            .to_string()
+    );
+
+    // The dependencies of dependencies are included, those of other members are not.
+    let names = import_map.keys().collect::<Vec<_>>();
+    assert_eq!(names, vec!["math", "registry", "helper"]);
+
+    let import_map = lock_file.to_workspace_import_map(&test_directory, &other).unwrap();
+    let names = import_map.keys().collect::<Vec<_>>();
+    assert_eq!(names, vec!["registry"]);
+    assert!(import_map["registry"].ends_with("author-registry@0.2.0"));
+}
@@ -17,7 +17,7 @@
 // Tests for package manifest
 
 use crate::test_dir;
-use leo_package::root::{Manifest, MANIFEST_FILENAME};
+use leo_package::root::{Curve, Dependency, Manifest, ProvingSystem, Target, MANIFEST_FILENAME};
 
 use std::{
     convert::TryFrom,
@@ -167,3 +167,96 @@ fn test_manifest_refactors() {
     // Check that the manifest file remote has been updated.
     assert!(remote_is_updated(&manifest_path));
 }
+
+#[test]
+fn test_manifest_dependency_sources() {
+    let test_directory = test_dir();
+    let manifest_path = test_directory.join(MANIFEST_FILENAME);
+    let mut file = File::create(&manifest_path).unwrap();
+    file.write_all(
+        br#"[project]
+name = "test-package"
+version = "0.1.0"
+description = "Testing dependency sources."
+license = "MIT"
+
+[dependencies]
+registry = { author = "author", package = "registry-package", version = "0.1.0" }
+local = { path = "../shared-crypto" }
+repository = { git = "file:///tmp/remote-package", rev = "main" }
+"#,
+    )
+    .unwrap();
+
+    let manifest = Manifest::try_from(manifest_path.as_path()).unwrap();
+    let dependencies = manifest.get_package_dependencies().unwrap();
+
+    assert_eq!(
+        dependencies["registry"],
+        Dependency::Registry {
+            author: "author".to_string(),
+            version: "0.1.0".to_string(),
+            package: "registry-package".to_string(),
+        }
+    );
+    assert_eq!(
+        dependencies["local"],
+        Dependency::Path {
+            path: PathBuf::from("../shared-crypto"),
+        }
+    );
+    assert_eq!(
+        dependencies["repository"],
+        Dependency::Git {
+            git: "file:///tmp/remote-package".to_string(),
+            rev: Some("main".to_string()),
+        }
+    );
+}
+
+#[test]
+fn test_manifest_target() {
+    let test_directory = test_dir();
+    let manifest_path = test_directory.join(MANIFEST_FILENAME);
+    let write_manifest = |target: &str| {
+        let mut file = File::create(&manifest_path).unwrap();
+        file.write_all(
+            format!(
+                r#"[project]
+name = "test-package"
+version = "0.1.0"
+description = "Testing targets."
+license = "MIT"
+{}"#,
+                target
+            )
+            .as_bytes(),
+        )
+        .unwrap();
+    };
+
+    // Manifests without a target default to Groth16 over BLS12-377.
+    write_manifest("");
+    let manifest = Manifest::try_from(manifest_path.as_path()).unwrap();
+    assert_eq!(manifest.get_target(), Target::default());
+
+    write_manifest("\n[target]\ncurve = \"bw6_761\"\nproving_system = \"marlin\"\n");
+    let manifest = Manifest::try_from(manifest_path.as_path()).unwrap();
+    assert_eq!(manifest.get_target().curve, Curve::Bw6_761);
+    assert_eq!(manifest.get_target().proving_system, ProvingSystem::Marlin);
+
+    write_manifest("\n[target]\ncurve = \"bn254\"\n");
+    assert!(Manifest::try_from(manifest_path.as_path()).is_err());
+
+    write_manifest("\n[target]\nsystem = \"groth16\"\n");
+    assert!(Manifest::try_from(manifest_path.as_path()).is_err());
+
+    // The template writes the target back out.
+    Manifest::new("test-package", None)
+        .unwrap()
+        .write_to(&test_directory)
+        .unwrap();
+    let manifest_string = read_manifest_file(&manifest_path);
+    assert!(manifest_string.contains("curve = \"bls12_377\""));
+    assert!(manifest_string.contains("proving_system = \"groth16\""));
+}
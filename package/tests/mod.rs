@@ -16,8 +16,14 @@
 
 #![allow(clippy::module_inception)]
 
+pub mod envelope;
+pub mod graph;
 pub mod initialize;
+pub mod lock_file;
 pub mod manifest;
+pub mod resolver;
+pub mod workspace;
+pub mod zip;
 
 use lazy_static::lazy_static;
 use std::{
@@ -0,0 +1,280 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+// Tests for the dependency resolver
+
+use crate::test_dir;
+use leo_errors::{CliError, Result};
+use leo_package::{
+    resolver::{Fetched, PackageIndex, Pinned, Resolver},
+    root::{
+        lock_file::{LockFile, Source},
+        Dependency,
+    },
+};
+
+use indexmap::IndexMap;
+use semver::Version;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// The commit at the head of the default branch of every test git repository.
+const HEAD: &str = "1111111111111111111111111111111111111111";
+
+/// A registry of packages stored under a test directory.
+struct TestIndex {
+    path: PathBuf,
+    versions: IndexMap<String, Vec<Version>>,
+    /// The revisions git dependencies were fetched at.
+    revs: Vec<Option<String>>,
+    /// The packages whose versions were listed.
+    listed: Vec<String>,
+}
+
+impl TestIndex {
+    fn new(path: &Path) -> Self {
+        Self {
+            path: path.join("registry"),
+            versions: IndexMap::new(),
+            revs: Vec::new(),
+            listed: Vec::new(),
+        }
+    }
+
+    /// Publishes a version of a package with the given registry dependencies.
+    fn publish(&mut self, package: &str, version: &str, dependencies: &[(&str, &str)]) -> &mut Self {
+        let path = self.path.join(format!("author-{}@{}", package, version));
+        fs::create_dir_all(path.join("src")).unwrap();
+        fs::write(path.join("src/main.leo"), "").unwrap();
+        fs::write(
+            path.join("Leo.toml"),
+            format!(
+                "[project]\nname = \"{}\"\nversion = \"{}\"\n\n[dependencies]\n{}",
+                package,
+                version,
+                dependencies
+                    .iter()
+                    .map(|(name, requirement)| format!(
+                        "{} = {{ author = \"author\", package = \"{}\", version = \"{}\" }}\n",
+                        name, name, requirement
+                    ))
+                    .collect::<String>()
+            ),
+        )
+        .unwrap();
+
+        self.versions
+            .entry(package.to_string())
+            .or_default()
+            .push(Version::parse(version).unwrap());
+        self
+    }
+}
+
+impl PackageIndex for TestIndex {
+    fn versions(&mut self, _author: &str, package: &str) -> Result<Vec<Version>> {
+        self.listed.push(package.to_string());
+        Ok(self.versions.get(package).cloned().unwrap_or_default())
+    }
+
+    fn fetch(&mut self, _import_name: &str, package: &Pinned) -> Result<Fetched> {
+        match package {
+            Pinned::Registry { package, version, .. } => Ok(Fetched {
+                path: self.path.join(format!("author-{}@{}", package, version)),
+                source: None,
+            }),
+            Pinned::Git { git, rev } => {
+                self.revs.push(rev.clone());
+                Ok(Fetched {
+                    path: self.path.join("author-lib@1.0.0"),
+                    source: Some(Source::Git {
+                        git: git.clone(),
+                        rev: rev.clone().unwrap_or_else(|| HEAD.to_string()),
+                    }),
+                })
+            }
+            _ => Err(CliError::package_not_found().into()),
+        }
+    }
+}
+
+fn dependencies(dependencies: &[(&str, &str)]) -> IndexMap<String, Dependency> {
+    dependencies
+        .iter()
+        .map(|(name, requirement)| {
+            (
+                name.to_string(),
+                Dependency::Registry {
+                    author: "author".to_string(),
+                    version: requirement.to_string(),
+                    package: name.to_string(),
+                },
+            )
+        })
+        .collect()
+}
+
+fn version_of<'a>(lock_file: &'a LockFile, package: &str) -> Vec<&'a str> {
+    lock_file
+        .package
+        .iter()
+        .filter(|record| record.name == package)
+        .map(|record| record.version.as_str())
+        .collect()
+}
+
+/// Publishes `lib` in several versions, and `a` and `b` which depend on it.
+fn registry(path: &Path, a_requirement: &str, b_requirement: &str) -> TestIndex {
+    let mut index = TestIndex::new(path);
+    for version in ["1.0.0", "1.1.0", "1.2.0", "1.2.5", "1.3.0", "2.0.0"] {
+        index.publish("lib", version, &[]);
+    }
+    index
+        .publish("a", "0.1.0", &[("lib", a_requirement)])
+        .publish("b", "0.1.0", &[("lib", b_requirement)]);
+    index
+}
+
+#[test]
+fn test_resolver_unifies_versions() {
+    let test_directory = test_dir();
+    let mut index = registry(&test_directory, "^1.1", "~1.2");
+
+    // `a` is walked first and selects 1.3.0, which `b` then rules out.
+    let lock_file = Resolver::new(&mut index)
+        .resolve("app", &test_directory, &dependencies(&[("a", "0.1"), ("b", "0.1")]))
+        .unwrap();
+
+    assert_eq!(version_of(&lock_file, "lib"), vec!["1.2.5"]);
+    assert_eq!(lock_file.to_import_map().unwrap()["lib"], "author-lib@1.2.5");
+
+    // The direct dependencies of the root package are recorded too.
+    let roots = lock_file.dependencies.keys().collect::<Vec<_>>();
+    assert_eq!(roots, vec!["a", "b"]);
+}
+
+#[test]
+fn test_resolver_prefers_locked_versions() {
+    let test_directory = test_dir();
+    let mut index = registry(&test_directory, "^1.1", "~1.2");
+    let manifest = dependencies(&[("a", "0.1"), ("b", "0.1")]);
+
+    let mut locked = Resolver::new(&mut index)
+        .resolve("app", &test_directory, &manifest)
+        .unwrap();
+    locked
+        .package
+        .iter_mut()
+        .filter(|record| record.name == "lib")
+        .for_each(|record| record.version = "1.2.0".to_string());
+
+    // A locked version is kept while it satisfies the requirements.
+    let lock_file = Resolver::new(&mut index)
+        .with_lock_file(&locked)
+        .resolve("app", &test_directory, &manifest)
+        .unwrap();
+    assert_eq!(version_of(&lock_file, "lib"), vec!["1.2.0"]);
+
+    // Updating ignores the lock file.
+    let lock_file = Resolver::new(&mut index)
+        .resolve("app", &test_directory, &manifest)
+        .unwrap();
+    assert_eq!(version_of(&lock_file, "lib"), vec!["1.2.5"]);
+}
+
+#[test]
+fn test_resolver_keeps_exact_versions() {
+    let test_directory = test_dir();
+    let mut index = registry(&test_directory, "^1.1", "~1.2");
+
+    // A bare version is exact, as it was before requirements, and needs no listing.
+    let lock_file = Resolver::new(&mut index)
+        .resolve("app", &test_directory, &dependencies(&[("lib", "1.0.0")]))
+        .unwrap();
+    assert_eq!(version_of(&lock_file, "lib"), vec!["1.0.0"]);
+    assert!(index.listed.is_empty(), "{:?}", index.listed);
+
+    // So is a locked version.
+    let lock_file = Resolver::new(&mut index)
+        .with_lock_file(&lock_file)
+        .resolve("app", &test_directory, &dependencies(&[("lib", "^1.0")]))
+        .unwrap();
+    assert_eq!(version_of(&lock_file, "lib"), vec!["1.0.0"]);
+    assert!(index.listed.is_empty(), "{:?}", index.listed);
+
+    let lock_file = Resolver::new(&mut index)
+        .resolve("app", &test_directory, &dependencies(&[("lib", "^1.0")]))
+        .unwrap();
+    assert_eq!(version_of(&lock_file, "lib"), vec!["1.3.0"]);
+    assert_eq!(index.listed, vec!["lib"]);
+}
+
+#[test]
+fn test_resolver_keeps_locked_git_commits() {
+    let test_directory = test_dir();
+    let mut index = registry(&test_directory, "^1.1", "~1.2");
+    let mut manifest = IndexMap::new();
+    manifest.insert(
+        "lib".to_string(),
+        Dependency::Git {
+            git: "https://example.com/lib.git".to_string(),
+            rev: None,
+        },
+    );
+
+    let mut locked = Resolver::new(&mut index)
+        .resolve("app", &test_directory, &manifest)
+        .unwrap();
+    let commit = "2222222222222222222222222222222222222222";
+    locked.package[0].source = Some(Source::Git {
+        git: "https://example.com/lib.git".to_string(),
+        rev: commit.to_string(),
+    });
+
+    // The locked commit is checked out instead of the default branch.
+    Resolver::new(&mut index)
+        .with_lock_file(&locked)
+        .resolve("app", &test_directory, &manifest)
+        .unwrap();
+    assert_eq!(index.revs, vec![None, Some(commit.to_string())]);
+}
+
+#[test]
+fn test_resolver_reports_conflicts() {
+    let test_directory = test_dir();
+    let mut index = registry(&test_directory, "^1.1", "^2.0");
+
+    let error = Resolver::new(&mut index)
+        .resolve("app", &test_directory, &dependencies(&[("a", "0.1"), ("b", "0.1")]))
+        .unwrap_err()
+        .to_string();
+
+    assert!(error.contains("author/lib"), "{}", error);
+    assert!(error.contains("app -> a -> lib requires `^1.1`"), "{}", error);
+    assert!(error.contains("app -> b -> lib requires `^2.0`"), "{}", error);
+}
+
+#[test]
+fn test_resolver_rejects_invalid_requirements() {
+    let test_directory = test_dir();
+    let mut index = registry(&test_directory, "^1.1", "~1.2");
+
+    assert!(Resolver::new(&mut index)
+        .resolve("app", &test_directory, &dependencies(&[("a", "not a version")]))
+        .is_err());
+}
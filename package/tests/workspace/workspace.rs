@@ -0,0 +1,95 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+// Tests for package workspaces
+
+use crate::test_dir;
+use leo_package::root::{Manifest, Workspace, MANIFEST_FILENAME};
+
+use std::{convert::TryFrom, fs, path::Path};
+
+/// Create a package with a manifest and no sources.
+fn create_package(path: &Path, name: &str) {
+    fs::create_dir_all(path).unwrap();
+    fs::write(
+        path.join(MANIFEST_FILENAME),
+        format!("[project]\nname = \"{}\"\nversion = \"0.1.0\"\n", name),
+    )
+    .unwrap();
+}
+
+#[test]
+fn test_workspace_members() {
+    let test_directory = test_dir().canonicalize().unwrap();
+    fs::write(
+        test_directory.join(MANIFEST_FILENAME),
+        "[workspace]\nmembers = [\"packages/*\", \"tools\"]\n",
+    )
+    .unwrap();
+    create_package(&test_directory.join("packages/math"), "math");
+    create_package(&test_directory.join("packages/app"), "app");
+    create_package(&test_directory.join("tools"), "tools");
+    create_package(&test_directory.join("other"), "other");
+    fs::create_dir_all(test_directory.join("packages/notes")).unwrap();
+
+    let workspace = Workspace::read_from(&test_directory).unwrap().unwrap();
+    assert_eq!(
+        workspace.member_paths(&test_directory).unwrap(),
+        vec![
+            test_directory.join("packages/app"),
+            test_directory.join("packages/math"),
+            test_directory.join("tools"),
+        ]
+    );
+
+    // Members find the workspace root, other packages do not.
+    let root = Some(test_directory.clone());
+    assert_eq!(Workspace::find_root(&test_directory).unwrap(), root);
+    assert_eq!(
+        Workspace::find_root(&test_directory.join("packages/math")).unwrap(),
+        root
+    );
+    assert_eq!(Workspace::find_root(&test_directory.join("tools")).unwrap(), root);
+    assert_eq!(Workspace::find_root(&test_directory.join("other")).unwrap(), None);
+}
+
+#[test]
+fn test_workspace_missing_member() {
+    let test_directory = test_dir();
+    fs::write(
+        test_directory.join(MANIFEST_FILENAME),
+        "[workspace]\nmembers = [\"missing\"]\n",
+    )
+    .unwrap();
+
+    let workspace = Workspace::read_from(&test_directory).unwrap().unwrap();
+    assert!(workspace.member_paths(&test_directory).is_err());
+}
+
+#[test]
+fn test_workspace_package_root() {
+    let test_directory = test_dir();
+    fs::write(
+        test_directory.join(MANIFEST_FILENAME),
+        "[project]\nname = \"root\"\nversion = \"0.1.0\"\n\n[workspace]\nmembers = []\n",
+    )
+    .unwrap();
+
+    // A package can be the root of a workspace as well.
+    let manifest = Manifest::try_from(test_directory.as_path()).unwrap();
+    assert_eq!(manifest.get_workspace(), Some(Workspace { members: vec![] }));
+    assert!(Workspace::find_root(&test_directory).unwrap().is_some());
+}
@@ -0,0 +1,145 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+// Tests for package zip files
+
+use crate::test_dir;
+use leo_package::{outputs::OutputsDirectory, root::ZipFile};
+
+use std::{
+    fs,
+    path::{Path, PathBuf},
+};
+
+/// Create the given files, relative to the package directory.
+fn create_files(path: &Path, files: &[(&str, &str)]) {
+    for (name, content) in files {
+        let file = path.join(name);
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(file, content).unwrap();
+    }
+}
+
+fn paths(names: &[&str]) -> Vec<PathBuf> {
+    names.iter().map(PathBuf::from).collect()
+}
+
+#[test]
+fn test_zip_default_files() {
+    let test_directory = test_dir();
+    create_files(
+        &test_directory,
+        &[
+            ("Leo.toml", "[project]\nname = \"zipped\"\nversion = \"0.1.0\"\n"),
+            ("README.md", "# zipped\n"),
+            ("notes.txt", "not published\n"),
+            ("inputs/zipped.in", "[main]\n"),
+            ("src/main.leo", "function main() {}\n"),
+            ("outputs/zipped.sum", "checksum\n"),
+            ("imports/other/src/main.leo", "function main() {}\n"),
+        ],
+    );
+
+    let files = ZipFile::new("zipped").included_files(&test_directory).unwrap();
+    assert_eq!(
+        files,
+        paths(&["Leo.toml", "README.md", "inputs/zipped.in", "src/main.leo"])
+    );
+}
+
+#[test]
+fn test_zip_exclude_and_leoignore() {
+    let test_directory = test_dir();
+    create_files(
+        &test_directory,
+        &[
+            (
+                "Leo.toml",
+                "[project]\nname = \"zipped\"\nversion = \"0.1.0\"\nexclude = [\"src/generated/\"]\n",
+            ),
+            (".leoignore", "# scratch files\nscratch.leo\n\n/README.md\n"),
+            ("README.md", "# zipped\n"),
+            ("src/main.leo", "function main() {}\n"),
+            ("src/scratch.leo", "function main() {}\n"),
+            ("src/nested/scratch.leo", "function main() {}\n"),
+            ("src/generated/big.leo", "function main() {}\n"),
+        ],
+    );
+
+    let files = ZipFile::new("zipped").included_files(&test_directory).unwrap();
+    assert_eq!(files, paths(&["Leo.toml", "src/main.leo"]));
+}
+
+#[test]
+fn test_zip_include() {
+    let test_directory = test_dir();
+    create_files(&test_directory, &[
+        (
+            "Leo.toml",
+            "[project]\nname = \"zipped\"\nversion = \"0.1.0\"\ninclude = [\"src/*.leo\", \"docs/\", \"outputs/\"]\n",
+        ),
+        ("README.md", "# zipped\n"),
+        ("docs/guide.md", "# guide\n"),
+        ("inputs/zipped.in", "[main]\n"),
+        ("src/main.leo", "function main() {}\n"),
+        ("src/nested/lib.leo", "function main() {}\n"),
+        ("outputs/zipped.sum", "checksum\n"),
+    ]);
+
+    // Build outputs are never published, even when included.
+    let files = ZipFile::new("zipped").included_files(&test_directory).unwrap();
+    assert_eq!(files, paths(&["Leo.toml", "docs/guide.md", "src/main.leo"]));
+}
+
+#[test]
+fn test_zip_invalid_pattern() {
+    let test_directory = test_dir();
+    create_files(
+        &test_directory,
+        &[(
+            "Leo.toml",
+            "[project]\nname = \"zipped\"\nversion = \"0.1.0\"\nexclude = [\"src/[\"]\n",
+        )],
+    );
+
+    assert!(ZipFile::new("zipped").included_files(&test_directory).is_err());
+}
+
+#[test]
+fn test_zip_is_reproducible() {
+    let files = [
+        ("Leo.toml", "[project]\nname = \"zipped\"\nversion = \"0.1.0\"\n"),
+        ("src/main.leo", "function main() {}\n"),
+        ("src/b.leo", "function b() {}\n"),
+        ("src/a.leo", "function a() {}\n"),
+        ("inputs/zipped.in", "[main]\n"),
+    ];
+    let mut reversed = files;
+    reversed.reverse();
+
+    let mut zips = Vec::new();
+    for files in [&files, &reversed] {
+        let test_directory = test_dir();
+        create_files(&test_directory, files);
+        OutputsDirectory::create(&test_directory).unwrap();
+
+        let zip_file = ZipFile::new("zipped");
+        zip_file.write(&test_directory).unwrap();
+        zips.push(fs::read(zip_file.get_file_path(&test_directory)).unwrap());
+    }
+
+    assert_eq!(zips[0], zips[1]);
+}
@@ -0,0 +1,265 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//!
+//! The binary encoding of a synthesized circuit.
+//!
+//! The encoding starts with the `LEOC` magic bytes, a little-endian `u16` version and a flags
+//! byte, followed by the number of public variables, private variables and constraints. Then come
+//! the public and private assignments, if the `ASSIGNMENTS` flag is set, and the `a`, `b` and `c`
+//! rows of every constraint. A row is its number of terms followed by the sparse
+//! `(index, coefficient)` terms.
+//!
+//! Counts and indices are LEB128 varints, an index is shifted left by one with the low bit set for
+//! private variables. A field element is its canonical little-endian bytes without the trailing
+//! zero bytes, prefixed with their length, so the small coefficients most rows use take two bytes.
+//!
+
+use crate::{CircuitSynthesizer, ConstraintSet};
+use leo_errors::{LeoError, SnarkVMError};
+
+use eyre::eyre;
+use snarkvm_curves::traits::PairingEngine;
+use snarkvm_fields::{PrimeField, Zero};
+use snarkvm_r1cs::{Index, OptionalVec};
+use snarkvm_utilities::biginteger::BigInteger;
+use std::io::{BufRead, Read, Write};
+
+pub const BINARY_CIRCUIT_MAGIC: &[u8; 4] = b"LEOC";
+pub const BINARY_CIRCUIT_VERSION: u16 = 2;
+
+/// Set if the encoding holds the assignments of the variables, not just the shape of the circuit.
+const ASSIGNMENTS: u8 = 1;
+
+/// Returns `true` if the reader is at the start of a binary circuit, without consuming any bytes.
+pub fn is_binary_circuit<R: BufRead>(reader: &mut R) -> Result<bool, LeoError> {
+    Ok(reader.fill_buf().map_err(error)?.starts_with(BINARY_CIRCUIT_MAGIC))
+}
+
+/// Returns `true` if the binary circuit at the start of the reader holds its assignments,
+/// without consuming any bytes.
+pub fn binary_circuit_has_assignments<R: BufRead>(reader: &mut R) -> Result<bool, LeoError> {
+    let header = reader.fill_buf().map_err(error)?;
+    match header.get(BINARY_CIRCUIT_MAGIC.len() + 2) {
+        Some(flags) => Ok(flags & ASSIGNMENTS != 0),
+        None => Err(error("unexpected end of file")),
+    }
+}
+
+impl<E: PairingEngine> CircuitSynthesizer<E> {
+    /// Writes the binary encoding of the circuit and its assignments to the given writer.
+    pub fn write_binary<W: Write>(&self, writer: W) -> Result<(), LeoError> {
+        self.write_binary_with(writer, true)
+    }
+
+    /// Writes the binary encoding of the shape of the circuit, without its assignments.
+    pub fn write_binary_shape<W: Write>(&self, writer: W) -> Result<(), LeoError> {
+        self.write_binary_with(writer, false)
+    }
+
+    fn write_binary_with<W: Write>(&self, mut writer: W, with_assignments: bool) -> Result<(), LeoError> {
+        writer.write_all(BINARY_CIRCUIT_MAGIC).map_err(error)?;
+        writer.write_all(&BINARY_CIRCUIT_VERSION.to_le_bytes()).map_err(error)?;
+        let flags = if with_assignments { ASSIGNMENTS } else { 0 };
+        writer.write_all(&[flags]).map_err(error)?;
+
+        write_varint(&mut writer, self.public_variables.len() as u64)?;
+        write_varint(&mut writer, self.private_variables.len() as u64)?;
+        write_varint(&mut writer, self.constraints.len() as u64)?;
+
+        if with_assignments {
+            for value in self.public_variables.iter().chain(self.private_variables.iter()) {
+                write_field(&mut writer, value)?;
+            }
+        }
+
+        for constraint in self.constraints.iter() {
+            for row in [&constraint.at, &constraint.bt, &constraint.ct] {
+                write_varint(&mut writer, row.len() as u64)?;
+                for (coeff, index) in row {
+                    let index = match index {
+                        Index::Public(i) => (*i as u64) << 1,
+                        Index::Private(i) => (*i as u64) << 1 | 1,
+                    };
+                    write_varint(&mut writer, index)?;
+                    write_field(&mut writer, coeff)?;
+                }
+            }
+        }
+
+        writer.flush().map_err(error)
+    }
+
+    ///
+    /// Reads a circuit and its assignments from their binary encoding.
+    ///
+    /// The variables of an encoded shape are all zero, until a witness is set.
+    ///
+    pub fn read_binary<R: Read>(mut reader: R) -> Result<Self, LeoError> {
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic).map_err(error)?;
+        if &magic != BINARY_CIRCUIT_MAGIC {
+            return Err(error("not a binary circuit file"));
+        }
+
+        let mut version = [0u8; 2];
+        reader.read_exact(&mut version).map_err(error)?;
+        let version = u16::from_le_bytes(version);
+        if version != BINARY_CIRCUIT_VERSION {
+            return Err(error(format!(
+                "binary circuit version {} is not supported, expected version {}",
+                version, BINARY_CIRCUIT_VERSION
+            )));
+        }
+
+        let mut flags = [0u8; 1];
+        reader.read_exact(&mut flags).map_err(error)?;
+        let with_assignments = flags[0] & ASSIGNMENTS != 0;
+
+        let num_public_variables = read_varint(&mut reader)? as usize;
+        let num_private_variables = read_varint(&mut reader)? as usize;
+        let num_constraints = read_varint(&mut reader)? as usize;
+
+        // The counts are untrusted, so they are not used to reserve memory up front.
+        let read_assignment = |reader: &mut R| {
+            if with_assignments {
+                read_field(reader)
+            } else {
+                Ok(E::Fr::zero())
+            }
+        };
+        let mut public_variables = OptionalVec::default();
+        for _ in 0..num_public_variables {
+            public_variables.insert(read_assignment(&mut reader)?);
+        }
+        let mut private_variables = OptionalVec::default();
+        for _ in 0..num_private_variables {
+            private_variables.insert(read_assignment(&mut reader)?);
+        }
+
+        let mut constraints = OptionalVec::default();
+        for _ in 0..num_constraints {
+            let mut rows: [Vec<(E::Fr, Index)>; 3] = Default::default();
+            for row in rows.iter_mut() {
+                let num_terms = read_varint(&mut reader)?;
+                for _ in 0..num_terms {
+                    let index = read_varint(&mut reader)?;
+                    let index = match index & 1 {
+                        0 => Index::Public((index >> 1) as usize),
+                        _ => Index::Private((index >> 1) as usize),
+                    };
+                    row.push((read_field(&mut reader)?, index));
+                }
+            }
+
+            let [at, bt, ct] = rows;
+            constraints.insert(ConstraintSet { at, bt, ct });
+        }
+
+        Ok(Self {
+            constraints,
+            public_variables,
+            private_variables,
+            namespaces: Default::default(),
+            profile: None,
+        })
+    }
+}
+
+pub(crate) fn error(error: impl std::fmt::Display) -> LeoError {
+    LeoError::from(SnarkVMError::from(eyre!("{}", error)))
+}
+
+fn write_varint<W: Write>(writer: &mut W, mut value: u64) -> Result<(), LeoError> {
+    loop {
+        let byte = (value & 0x7f) as u8;
+        value >>= 7;
+        if value == 0 {
+            return writer.write_all(&[byte]).map_err(error);
+        }
+        writer.write_all(&[byte | 0x80]).map_err(error)?;
+    }
+}
+
+fn read_varint<R: Read>(reader: &mut R) -> Result<u64, LeoError> {
+    let mut value = 0u64;
+    for shift in (0..64).step_by(7) {
+        let mut byte = [0u8; 1];
+        reader.read_exact(&mut byte).map_err(error)?;
+        value |= u64::from(byte[0] & 0x7f) << shift;
+        if byte[0] & 0x80 == 0 {
+            return Ok(value);
+        }
+    }
+    Err(error("varint is longer than 64 bits"))
+}
+
+fn write_field<W: Write, F: PrimeField>(writer: &mut W, value: &F) -> Result<(), LeoError> {
+    let mut bytes = field_to_le_bytes(value);
+    while bytes.last() == Some(&0) {
+        bytes.pop();
+    }
+
+    writer.write_all(&[bytes.len() as u8]).map_err(error)?;
+    writer.write_all(&bytes).map_err(error)
+}
+
+fn read_field<R: Read, F: PrimeField>(reader: &mut R) -> Result<F, LeoError> {
+    let mut length = [0u8; 1];
+    reader.read_exact(&mut length).map_err(error)?;
+    let length = length[0] as usize;
+    if length > field_size::<F>() {
+        return Err(error("field element is longer than the field modulus"));
+    }
+
+    let mut bytes = vec![0u8; length];
+    reader.read_exact(&mut bytes).map_err(error)?;
+    field_from_le_bytes(&bytes)
+}
+
+/// The number of bytes of a field element in its canonical little-endian form.
+pub(crate) fn field_size<F: PrimeField>() -> usize {
+    F::BigInteger::NUM_LIMBS * 8
+}
+
+/// Returns the canonical little-endian bytes of a field element, `field_size` bytes long.
+pub(crate) fn field_to_le_bytes<F: PrimeField>(value: &F) -> Vec<u8> {
+    value
+        .to_repr()
+        .as_ref()
+        .iter()
+        .flat_map(|limb| limb.to_le_bytes())
+        .collect()
+}
+
+/// Reads a field element from at most `field_size` canonical little-endian bytes.
+pub(crate) fn field_from_le_bytes<F: PrimeField>(bytes: &[u8]) -> Result<F, LeoError> {
+    if bytes.len() > field_size::<F>() {
+        return Err(error("field element is longer than the field modulus"));
+    }
+
+    let mut padded = vec![0u8; field_size::<F>()];
+    padded[..bytes.len()].copy_from_slice(bytes);
+
+    let mut repr = F::BigInteger::default();
+    for (limb, chunk) in repr.as_mut().iter_mut().zip(padded.chunks(8)) {
+        let mut limb_bytes = [0u8; 8];
+        limb_bytes.copy_from_slice(chunk);
+        *limb = u64::from_le_bytes(limb_bytes);
+    }
+
+    F::from_repr(repr).ok_or_else(|| error("field element is not less than the field modulus"))
+}
@@ -0,0 +1,295 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//!
+//! The circom `.r1cs` and `.wtns` binary formats, read by snarkjs and other circom tooling.
+//!
+//! Both files are a magic, a `u32` version and a list of sections, each a `u32` type and a `u64`
+//! size. Wire 0 is the constant one, followed by the public and then the private variables. Field
+//! elements are canonical little-endian bytes, padded to the size of the field.
+//!
+
+use crate::{error, field_from_le_bytes, field_size, field_to_le_bytes, CircuitSynthesizer, ConstraintSet};
+use leo_errors::LeoError;
+
+use snarkvm_curves::traits::PairingEngine;
+use snarkvm_fields::{FieldParameters, One, PrimeField};
+use snarkvm_r1cs::{Index, OptionalVec};
+use std::io::{Read, Write};
+
+pub const R1CS_MAGIC: &[u8; 4] = b"r1cs";
+pub const R1CS_VERSION: u32 = 1;
+pub const WTNS_MAGIC: &[u8; 4] = b"wtns";
+pub const WTNS_VERSION: u32 = 2;
+
+const R1CS_HEADER_SECTION: u32 = 1;
+const R1CS_CONSTRAINTS_SECTION: u32 = 2;
+const R1CS_WIRE_TO_LABEL_SECTION: u32 = 3;
+const WTNS_HEADER_SECTION: u32 = 1;
+const WTNS_WITNESS_SECTION: u32 = 2;
+
+impl<E: PairingEngine> CircuitSynthesizer<E> {
+    /// The number of wires, counting the constant one.
+    pub(crate) fn num_wires(&self) -> usize {
+        1 + self.public_variables.len() + self.private_variables.len()
+    }
+
+    /// Returns the wire of a variable, private variables follow all public ones.
+    pub(crate) fn wire(&self, index: Index) -> usize {
+        match index {
+            Index::Public(i) => i,
+            Index::Private(i) => 1 + self.public_variables.len() + i,
+        }
+    }
+
+    /// Writes the constraints of the circuit in the circom `.r1cs` format.
+    pub fn write_r1cs<W: Write>(&self, mut writer: W) -> Result<(), LeoError> {
+        let field_size = field_size::<E::Fr>();
+        let num_wires = self.num_wires();
+
+        writer.write_all(R1CS_MAGIC).map_err(error)?;
+        write_u32(&mut writer, R1CS_VERSION)?;
+        write_u32(&mut writer, 3)?;
+
+        write_section_header(&mut writer, R1CS_HEADER_SECTION, 32 + field_size)?;
+        write_u32(&mut writer, field_size as u32)?;
+        writer.write_all(&modulus::<E::Fr>()).map_err(error)?;
+        write_u32(&mut writer, num_wires as u32)?;
+        // Leo has no public outputs, and its private inputs are not told apart from other wires.
+        write_u32(&mut writer, 0)?;
+        write_u32(&mut writer, self.public_variables.len() as u32)?;
+        write_u32(&mut writer, 0)?;
+        writer.write_all(&(num_wires as u64).to_le_bytes()).map_err(error)?;
+        write_u32(&mut writer, self.constraints.len() as u32)?;
+
+        // The section size is computed up front, so the constraints are streamed in a single pass.
+        let constraints_size = self
+            .constraints
+            .iter()
+            .flat_map(|constraint| [&constraint.at, &constraint.bt, &constraint.ct])
+            .map(|row| 4 + row.len() * (4 + field_size))
+            .sum();
+        write_section_header(&mut writer, R1CS_CONSTRAINTS_SECTION, constraints_size)?;
+        for constraint in self.constraints.iter() {
+            for row in [&constraint.at, &constraint.bt, &constraint.ct] {
+                write_u32(&mut writer, row.len() as u32)?;
+                for (coeff, index) in row {
+                    write_u32(&mut writer, self.wire(*index) as u32)?;
+                    writer.write_all(&field_to_le_bytes(coeff)).map_err(error)?;
+                }
+            }
+        }
+
+        // Every wire is its own label.
+        write_section_header(&mut writer, R1CS_WIRE_TO_LABEL_SECTION, num_wires * 8)?;
+        for wire in 0..num_wires as u64 {
+            writer.write_all(&wire.to_le_bytes()).map_err(error)?;
+        }
+
+        writer.flush().map_err(error)
+    }
+
+    /// Writes the assignment of every wire in the circom `.wtns` format.
+    pub fn write_wtns<W: Write>(&self, mut writer: W) -> Result<(), LeoError> {
+        let field_size = field_size::<E::Fr>();
+        let num_wires = self.num_wires();
+
+        writer.write_all(WTNS_MAGIC).map_err(error)?;
+        write_u32(&mut writer, WTNS_VERSION)?;
+        write_u32(&mut writer, 2)?;
+
+        write_section_header(&mut writer, WTNS_HEADER_SECTION, 8 + field_size)?;
+        write_u32(&mut writer, field_size as u32)?;
+        writer.write_all(&modulus::<E::Fr>()).map_err(error)?;
+        write_u32(&mut writer, num_wires as u32)?;
+
+        write_section_header(&mut writer, WTNS_WITNESS_SECTION, num_wires * field_size)?;
+        let one = E::Fr::one();
+        let values = std::iter::once(&one)
+            .chain(self.public_variables.iter())
+            .chain(self.private_variables.iter());
+        for value in values {
+            writer.write_all(&field_to_le_bytes(value)).map_err(error)?;
+        }
+
+        writer.flush().map_err(error)
+    }
+
+    /// Reads a circuit from its circom `.r1cs` constraints and `.wtns` assignments.
+    pub fn read_circom<R1: Read, R2: Read>(r1cs: R1, wtns: R2) -> Result<Self, LeoError> {
+        let sections = read_sections(r1cs, R1CS_MAGIC, R1CS_VERSION)?;
+        let mut header = Bytes::new(section(&sections, R1CS_HEADER_SECTION)?);
+        read_field_header::<E::Fr>(&mut header)?;
+        let num_wires = header.u32()? as usize;
+        let num_public_variables = header.u32()? as usize + header.u32()? as usize;
+        header.u32()?;
+        header.u64()?;
+        let num_constraints = header.u32()? as usize;
+        if num_public_variables >= num_wires {
+            return Err(error("the public variables do not fit in the wires of the circuit"));
+        }
+
+        let sections_wtns = read_sections(wtns, WTNS_MAGIC, WTNS_VERSION)?;
+        let mut header = Bytes::new(section(&sections_wtns, WTNS_HEADER_SECTION)?);
+        read_field_header::<E::Fr>(&mut header)?;
+        if header.u32()? as usize != num_wires {
+            return Err(error("the witness does not assign every wire of the circuit"));
+        }
+
+        let mut witness = Bytes::new(section(&sections_wtns, WTNS_WITNESS_SECTION)?);
+        if witness.field::<E::Fr>()? != E::Fr::one() {
+            return Err(error("the first wire of the witness is not the constant one"));
+        }
+        let mut public_variables = OptionalVec::default();
+        for _ in 0..num_public_variables {
+            public_variables.insert(witness.field()?);
+        }
+        let mut private_variables = OptionalVec::default();
+        for _ in num_public_variables + 1..num_wires {
+            private_variables.insert(witness.field()?);
+        }
+
+        let index = |wire: usize| match wire {
+            wire if wire <= num_public_variables => Ok(Index::Public(wire)),
+            wire if wire < num_wires => Ok(Index::Private(wire - num_public_variables - 1)),
+            wire => Err(error(format!("wire {} is not in the circuit", wire))),
+        };
+
+        let mut rows = Bytes::new(section(&sections, R1CS_CONSTRAINTS_SECTION)?);
+        let mut constraints = OptionalVec::default();
+        for _ in 0..num_constraints {
+            let mut lcs: [Vec<(E::Fr, Index)>; 3] = Default::default();
+            for lc in lcs.iter_mut() {
+                let num_terms = rows.u32()?;
+                for _ in 0..num_terms {
+                    let index = index(rows.u32()? as usize)?;
+                    lc.push((rows.field()?, index));
+                }
+            }
+
+            let [at, bt, ct] = lcs;
+            constraints.insert(ConstraintSet { at, bt, ct });
+        }
+
+        Ok(Self {
+            constraints,
+            public_variables,
+            private_variables,
+            namespaces: Default::default(),
+            profile: None,
+        })
+    }
+}
+
+/// Returns the canonical little-endian bytes of the field modulus.
+pub(crate) fn modulus<F: PrimeField>() -> Vec<u8> {
+    F::Parameters::MODULUS
+        .as_ref()
+        .iter()
+        .flat_map(|limb| limb.to_le_bytes())
+        .collect()
+}
+
+fn write_u32<W: Write>(writer: &mut W, value: u32) -> Result<(), LeoError> {
+    writer.write_all(&value.to_le_bytes()).map_err(error)
+}
+
+fn write_section_header<W: Write>(writer: &mut W, section_type: u32, size: usize) -> Result<(), LeoError> {
+    write_u32(writer, section_type)?;
+    writer.write_all(&(size as u64).to_le_bytes()).map_err(error)
+}
+
+/// Reads the sections of a circom file, checking its magic and version.
+fn read_sections<R: Read>(mut reader: R, magic: &[u8; 4], version: u32) -> Result<Vec<(u32, Vec<u8>)>, LeoError> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes).map_err(error)?;
+    let mut bytes = Bytes::new(&bytes);
+
+    if bytes.take(4)? != magic {
+        return Err(error(format!("not a `{}` file", String::from_utf8_lossy(magic))));
+    }
+    let found = bytes.u32()?;
+    if found != version {
+        return Err(error(format!(
+            "`{}` version {} is not supported, expected version {}",
+            String::from_utf8_lossy(magic),
+            found,
+            version
+        )));
+    }
+
+    let num_sections = bytes.u32()?;
+    let mut sections = Vec::new();
+    for _ in 0..num_sections {
+        let section_type = bytes.u32()?;
+        let size = bytes.u64()? as usize;
+        sections.push((section_type, bytes.take(size)?.to_vec()));
+    }
+    Ok(sections)
+}
+
+fn section(sections: &[(u32, Vec<u8>)], section_type: u32) -> Result<&[u8], LeoError> {
+    sections
+        .iter()
+        .find(|(found, _)| *found == section_type)
+        .map(|(_, bytes)| bytes.as_slice())
+        .ok_or_else(|| error(format!("section {} is missing", section_type)))
+}
+
+/// Reads a field size and modulus, and checks that they are those of the given field.
+fn read_field_header<F: PrimeField>(bytes: &mut Bytes) -> Result<(), LeoError> {
+    let size = bytes.u32()? as usize;
+    if size != field_size::<F>() || bytes.take(size)? != modulus::<F>().as_slice() {
+        return Err(error("the circuit is not defined over the scalar field of the curve"));
+    }
+    Ok(())
+}
+
+/// A cursor over a byte slice that fails instead of reading past the end.
+struct Bytes<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Bytes<'a> {
+    fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes }
+    }
+
+    fn take(&mut self, length: usize) -> Result<&'a [u8], LeoError> {
+        if length > self.bytes.len() {
+            return Err(error("unexpected end of file"));
+        }
+        let (taken, rest) = self.bytes.split_at(length);
+        self.bytes = rest;
+        Ok(taken)
+    }
+
+    fn u32(&mut self) -> Result<u32, LeoError> {
+        let mut bytes = [0u8; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn u64(&mut self) -> Result<u64, LeoError> {
+        let mut bytes = [0u8; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn field<F: PrimeField>(&mut self) -> Result<F, LeoError> {
+        field_from_le_bytes(self.take(field_size::<F>())?)
+    }
+}
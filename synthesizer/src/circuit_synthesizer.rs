@@ -14,9 +14,12 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
+use crate::{ConstraintProfile, Cost};
 use snarkvm_curves::traits::PairingEngine;
-use snarkvm_fields::Field;
-use snarkvm_r1cs::{ConstraintSystem, Index, LinearCombination, OptionalVec, SynthesisError, Variable};
+use snarkvm_fields::{Field, One, Zero};
+use snarkvm_r1cs::{
+    ConstraintSynthesizer, ConstraintSystem, Index, LinearCombination, OptionalVec, SynthesisError, Variable,
+};
 
 #[derive(Default)]
 pub struct Namespace {
@@ -45,12 +48,15 @@ pub struct CircuitSynthesizer<E: PairingEngine> {
     // Constraints
     pub constraints: OptionalVec<ConstraintSet<E>>,
 
-    // Assignments of variables
+    // Assignments of variables, public variable 0 is the constant one so `public_variables[i]` is `Public(i + 1)`
     pub public_variables: OptionalVec<E::Fr>,
     pub private_variables: OptionalVec<E::Fr>,
 
     // Technical namespaces used to remove of out-of-scope objects.
     pub namespaces: Vec<Namespace>,
+
+    // Cost of every namespace path, only recorded when profiling.
+    pub profile: Option<ConstraintProfile>,
 }
 
 impl<E: PairingEngine> Default for CircuitSynthesizer<E> {
@@ -60,6 +66,7 @@ impl<E: PairingEngine> Default for CircuitSynthesizer<E> {
             public_variables: Default::default(),
             private_variables: Default::default(),
             namespaces: Default::default(),
+            profile: None,
         }
     }
 }
@@ -78,6 +85,12 @@ impl<E: PairingEngine> ConstraintSystem<E::Fr> for CircuitSynthesizer<E> {
         if let Some(ref mut ns) = self.namespaces.last_mut() {
             ns.private_var_indices.push(index);
         }
+        if let Some(ref mut profile) = self.profile {
+            profile.record(Cost {
+                private_variables: 1,
+                ..Default::default()
+            });
+        }
         Ok(Variable::new_unchecked(Index::Private(index)))
     }
 
@@ -92,7 +105,13 @@ impl<E: PairingEngine> ConstraintSystem<E::Fr> for CircuitSynthesizer<E> {
         if let Some(ref mut ns) = self.namespaces.last_mut() {
             ns.public_var_indices.push(index);
         }
-        Ok(Variable::new_unchecked(Index::Public(index)))
+        if let Some(ref mut profile) = self.profile {
+            profile.record(Cost {
+                public_variables: 1,
+                ..Default::default()
+            });
+        }
+        Ok(Variable::new_unchecked(Index::Public(index + 1)))
     }
 
     #[inline]
@@ -113,17 +132,32 @@ impl<E: PairingEngine> ConstraintSystem<E::Fr> for CircuitSynthesizer<E> {
         if let Some(ref mut ns) = self.namespaces.last_mut() {
             ns.constraint_indices.push(index);
         }
+        if let Some(ref mut profile) = self.profile {
+            profile.record(Cost {
+                constraints: 1,
+                ..Default::default()
+            });
+        }
     }
 
-    fn push_namespace<NR, N>(&mut self, _: N)
+    fn push_namespace<NR, N>(&mut self, name_fn: N)
     where
         NR: AsRef<str>,
         N: FnOnce() -> NR,
     {
         self.namespaces.push(Namespace::default());
+
+        // Names are only formatted when profiling, since most namespaces are never looked at.
+        if let Some(ref mut profile) = self.profile {
+            profile.push_namespace(name_fn().as_ref().to_string());
+        }
     }
 
     fn pop_namespace(&mut self) {
+        if let Some(ref mut profile) = self.profile {
+            profile.pop_namespace();
+        }
+
         // Todo @ljedrz: Fix constraint system optimizations.
         // if let Some(ns) = self.namespaces.pop() {
         //     for idx in ns.constraint_indices {
@@ -161,6 +195,80 @@ impl<E: PairingEngine> ConstraintSystem<E::Fr> for CircuitSynthesizer<E> {
     }
 }
 
+impl<E: PairingEngine> CircuitSynthesizer<E> {
+    /// Returns the variable with the given index, if it was allocated.
+    fn variable(&self, index: Index) -> Option<Variable> {
+        self.value(index).map(|_| Variable::new_unchecked(index))
+    }
+
+    /// Returns the assignment of the variable with the given index, if it was allocated.
+    fn value(&self, index: Index) -> Option<E::Fr> {
+        match index {
+            Index::Public(0) => Some(E::Fr::one()),
+            Index::Public(i) if i <= self.public_variables.len() => Some(self.public_variables[i - 1]),
+            Index::Private(i) if i < self.private_variables.len() => Some(self.private_variables[i]),
+            _ => None,
+        }
+    }
+
+    fn linear_combination(&self, terms: &[(E::Fr, Index)]) -> Result<LinearCombination<E::Fr>, SynthesisError> {
+        let mut lc = LinearCombination::zero();
+        for (coeff, index) in terms {
+            lc += (*coeff, self.variable(*index).ok_or(SynthesisError::AssignmentMissing)?);
+        }
+        Ok(lc)
+    }
+
+    fn evaluate(&self, terms: &[(E::Fr, Index)]) -> Option<E::Fr> {
+        let mut sum = E::Fr::zero();
+        for (coeff, index) in terms {
+            sum += *coeff * self.value(*index)?;
+        }
+        Some(sum)
+    }
+
+    /// Returns the index of the first constraint that the assignments do not satisfy, if any.
+    pub fn which_is_unsatisfied(&self) -> Option<usize> {
+        self.constraints.iter().enumerate().find_map(|(i, constraint)| {
+            let a = self.evaluate(&constraint.at);
+            let b = self.evaluate(&constraint.bt);
+            let c = self.evaluate(&constraint.ct);
+            match (a, b, c) {
+                (Some(a), Some(b), Some(c)) if a * b == c => None,
+                _ => Some(i),
+            }
+        })
+    }
+}
+
+///
+/// Replays the variables and constraints of a synthesized circuit on another constraint system.
+///
+/// This lets a circuit read back from a `SerializedCircuit` be set up and proven without the
+/// program that produced it. Variables are allocated in their original order, so the replayed
+/// circuit matches the keys generated from the program.
+///
+impl<E: PairingEngine> ConstraintSynthesizer<E::Fr> for CircuitSynthesizer<E> {
+    fn generate_constraints<CS: ConstraintSystem<E::Fr>>(&self, cs: &mut CS) -> Result<(), SynthesisError> {
+        for (i, value) in self.public_variables.iter().enumerate() {
+            cs.alloc_input(|| format!("public variable {}", i), || Ok(*value))?;
+        }
+
+        for (i, value) in self.private_variables.iter().enumerate() {
+            cs.alloc(|| format!("private variable {}", i), || Ok(*value))?;
+        }
+
+        for (i, constraint) in self.constraints.iter().enumerate() {
+            let a = self.linear_combination(&constraint.at)?;
+            let b = self.linear_combination(&constraint.bt)?;
+            let c = self.linear_combination(&constraint.ct)?;
+            cs.enforce(|| format!("constraint {}", i), |_| a, |_| b, |_| c);
+        }
+
+        Ok(())
+    }
+}
+
 fn push_constraints<F: Field>(l: LinearCombination<F>, constraint: &mut Vec<(F, Index)>) {
     for (var, coeff) in l.as_ref() {
         match var.get_unchecked() {
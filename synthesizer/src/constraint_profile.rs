@@ -0,0 +1,224 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Write},
+    ops::AddAssign,
+};
+
+/// The root frame of the profile, the main function is synthesized outside of any namespace.
+pub const MAIN_FRAME: &str = "main";
+
+/// The number of constraints and variables allocated by a part of the circuit.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Cost {
+    pub constraints: usize,
+    pub public_variables: usize,
+    pub private_variables: usize,
+}
+
+impl AddAssign for Cost {
+    fn add_assign(&mut self, other: Self) {
+        self.constraints += other.constraints;
+        self.public_variables += other.public_variables;
+        self.private_variables += other.private_variables;
+    }
+}
+
+/// Where in the program the cost of a namespace path is attributed to.
+#[derive(Clone, Debug, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Location {
+    /// The innermost function call, or the main function.
+    pub function: String,
+    /// The innermost statement within that function, as `line:column`.
+    pub statement: Option<String>,
+    /// The line of the innermost namespace with a span within that function.
+    pub line: Option<usize>,
+}
+
+///
+/// Records the constraints and variables allocated under every namespace path.
+///
+/// The compiler names its namespaces after the construct and span that creates them,
+/// such as `function call double 3:12` or `statement 4:5`, which lets the cost be
+/// attributed back to functions, statements and source lines.
+///
+#[derive(Clone, Debug, Default)]
+pub struct ConstraintProfile {
+    path: Vec<String>,
+    costs: BTreeMap<Vec<String>, Cost>,
+}
+
+impl ConstraintProfile {
+    pub fn push_namespace(&mut self, name: String) {
+        self.path.push(name);
+    }
+
+    pub fn pop_namespace(&mut self) {
+        self.path.pop();
+    }
+
+    /// Adds the given cost to the current namespace path.
+    pub fn record(&mut self, cost: Cost) {
+        if let Some(total) = self.costs.get_mut(&self.path) {
+            *total += cost;
+        } else {
+            self.costs.insert(self.path.clone(), cost);
+        }
+    }
+
+    /// Returns the cost of the whole circuit.
+    pub fn total(&self) -> Cost {
+        let mut total = Cost::default();
+        for cost in self.costs.values() {
+            total += *cost;
+        }
+        total
+    }
+
+    /// Returns the cost allocated by each function, excluding the functions it calls.
+    pub fn by_function(&self) -> Vec<(String, Cost)> {
+        self.group_by(|location| Some(location.function.clone()))
+    }
+
+    /// Returns the cost of each statement, as `function line:column`.
+    pub fn by_statement(&self) -> Vec<(String, Cost)> {
+        self.group_by(|location| {
+            location
+                .statement
+                .as_ref()
+                .map(|statement| format!("{} {}", location.function, statement))
+        })
+    }
+
+    /// Returns the cost of each source line, as `function line`.
+    pub fn by_line(&self) -> Vec<(String, Cost)> {
+        self.group_by(|location| location.line.map(|line| format!("{} {}", location.function, line)))
+    }
+
+    ///
+    /// Returns the constraints of every namespace path in the folded stack format
+    /// read by flamegraph tools, one `frame;frame;frame count` line per path.
+    ///
+    pub fn to_folded_stacks(&self) -> String {
+        let mut folded = String::new();
+        for (path, cost) in self.costs.iter().filter(|(_, cost)| cost.constraints > 0) {
+            folded.push_str(MAIN_FRAME);
+            for frame in path {
+                // Semicolons separate the frames, so they cannot appear inside one.
+                write!(folded, ";{}", frame.replace(';', ",")).unwrap();
+            }
+            writeln!(folded, " {}", cost.constraints).unwrap();
+        }
+        folded
+    }
+
+    /// Sums the cost of the namespace paths by key, sorted by descending constraints.
+    fn group_by(&self, key: impl Fn(&Location) -> Option<String>) -> Vec<(String, Cost)> {
+        let mut groups: BTreeMap<String, Cost> = BTreeMap::new();
+        for (path, cost) in &self.costs {
+            if let Some(key) = key(&locate(path)) {
+                *groups.entry(key).or_default() += *cost;
+            }
+        }
+
+        let mut groups = groups.into_iter().collect::<Vec<_>>();
+        groups.sort_by(|(a_key, a), (b_key, b)| b.constraints.cmp(&a.constraints).then(a_key.cmp(b_key)));
+        groups
+    }
+}
+
+impl fmt::Display for ConstraintProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let sections = [
+            ("Function", self.by_function()),
+            ("Statement", self.by_statement()),
+            ("Line", self.by_line()),
+        ];
+
+        for (index, (title, rows)) in sections.iter().enumerate() {
+            if index > 0 {
+                writeln!(f)?;
+            }
+
+            let width = rows
+                .iter()
+                .map(|(key, _)| key.len())
+                .chain(Some(title.len()))
+                .max()
+                .unwrap_or(0);
+            writeln!(
+                f,
+                "{:<width$}  {:>12}  {:>10}  {:>10}",
+                title,
+                "constraints",
+                "public",
+                "private",
+                width = width
+            )?;
+            for (key, cost) in rows {
+                writeln!(
+                    f,
+                    "{:<width$}  {:>12}  {:>10}  {:>10}",
+                    key,
+                    cost.constraints,
+                    cost.public_variables,
+                    cost.private_variables,
+                    width = width
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Attributes a namespace path to the innermost function, statement and line that contain it.
+fn locate(path: &[String]) -> Location {
+    let mut location = Location {
+        function: MAIN_FRAME.to_string(),
+        statement: None,
+        line: None,
+    };
+
+    for frame in path {
+        if let Some(call) = frame.strip_prefix("function call ") {
+            // The span of a call is in the caller, the statements and lines that follow are in the callee.
+            location.function = call.split_whitespace().next().unwrap_or_default().to_string();
+            location.statement = None;
+            location.line = None;
+            continue;
+        }
+
+        if let Some(statement) = frame.strip_prefix("statement ") {
+            location.statement = Some(statement.to_string());
+        }
+
+        if let Some(line) = span_line(frame) {
+            location.line = Some(line);
+        }
+    }
+
+    location
+}
+
+/// Returns the line of a namespace named after a span, which ends with `line:column`.
+fn span_line(frame: &str) -> Option<usize> {
+    let (line, column) = frame.rsplit(' ').next()?.split_once(':')?;
+    column.parse::<usize>().ok()?;
+    line.parse().ok()
+}
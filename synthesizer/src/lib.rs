@@ -16,17 +16,31 @@
 
 #![doc = include_str!("../README.md")]
 
+pub mod binary_circuit;
+pub use self::binary_circuit::*;
+
+pub mod circom;
+pub use self::circom::*;
+
 pub mod circuit_synthesizer;
 pub use self::circuit_synthesizer::*;
 
+pub mod constraint_profile;
+pub use self::constraint_profile::*;
+
 pub mod serialized_circuit;
 pub use self::serialized_circuit::*;
 
 pub mod summarized_circuit;
 pub use self::summarized_circuit::*;
 
+pub mod zkinterface;
+
 pub mod serialized_field;
 pub use self::serialized_field::*;
 
 pub mod serialized_index;
 pub use self::serialized_index::*;
+
+pub mod serialized_witness;
+pub use self::serialized_witness::*;
@@ -19,9 +19,10 @@ use std::convert::TryFrom;
 use eyre::eyre;
 use serde::{Deserialize, Serialize};
 use snarkvm_curves::{bls12_377::Bls12_377, traits::PairingEngine};
+use snarkvm_fields::Zero;
 use snarkvm_r1cs::{ConstraintSystem, Index, OptionalVec};
 
-use crate::{CircuitSynthesizer, ConstraintSet, SerializedField, SerializedIndex};
+use crate::{CircuitSynthesizer, ConstraintSet, SerializedField, SerializedIndex, SerializedWitness};
 use leo_errors::{LeoError, SnarkVMError};
 
 #[derive(Serialize, Deserialize)]
@@ -30,7 +31,10 @@ pub struct SerializedCircuit {
     pub num_private_variables: usize,
     pub num_constraints: usize,
 
+    // The assignments are left out of circuit files, which only hold the shape of the circuit
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub public_variables: Vec<SerializedField>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub private_variables: Vec<SerializedField>,
 
     pub at: Vec<Vec<(SerializedField, SerializedIndex)>>,
@@ -46,6 +50,22 @@ impl SerializedCircuit {
     pub fn from_json_string(json: &str) -> Result<Self, LeoError> {
         serde_json::from_str(json).map_err(|e| LeoError::from(SnarkVMError::from(eyre!(e))))
     }
+
+    /// Returns `true` if the circuit holds the assignments of its variables, not just its shape.
+    pub fn has_assignments(&self) -> bool {
+        self.num_public_variables + self.num_private_variables == 0
+            || !self.public_variables.is_empty()
+            || !self.private_variables.is_empty()
+    }
+
+    /// Splits the circuit into its shape and the witness that assigns its variables.
+    pub fn split(mut self) -> (Self, SerializedWitness) {
+        let witness = SerializedWitness {
+            public_variables: std::mem::take(&mut self.public_variables),
+            private_variables: std::mem::take(&mut self.private_variables),
+        };
+        (self, witness)
+    }
 }
 
 impl<E: PairingEngine> From<CircuitSynthesizer<E>> for SerializedCircuit {
@@ -118,24 +138,44 @@ impl TryFrom<SerializedCircuit> for CircuitSynthesizer<Bls12_377> {
     type Error = LeoError;
 
     fn try_from(serialized: SerializedCircuit) -> Result<CircuitSynthesizer<Bls12_377>, Self::Error> {
-        // Deserialize assignments
-        fn get_deserialized_assignments(
-            assignments: &[SerializedField],
-        ) -> Result<OptionalVec<<Bls12_377 as PairingEngine>::Fr>, LeoError> {
-            let mut deserialized = OptionalVec::with_capacity(assignments.len());
-
-            for serialized_assignment in assignments {
-                let field = <Bls12_377 as PairingEngine>::Fr::try_from(serialized_assignment)
-                    .map_err(|e| LeoError::from(SnarkVMError::from(eyre!(e))))?;
-
-                deserialized.insert(field);
-            }
-
-            Ok(deserialized)
+        // Check the counts against the lists, so a truncated file is rejected before indexing them
+        let has_assignments = serialized.has_assignments();
+        if has_assignments
+            && (serialized.public_variables.len() != serialized.num_public_variables
+                || serialized.private_variables.len() != serialized.num_private_variables)
+        {
+            return Err(LeoError::from(SnarkVMError::from(eyre!(
+                "the number of variables does not match the serialized assignments"
+            ))));
+        }
+        if [&serialized.at, &serialized.bt, &serialized.ct]
+            .iter()
+            .any(|terms| terms.len() != serialized.num_constraints)
+        {
+            return Err(LeoError::from(SnarkVMError::from(eyre!(
+                "the number of constraints does not match the serialized linear combinations"
+            ))));
         }
 
-        let public_variables = get_deserialized_assignments(&serialized.public_variables)?;
-        let private_variables = get_deserialized_assignments(&serialized.private_variables)?;
+        // Deserialize assignments, a circuit shape has every variable zero until its witness is set
+        let (public_variables, private_variables) = if has_assignments {
+            (
+                deserialize_assignments(&serialized.public_variables)?,
+                deserialize_assignments(&serialized.private_variables)?,
+            )
+        } else {
+            let zeros = |count| {
+                let mut zeros = OptionalVec::default();
+                for _ in 0..count {
+                    zeros.insert(<Bls12_377 as PairingEngine>::Fr::zero());
+                }
+                zeros
+            };
+            (
+                zeros(serialized.num_public_variables),
+                zeros(serialized.num_private_variables),
+            )
+        };
 
         // Deserialize constraints
         fn get_deserialized_constraints(
@@ -178,6 +218,23 @@ impl TryFrom<SerializedCircuit> for CircuitSynthesizer<Bls12_377> {
             public_variables,
             private_variables,
             namespaces: Default::default(),
+            profile: None,
         })
     }
 }
+
+/// Deserializes the assignments of variables in their original order.
+pub(crate) fn deserialize_assignments(
+    assignments: &[SerializedField],
+) -> Result<OptionalVec<<Bls12_377 as PairingEngine>::Fr>, LeoError> {
+    let mut deserialized = OptionalVec::with_capacity(assignments.len());
+
+    for serialized_assignment in assignments {
+        let field = <Bls12_377 as PairingEngine>::Fr::try_from(serialized_assignment)
+            .map_err(|e| LeoError::from(SnarkVMError::from(eyre!(e))))?;
+
+        deserialized.insert(field);
+    }
+
+    Ok(deserialized)
+}
@@ -0,0 +1,78 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+use eyre::eyre;
+use serde::{Deserialize, Serialize};
+use snarkvm_curves::{bls12_377::Bls12_377, traits::PairingEngine};
+
+use crate::{deserialize_assignments, CircuitSynthesizer, SerializedField};
+use leo_errors::{LeoError, SnarkVMError};
+
+///
+/// The assignments of the variables of a circuit for one set of inputs.
+///
+/// A witness is kept apart from the shape of its circuit, so the same circuit and keys prove
+/// every witness generated for it.
+///
+#[derive(Serialize, Deserialize)]
+pub struct SerializedWitness {
+    pub public_variables: Vec<SerializedField>,
+    pub private_variables: Vec<SerializedField>,
+}
+
+impl SerializedWitness {
+    pub fn to_json_string(&self) -> Result<String, LeoError> {
+        serde_json::to_string_pretty(&self).map_err(|e| LeoError::from(SnarkVMError::from(eyre!(e))))
+    }
+
+    pub fn from_json_string(json: &str) -> Result<Self, LeoError> {
+        serde_json::from_str(json).map_err(|e| LeoError::from(SnarkVMError::from(eyre!(e))))
+    }
+}
+
+impl<E: PairingEngine> From<&CircuitSynthesizer<E>> for SerializedWitness {
+    fn from(synthesizer: &CircuitSynthesizer<E>) -> Self {
+        Self {
+            public_variables: synthesizer.public_variables.iter().map(SerializedField::from).collect(),
+            private_variables: synthesizer
+                .private_variables
+                .iter()
+                .map(SerializedField::from)
+                .collect(),
+        }
+    }
+}
+
+impl CircuitSynthesizer<Bls12_377> {
+    /// Replaces the assignments of the circuit with the given witness of the same circuit.
+    pub fn set_witness(&mut self, witness: &SerializedWitness) -> Result<(), LeoError> {
+        if witness.public_variables.len() != self.public_variables.len()
+            || witness.private_variables.len() != self.private_variables.len()
+        {
+            return Err(LeoError::from(SnarkVMError::from(eyre!(
+                "the witness assigns {} public and {} private variables, but the circuit has {} and {}",
+                witness.public_variables.len(),
+                witness.private_variables.len(),
+                self.public_variables.len(),
+                self.private_variables.len()
+            ))));
+        }
+
+        self.public_variables = deserialize_assignments(&witness.public_variables)?;
+        self.private_variables = deserialize_assignments(&witness.private_variables)?;
+        Ok(())
+    }
+}
@@ -14,12 +14,14 @@
 // You should have received a copy of the GNU General Public License
 // along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
 
-use serde::{Deserialize, Serialize};
+use serde::{Deserialize, Serialize, Serializer};
 use sha2::{Digest, Sha256};
+use snarkvm_curves::traits::PairingEngine;
+use snarkvm_r1cs::{ConstraintSystem, Index};
 
-use crate::SerializedCircuit;
+use crate::{CircuitSynthesizer, ConstraintSet, SerializedCircuit, SerializedField, SerializedIndex};
 
-#[derive(Serialize, Deserialize, PartialEq)]
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
 pub struct SummarizedCircuit {
     pub num_public_variables: usize,
     pub num_private_variables: usize,
@@ -39,8 +41,21 @@ fn hash_field(input: &str) -> String {
     hex::encode(&output[..])
 }
 
+impl SummarizedCircuit {
+    /// Returns a SHA256 checksum of the circuit shape.
+    pub fn checksum(&self) -> String {
+        hash_field(&serde_json::to_string(self).expect("failed to serialize summary"))
+    }
+}
+
 impl From<SerializedCircuit> for SummarizedCircuit {
     fn from(other: SerializedCircuit) -> Self {
+        Self::from(&other)
+    }
+}
+
+impl From<&SerializedCircuit> for SummarizedCircuit {
+    fn from(other: &SerializedCircuit) -> Self {
         Self {
             num_public_variables: other.num_public_variables,
             num_private_variables: other.num_private_variables,
@@ -55,3 +70,65 @@ impl From<SerializedCircuit> for SummarizedCircuit {
         }
     }
 }
+
+impl<E: PairingEngine> From<&CircuitSynthesizer<E>> for SummarizedCircuit {
+    ///
+    /// Summarizes the circuit without serializing it first.
+    ///
+    /// Each matrix is hashed as it is written, in the same JSON as a `SerializedCircuit`, so the
+    /// summary of a circuit does not depend on whether it was read back from a file.
+    ///
+    fn from(other: &CircuitSynthesizer<E>) -> Self {
+        let hash_matrix = |row| {
+            let mut hasher = Sha256::new();
+            serde_json::to_writer(
+                &mut hasher,
+                &Matrix {
+                    synthesizer: other,
+                    row,
+                },
+            )
+            .expect("failed to serialize matrix");
+            hex::encode(&hasher.finalize()[..])
+        };
+
+        Self {
+            num_public_variables: other.num_public_variables(),
+            num_private_variables: other.num_private_variables(),
+            num_constraints: other.num_constraints(),
+            at: hash_matrix(|constraint| &constraint.at),
+            bt: hash_matrix(|constraint| &constraint.bt),
+            ct: hash_matrix(|constraint| &constraint.ct),
+        }
+    }
+}
+
+/// Returns one row of a constraint matrix.
+type RowSelector<E> = fn(&ConstraintSet<E>) -> &Vec<(<E as PairingEngine>::Fr, Index)>;
+
+/// A constraint matrix of a circuit, serialized like the matrices of a `SerializedCircuit`.
+struct Matrix<'a, E: PairingEngine> {
+    synthesizer: &'a CircuitSynthesizer<E>,
+    row: RowSelector<E>,
+}
+
+impl<'a, E: PairingEngine> Serialize for Matrix<'a, E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let rows =
+            (0..self.synthesizer.num_constraints()).map(|i| Row::<E>((self.row)(&self.synthesizer.constraints[i])));
+        serializer.collect_seq(rows)
+    }
+}
+
+/// A row of a constraint matrix, serialized like the rows of a `SerializedCircuit`.
+struct Row<'a, E: PairingEngine>(&'a [(E::Fr, Index)]);
+
+impl<'a, E: PairingEngine> Serialize for Row<'a, E> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(
+            self.0
+                .iter()
+                .map(|(coeff, index)| (SerializedField::from(coeff), SerializedIndex::from(*index))),
+        )
+    }
+}
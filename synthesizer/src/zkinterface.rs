@@ -0,0 +1,192 @@
+// Copyright (C) 2019-2021 Aleo Systems Inc.
+// This file is part of the Leo library.
+
+// The Leo library is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// The Leo library is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with the Leo library. If not, see <https://www.gnu.org/licenses/>.
+
+//!
+//! The zkInterface format, a stream of size-prefixed FlatBuffers messages.
+//!
+//! A circuit is written as a `CircuitHeader` with the public variables, a `ConstraintSystem` and
+//! a `Witness` with the private variables. Variable ids are the wires of the circom formats, with
+//! the constant one as variable 0. Values are canonical little-endian bytes, padded to the size
+//! of the field.
+//!
+//! The messages are built and parsed by the `zkinterface` crate, the reference implementation
+//! of the format. It does not verify the messages it parses, so only read files from trusted
+//! producers.
+//!
+
+use crate::{error, field_from_le_bytes, field_size, field_to_le_bytes, CircuitSynthesizer, ConstraintSet};
+use leo_errors::LeoError;
+
+use snarkvm_curves::traits::PairingEngine;
+use snarkvm_fields::{One, PrimeField};
+use snarkvm_r1cs::{Index, OptionalVec};
+use std::io::{Read, Write};
+use zkinterface::{BilinearConstraint, CircuitHeader, ConstraintSystem, Message, Reader, Variables, Witness};
+
+impl<E: PairingEngine> CircuitSynthesizer<E> {
+    /// Writes the circuit and its assignments as zkInterface messages.
+    pub fn write_zkif<W: Write>(&self, mut writer: W) -> Result<(), LeoError> {
+        let num_public_variables = self.public_variables.len() as u64;
+
+        let header = CircuitHeader {
+            instance_variables: variables((1..=num_public_variables).collect(), self.public_variables.iter()),
+            free_variable_id: self.num_wires() as u64,
+            field_maximum: Some(field_to_le_bytes(&-E::Fr::one())),
+            configuration: None,
+        };
+        header.write_into(&mut writer).map_err(error)?;
+
+        let constraint_system = ConstraintSystem {
+            constraints: self
+                .constraints
+                .iter()
+                .map(|constraint| {
+                    let [a, b, c] = [&constraint.at, &constraint.bt, &constraint.ct].map(|row| {
+                        let ids = row.iter().map(|(_, index)| self.wire(*index) as u64).collect();
+                        variables(ids, row.iter().map(|(coeff, _)| coeff))
+                    });
+                    BilinearConstraint {
+                        linear_combination_a: a,
+                        linear_combination_b: b,
+                        linear_combination_c: c,
+                    }
+                })
+                .collect(),
+        };
+        constraint_system.write_into(&mut writer).map_err(error)?;
+
+        let witness = Witness {
+            assigned_variables: variables(
+                (num_public_variables + 1..self.num_wires() as u64).collect(),
+                self.private_variables.iter(),
+            ),
+        };
+        witness.write_into(&mut writer).map_err(error)?;
+
+        writer.flush().map_err(error)
+    }
+
+    /// Reads a circuit and its assignments from zkInterface messages.
+    pub fn read_zkif<R: Read>(reader: R) -> Result<Self, LeoError> {
+        let mut messages = Reader::new();
+        messages.read_from(reader).map_err(error)?;
+
+        let mut header = None;
+        let mut assignments = Vec::new();
+        let mut rows = Vec::new();
+
+        for message in &messages.messages {
+            match Message::from(message.as_slice()) {
+                Message::Header(message) => {
+                    if message.field_maximum != Some(field_to_le_bytes(&-E::Fr::one())) {
+                        return Err(error("the circuit is not defined over the scalar field of the curve"));
+                    }
+                    let instance_variables = read_variables::<E::Fr>(&message.instance_variables)?;
+                    header = Some((message.free_variable_id as usize, instance_variables.len()));
+                    assignments.extend(instance_variables);
+                }
+                Message::ConstraintSystem(message) => {
+                    for constraint in &message.constraints {
+                        rows.push([
+                            read_variables::<E::Fr>(&constraint.linear_combination_a)?,
+                            read_variables::<E::Fr>(&constraint.linear_combination_b)?,
+                            read_variables::<E::Fr>(&constraint.linear_combination_c)?,
+                        ]);
+                    }
+                }
+                Message::Witness(message) => {
+                    assignments.extend(read_variables::<E::Fr>(&message.assigned_variables)?);
+                }
+                Message::Command(_) => return Err(error("command messages are not supported")),
+                Message::Err(message) => return Err(error(message)),
+            }
+        }
+
+        // The instance variables of the header are the public variables, following the constant one.
+        let (num_wires, num_public_variables) = header.ok_or_else(|| error("the circuit header is missing"))?;
+        if num_public_variables >= num_wires {
+            return Err(error("the public variables do not fit in the variables of the circuit"));
+        }
+        let index = |id: usize| match id {
+            id if id <= num_public_variables => Ok(Index::Public(id)),
+            id if id < num_wires => Ok(Index::Private(id - num_public_variables - 1)),
+            id => Err(error(format!("variable {} is not in the circuit", id))),
+        };
+
+        let mut values = vec![None; num_wires];
+        for (id, value) in assignments {
+            index(id)?;
+            values[id] = Some(value);
+        }
+        let mut public_variables = OptionalVec::default();
+        let mut private_variables = OptionalVec::default();
+        for (id, value) in values.into_iter().enumerate().skip(1) {
+            let value = value.ok_or_else(|| error(format!("variable {} is not assigned", id)))?;
+            match index(id)? {
+                Index::Public(_) => public_variables.insert(value),
+                Index::Private(_) => private_variables.insert(value),
+            };
+        }
+
+        let mut constraints = OptionalVec::default();
+        for row in rows {
+            let [at, bt, ct] = row.map(|terms| {
+                terms
+                    .into_iter()
+                    .map(|(id, coeff)| Ok((coeff, index(id)?)))
+                    .collect::<Result<Vec<_>, LeoError>>()
+            });
+            constraints.insert(ConstraintSet {
+                at: at?,
+                bt: bt?,
+                ct: ct?,
+            });
+        }
+
+        Ok(Self {
+            constraints,
+            public_variables,
+            private_variables,
+            namespaces: Default::default(),
+            profile: None,
+        })
+    }
+}
+
+/// Returns the `Variables` of the given variable ids and their values.
+fn variables<'a, F: PrimeField + 'a>(ids: Vec<u64>, values: impl Iterator<Item = &'a F>) -> Variables {
+    Variables {
+        variable_ids: ids,
+        values: Some(values.flat_map(field_to_le_bytes).collect()),
+    }
+}
+
+/// Reads the ids and values of a `Variables` table.
+fn read_variables<F: PrimeField>(variables: &Variables) -> Result<Vec<(usize, F)>, LeoError> {
+    let ids = &variables.variable_ids;
+    if ids.is_empty() {
+        return Ok(Vec::new());
+    }
+    let values = variables.values.as_deref().unwrap_or_default();
+    if values.len() != ids.len() * field_size::<F>() {
+        return Err(error("the values do not match the variable ids"));
+    }
+
+    ids.iter()
+        .zip(values.chunks(field_size::<F>()))
+        .map(|(id, value)| Ok((*id as usize, field_from_le_bytes(value)?)))
+        .collect()
+}